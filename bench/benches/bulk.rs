@@ -0,0 +1,69 @@
+use adobe_swatch_exchange::{Ase, ColorBlock, ColorModel, ColorType, ColorValue, Group};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// A palette of swatches in all color models, in groups of 100.
+fn palette(count: usize) -> Ase {
+    let blocks: Vec<ColorBlock> = (0..count)
+        .map(|index| {
+            let value = index as f32 / count as f32;
+            let color = match index % 4 {
+                0 => ColorValue::Rgb(0.2, 0.5, value),
+                1 => ColorValue::Cmyk(value, 0.4, 0.1, 0.2),
+                2 => ColorValue::Lab(value * 100.0, 20.0, -40.0),
+                _ => ColorValue::Gray(value),
+            };
+            ColorBlock::new(format!("swatch {index}"), color, ColorType::Normal)
+        })
+        .collect();
+    let groups = blocks
+        .chunks(100)
+        .enumerate()
+        .map(|(index, colors)| Group::new(format!("group {index}"), colors.to_vec()))
+        .collect();
+    Ase::new(groups, vec![])
+}
+
+fn convert_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("convert_all");
+    for count in [1_000, 10_000, 100_000] {
+        let ase = palette(count);
+        group.bench_with_input(BenchmarkId::new("sequential", count), &ase, |b, ase| {
+            b.iter(|| ase.clone().convert_all(black_box(ColorModel::Lab)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", count), &ase, |b, ase| {
+            b.iter(|| ase.clone().convert_all_par(black_box(ColorModel::Lab)))
+        });
+    }
+    group.finish();
+}
+
+fn dedup_perceptual(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dedup_perceptual");
+    for count in [500, 2_000, 5_000] {
+        let ase = palette(count);
+        group.bench_with_input(BenchmarkId::new("sequential", count), &ase, |b, ase| {
+            b.iter(|| ase.clone().dedup_perceptual(black_box(1.0)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", count), &ase, |b, ase| {
+            b.iter(|| ase.clone().dedup_perceptual_par(black_box(1.0)))
+        });
+    }
+    group.finish();
+}
+
+fn contrast_audit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contrast_audit");
+    for count in [500, 2_000, 5_000] {
+        let ase = palette(count);
+        group.bench_with_input(BenchmarkId::new("sequential", count), &ase, |b, ase| {
+            b.iter(|| ase.contrast_audit(black_box(1.1)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", count), &ase, |b, ase| {
+            b.iter(|| ase.contrast_audit_par(black_box(1.1)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, convert_all, dedup_perceptual, contrast_audit);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use adobe_swatch_exchange::{
+    create_ase, read_ase, read_ase_borrowed, read_ase_bytes, ColorBlock, ColorType, ColorValue,
+    Group,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Swatches with short ASCII names, like `swatch 42`.
+fn swatches(count: usize) -> Vec<ColorBlock> {
+    (0..count)
+        .map(|index| {
+            ColorBlock::new(
+                format!("swatch {index}"),
+                ColorValue::Rgb(0.2, 0.5, index as f32 / count as f32),
+                ColorType::Normal,
+            )
+        })
+        .collect()
+}
+
+/// A library of 10k single swatches and 40k swatches in groups of 400.
+fn library() -> Vec<u8> {
+    let groups: Vec<Group> = swatches(40_000)
+        .chunks(400)
+        .enumerate()
+        .map(|(index, colors)| Group::new(format!("group {index}"), colors.to_vec()))
+        .collect();
+    create_ase(groups, swatches(10_000))
+}
+
+fn read_single(c: &mut Criterion) {
+    let ase = create_ase(vec![], swatches(10_000));
+    let mut group = c.benchmark_group("read 10k");
+    group.bench_function("read_ase", |b| b.iter(|| read_ase(black_box(&*ase))));
+    group.bench_function("read_ase_bytes", |b| {
+        b.iter(|| read_ase_bytes(black_box(&ase)))
+    });
+    group.bench_function("read_ase_borrowed", |b| {
+        b.iter(|| read_ase_borrowed(black_box(&ase)))
+    });
+    group.finish();
+}
+
+fn read_library(c: &mut Criterion) {
+    let ase = library();
+    let mut group = c.benchmark_group("read 50k");
+    group.bench_function("read_ase", |b| b.iter(|| read_ase(black_box(&*ase))));
+    group.bench_function("read_ase_bytes", |b| {
+        b.iter(|| read_ase_bytes(black_box(&ase)))
+    });
+    group.bench_function("read_ase_borrowed", |b| {
+        b.iter(|| read_ase_borrowed(black_box(&ase)))
+    });
+    group.finish();
+}
+
+fn read_large(c: &mut Criterion) {
+    let ase = create_ase(vec![], swatches(60_000));
+    c.bench_function("read_ase 60k", |b| b.iter(|| read_ase(black_box(&*ase))));
+}
+
+criterion_group!(benches, read_single, read_library, read_large);
+criterion_main!(benches);
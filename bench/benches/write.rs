@@ -0,0 +1,74 @@
+use adobe_swatch_exchange::{create_ase, ColorBlock, ColorType, ColorValue, Group};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+/// Swatches with names of 64 characters, mixing ASCII and non-ASCII characters.
+fn long_named_swatches(count: usize) -> Vec<ColorBlock> {
+    (0..count)
+        .map(|index| {
+            let name = format!("{index:06} swatch with a rather long name — äöü ✓ {index:>14}");
+            ColorBlock::new(
+                name,
+                ColorValue::Rgb(0.2, 0.5, index as f32 / count as f32),
+                ColorType::Normal,
+            )
+        })
+        .collect()
+}
+
+fn write_long_names(c: &mut Criterion) {
+    let colors = long_named_swatches(10_000);
+    c.bench_function("create_ase 10k long names", |b| {
+        b.iter_batched(
+            || colors.clone(),
+            |colors| black_box(create_ase(vec![], colors)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn write_grouped(c: &mut Criterion) {
+    let groups: Vec<Group> = long_named_swatches(10_000)
+        .chunks(100)
+        .enumerate()
+        .map(|(index, colors)| Group::new(format!("group {index}"), colors.to_vec()))
+        .collect();
+    c.bench_function("create_ase 10k grouped", |b| {
+        b.iter_batched(
+            || groups.clone(),
+            |groups| black_box(create_ase(groups, vec![])),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Swatches with short ASCII names, like `swatch 42`.
+fn swatches(count: usize) -> Vec<ColorBlock> {
+    (0..count)
+        .map(|index| {
+            ColorBlock::new(
+                format!("swatch {index}"),
+                ColorValue::Rgb(0.2, 0.5, index as f32 / count as f32),
+                ColorType::Normal,
+            )
+        })
+        .collect()
+}
+
+fn write_many(c: &mut Criterion) {
+    let groups: Vec<Group> = swatches(40_000)
+        .chunks(400)
+        .enumerate()
+        .map(|(index, colors)| Group::new(format!("group {index}"), colors.to_vec()))
+        .collect();
+    let colors = swatches(10_000);
+    c.bench_function("create_ase 50k", |b| {
+        b.iter_batched(
+            || (groups.clone(), colors.clone()),
+            |(groups, colors)| black_box(create_ase(groups, colors)),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, write_long_names, write_grouped, write_many);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+extern crate adobe_swatch_exchange;
+
+use adobe_swatch_exchange::{ColorBlock, Group};
+
+// Checks that `read_ase(create_ase(document)) == document` for generated documents,
+// and that every file, which can be read, is read the same after being written again.
+// The files are generated documents with some bytes flipped, like in the `mutate`
+// target, as arbitrary data is rarely a file, which can be read.
+//
+// The documents are compared by `assert_roundtrip`, which expects names and color
+// types to be equal and color components to be bitwise equal, so `-0.0` and the
+// payload of NaN have to be kept. The only accepted difference is the L* component of
+// Lab colors, which is stored in `[0, 1]` and therefore compared after being scaled
+// down and up again.
+fuzz_target!(|input: (Vec<Group>, Vec<ColorBlock>, Vec<(u16, u8)>)| {
+    let (groups, colors, mutations) = input;
+    if let Err(err) = adobe_swatch_exchange::assert_roundtrip(&groups, &colors) {
+        panic!("generated document: {err}");
+    }
+
+    let mut ase = adobe_swatch_exchange::create_ase(groups, colors);
+    for (position, mask) in mutations {
+        let position = usize::from(position) % ase.len();
+        ase[position] ^= mask;
+    }
+    if let Ok((groups, colors)) = adobe_swatch_exchange::read_ase(&*ase) {
+        if let Err(err) = adobe_swatch_exchange::assert_roundtrip(&groups, &colors) {
+            panic!("read document: {err}");
+        }
+    }
+});
@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+extern crate adobe_swatch_exchange;
+
+use adobe_swatch_exchange::{ColorBlock, Group, ParseOptions};
+
+fuzz_target!(|input: (Vec<Group>, Vec<ColorBlock>, Vec<(u16, u8)>)| {
+    // generates a valid document and flips bytes in it, reaching the recovery paths
+    // of the parser more often than unstructured data does
+    let (groups, colors, mutations) = input;
+    let mut ase = adobe_swatch_exchange::create_ase(groups, colors);
+    for (position, mask) in mutations {
+        let position = usize::from(position) % ase.len();
+        ase[position] ^= mask;
+    }
+    for options in [
+        &ParseOptions::STRICT,
+        &ParseOptions::DEFAULT,
+        &ParseOptions::LENIENT,
+        &ParseOptions {
+            resync_corrupt_blocks: true,
+            ..ParseOptions::LENIENT
+        },
+    ] {
+        let _ = adobe_swatch_exchange::read_ase_with_options(&*ase, options);
+    }
+});
@@ -4,7 +4,8 @@ use libfuzzer_sys::fuzz_target;
 extern crate adobe_swatch_exchange;
 
 fuzz_target!(|data: &[u8]| {
-    // fuzzes ColorBlock::parse(), skipping the magic bytes in `read_ase()`
-    // for this to work, the function has to be temporarily made public
+    // fuzzes the block parsers directly, skipping the magic bytes in `read_ase()`
     let _ = adobe_swatch_exchange::ColorBlock::parse(data);
+    let _ = adobe_swatch_exchange::ColorBlock::from_bytes(data);
+    let _ = adobe_swatch_exchange::Group::from_bytes(data);
 });
@@ -4,7 +4,18 @@ use libfuzzer_sys::fuzz_target;
 extern crate adobe_swatch_exchange;
 
 fuzz_target!(|data: &[u8]| {
-    // fuzzes Group::parse(), skipping the magic bytes in `read_ase()`
-    // for this to work, the function has to be temporarily made public
+    // fuzzes Group::parse() directly, and through `read_ase()`
     let _ = adobe_swatch_exchange::Group::parse(data);
+    let _ = adobe_swatch_exchange::Group::parse_strict(data);
+
+    // wraps the data into a single group start block,
+    // so that the magic bytes in `read_ase()` are always valid
+    let mut ase = Vec::with_capacity(data.len() + 18);
+    ase.extend_from_slice(b"ASEF");
+    ase.extend_from_slice(&0x0001_0000_u32.to_be_bytes());
+    ase.extend_from_slice(&1_u32.to_be_bytes());
+    ase.extend_from_slice(&0xc001_u16.to_be_bytes());
+    ase.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    ase.extend_from_slice(data);
+    let _ = adobe_swatch_exchange::read_ase(&*ase);
 });
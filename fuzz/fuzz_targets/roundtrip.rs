@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+extern crate adobe_swatch_exchange;
+
+use adobe_swatch_exchange::{ColorBlock, Group};
+
+fuzz_target!(|document: (Vec<Group>, Vec<ColorBlock>)| {
+    // every generated document has to be read exactly as it was written
+    let (groups, colors) = document;
+    if let Err(err) = adobe_swatch_exchange::assert_roundtrip(&groups, &colors) {
+        panic!("{err}");
+    }
+});
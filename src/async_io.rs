@@ -0,0 +1,273 @@
+//! Reading and writing .ase files using tokio's [`AsyncRead`] and [`AsyncWrite`], sharing
+//! the parsing and encoding with the sync readers and writers.
+
+use tokio::io::{
+    AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, SeekFrom,
+};
+
+use crate::{
+    events::collect_events,
+    write_header,
+    writer::{self, BlockEncoder, DEFAULT_OPTIONS},
+    ASEError, AseParser, ColorBlock, Group, WriteOptions, WriteReport,
+};
+
+/// Size of the chunks read from the reader.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Reads groups and single colors from `reader`, like [`read_ase`](crate::read_ase).
+///
+/// The data is parsed by an [`AseParser`] as it arrives, so the file is not buffered
+/// first. Reading stops once all blocks declared in the header have been read.
+///
+/// # Errors
+///
+/// This function will return an error if either a read from `reader` fails,
+/// or the ASE file is invalid.
+pub async fn read_ase_async<R: AsyncRead + Unpin>(
+    mut reader: R,
+) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    let mut parser = AseParser::new();
+    let mut events = Vec::new();
+    let mut buf = vec![0; CHUNK_SIZE];
+    while !parser.is_finished() {
+        let read = reader.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        events.extend(parser.feed(buf.get(..read).unwrap_or_default())?);
+    }
+    events.extend(parser.finish()?);
+    Ok(collect_events(events))
+}
+
+/// Writes an Adobe Swatch Exchange (ASE) file to `writer`, like [`write_ase`](crate::write_ase).
+///
+/// # Errors
+///
+/// This function will return an error if either writing to `writer` fails, or a name is too long.
+pub async fn write_ase_async<W: AsyncWrite + Unpin>(
+    mut writer: W,
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+) -> Result<(), ASEError> {
+    let options = WriteOptions::default();
+    let mut blocks = writer::Writer::new(0, &options);
+    write_header(&mut blocks, &groups, &colors)?;
+    writer.write_all(blocks.buf.as_slice()).await?;
+    blocks.buf.clear();
+
+    for group in groups {
+        group.write(&mut blocks)?;
+        writer.write_all(blocks.buf.as_slice()).await?;
+        blocks.buf.clear();
+    }
+    for block in colors {
+        block.write(&mut blocks)?;
+        writer.write_all(blocks.buf.as_slice()).await?;
+        blocks.buf.clear();
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Writes an .ase file block by block to tokio's [`AsyncWrite`], like [`AseWriter`](crate::AseWriter).
+pub struct AsyncAseWriter<'a, W: AsyncWrite + AsyncSeek + Unpin> {
+    sink: W,
+    /// Position of the header in the sink.
+    start: u64,
+    encoder: BlockEncoder<'a>,
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin> AsyncAseWriter<'static, W> {
+    /// Writes the header of the file to `sink`, using the default [`WriteOptions`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing to `sink` fails.
+    pub async fn new(sink: W) -> Result<Self, ASEError> {
+        Self::with_options(sink, &DEFAULT_OPTIONS).await
+    }
+}
+
+impl<'a, W: AsyncWrite + AsyncSeek + Unpin> AsyncAseWriter<'a, W> {
+    /// Writes the header of the file to `sink`, using the given options.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to `sink` fails, or the version
+    /// is rejected by the options.
+    pub async fn with_options(mut sink: W, options: &'a WriteOptions) -> Result<Self, ASEError> {
+        let start = sink.stream_position().await?;
+        let mut ase_writer = Self {
+            sink,
+            start,
+            encoder: BlockEncoder::new(options)?,
+        };
+        ase_writer.flush().await?;
+        Ok(ase_writer)
+    }
+
+    /// Starts a group, all colors written until [`AsyncAseWriter::end_group`] are part of it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if another group was not ended yet, or the name
+    /// is rejected by the options.
+    pub async fn start_group(&mut self, name: &str) -> Result<(), ASEError> {
+        if self.encoder.start_group(name)? {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Writes a color, either to the current group or as a single color.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or the
+    /// name is rejected by the options.
+    pub async fn write_color(&mut self, block: ColorBlock) -> Result<(), ASEError> {
+        if self.encoder.write_color(block)? {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Ends the current group.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or no
+    /// group was started.
+    pub async fn end_group(&mut self) -> Result<(), ASEError> {
+        self.encoder.end_group()?;
+        self.flush().await
+    }
+
+    /// Returns the changes made while writing so far.
+    pub fn report(&self) -> &WriteReport {
+        self.encoder.report()
+    }
+
+    /// Writes the number of blocks to the header, returning the sink.
+    ///
+    /// The sink is positioned at the end of the written file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or a
+    /// group was not ended.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub async fn finish(mut self) -> Result<W, ASEError> {
+        let blocks = self.encoder.finish()?;
+        let end = self.sink.stream_position().await?;
+        self.sink.seek(SeekFrom::Start(self.start + 8)).await?;
+        self.sink.write_all(&blocks.to_be_bytes()).await?;
+        self.sink.seek(SeekFrom::Start(end)).await?;
+        self.sink.flush().await?;
+        Ok(self.sink)
+    }
+
+    /// Passes the buffered bytes to the sink.
+    async fn flush(&mut self) -> Result<(), ASEError> {
+        self.sink.write_all(self.encoder.buffered()).await?;
+        self.encoder.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use tokio::io::AsyncReadExt;
+
+    use crate::{
+        create_ase, read_ase_with_options, test_util::named, ColorType, ColorValue, ParseOptions,
+    };
+
+    use super::*;
+
+    fn palette() -> (Vec<Group>, Vec<ColorBlock>) {
+        let group = Group::new(
+            "Grün".to_owned(),
+            vec![
+                named("a", ColorValue::Rgb(1.0, 0.0, 0.0)),
+                named("b", ColorValue::Lab(50.0, 10.0, -10.0)),
+            ],
+        );
+        let empty = Group::new("empty".to_owned(), vec![]);
+        let color = ColorBlock::new("gray".to_owned(), ColorValue::Gray(0.5), ColorType::Global);
+        (vec![group, empty], vec![color])
+    }
+
+    /// Reads the input from a duplex stream, which is written in small chunks.
+    async fn read_chunked(input: Vec<u8>) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+        let (mut writer, reader) = tokio::io::duplex(4);
+        let write = tokio::spawn(async move {
+            for chunk in input.chunks(3) {
+                // the reader stops after the declared blocks
+                if writer.write_all(chunk).await.is_err() {
+                    break;
+                }
+            }
+        });
+        let result = read_ase_async(reader).await;
+        write.await.unwrap();
+        result
+    }
+
+    #[tokio::test]
+    async fn it_reads_async() {
+        let (groups, colors) = palette();
+        let mut inputs = vec![create_ase(groups, colors), create_ase(vec![], vec![])];
+        for kind in ["lenient", "invalid"] {
+            let dir = format!("{}/tests/fixtures/{kind}", env!("CARGO_MANIFEST_DIR"));
+            for entry in std::fs::read_dir(dir).unwrap() {
+                inputs.push(std::fs::read(entry.unwrap().path()).unwrap());
+            }
+        }
+
+        for input in inputs {
+            let expected = read_ase_with_options(&*input, &ParseOptions::DEFAULT)
+                .map_err(|err| err.to_string());
+            assert_eq!(
+                read_chunked(input).await.map_err(|err| err.to_string()),
+                expected
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn it_writes_async() {
+        let (groups, colors) = palette();
+        let expected = create_ase(groups.clone(), colors.clone());
+
+        let (writer, mut reader) = tokio::io::duplex(16);
+        let write = tokio::spawn(write_ase_async(writer, groups, colors));
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.unwrap();
+        write.await.unwrap().unwrap();
+        assert_eq!(bytes, expected);
+    }
+
+    #[tokio::test]
+    async fn it_writes_streaming_async() {
+        let (groups, colors) = palette();
+        let expected = create_ase(groups.clone(), colors.clone());
+
+        let mut writer = AsyncAseWriter::new(Cursor::new(Vec::new())).await.unwrap();
+        for group in groups {
+            writer.start_group(&group.name).await.unwrap();
+            for block in group.blocks {
+                writer.write_color(block).await.unwrap();
+            }
+            writer.end_group().await.unwrap();
+        }
+        for block in colors {
+            writer.write_color(block).await.unwrap();
+        }
+        assert_eq!(writer.finish().await.unwrap().into_inner(), expected);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    error::{ASEError, ConformationError},
+    reader::BlockReader,
+    types::GroupHold,
+    BlockType, ColorBlock, ColorBlockRef, Group, GroupRef,
+};
+
+/// Read groups and single colors from the data of an .ase file, borrowing their names
+/// from `ase`.
+///
+/// Files are read the same way as [`read_ase`](crate::read_ase) reads them, but neither
+/// the blocks nor the names are copied. Names are decoded when they are requested, so
+/// single colors only allocate the returned vector.
+///
+/// # Errors
+///
+/// This function will return an error if the ASE file is invalid. Errors after the
+/// header are wrapped in an [`ASEError::Block`], locating the block, which could not be read.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, read_ase_borrowed, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color.clone()]);
+///
+/// let (groups, colors) = read_ase_borrowed(&source).unwrap();
+/// assert!(groups.is_empty());
+/// assert_eq!(colors[0].name(), "name");
+/// assert_eq!(ColorBlock::from(colors[0].clone()), color);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_ase_borrowed(
+    ase: &[u8],
+) -> Result<(Vec<GroupRef<'_>>, Vec<ColorBlockRef<'_>>), ASEError> {
+    read_slice::<Borrowed>(ase)
+}
+
+/// Read groups and single colors from the data of an .ase file, like [`read_ase`](crate::read_ase).
+///
+/// The blocks are parsed directly from `ase`, instead of being copied out of a reader
+/// first. Use [`read_ase_borrowed`] to also avoid copying the names.
+///
+/// # Errors
+///
+/// This function will return an error if the ASE file is invalid. Errors after the
+/// header are wrapped in an [`ASEError::Block`], locating the block, which could not be read.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, read_ase, read_ase_bytes, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color]);
+/// assert_eq!(read_ase_bytes(&source).unwrap(), read_ase(&*source).unwrap());
+/// ```
+pub fn read_ase_bytes(ase: &[u8]) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    read_slice::<Owned>(ase)
+}
+
+/// How groups and colors are parsed from block data borrowed from the file.
+trait SliceBlocks<'a> {
+    type Group;
+    type Color;
+
+    fn parse_group(data: &'a [u8]) -> Result<Self::Group, ASEError>;
+    fn parse_color(data: &'a [u8]) -> Result<Self::Color, ASEError>;
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color>;
+}
+
+/// Parses [`GroupRef`]s and [`ColorBlockRef`]s.
+struct Borrowed;
+
+impl<'a> SliceBlocks<'a> for Borrowed {
+    type Group = GroupRef<'a>;
+    type Color = ColorBlockRef<'a>;
+
+    fn parse_group(data: &'a [u8]) -> Result<Self::Group, ASEError> {
+        GroupRef::parse(data)
+    }
+
+    fn parse_color(data: &'a [u8]) -> Result<Self::Color, ASEError> {
+        ColorBlockRef::parse(data)
+    }
+
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color> {
+        &mut group.blocks
+    }
+}
+
+/// Parses [`Group`]s and [`ColorBlock`]s.
+struct Owned;
+
+impl<'a> SliceBlocks<'a> for Owned {
+    type Group = Group;
+    type Color = ColorBlock;
+
+    fn parse_group(data: &'a [u8]) -> Result<Self::Group, ASEError> {
+        Group::parse(data)
+    }
+
+    fn parse_color(data: &'a [u8]) -> Result<Self::Color, ASEError> {
+        ColorBlock::parse(data)
+    }
+
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color> {
+        &mut group.blocks
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn read_slice<'a, B: SliceBlocks<'a>>(
+    ase: &'a [u8],
+) -> Result<(Vec<B::Group>, Vec<B::Color>), ASEError> {
+    let mut blocks = BlockReader::new(ase, false)?;
+    parse_slice::<B>(&mut blocks).map_err(|err| blocks.locate(err))
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_slice<'a, B: SliceBlocks<'a>>(
+    blocks: &mut BlockReader<&'a [u8]>,
+) -> Result<(Vec<B::Group>, Vec<B::Color>), ASEError> {
+    let mut groups = Vec::new();
+    let mut colors = Vec::new();
+
+    let mut group_hold = GroupHold::Empty;
+    let mut group_hold_value: Option<B::Group> = None;
+
+    loop {
+        let mut frame = match blocks.next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            // the file ends in place of the group end, like `read_ase` rejects it
+            Err(err) if group_hold != GroupHold::Empty && blocks.is_missing_last_block(&err) => {
+                return Err(ASEError::Invalid(ConformationError::GroupEnd))
+            }
+            Err(err) => return Err(err),
+        };
+        let block_type = frame.block_type;
+
+        if block_type != BlockType::GroupEnd && group_hold == GroupHold::HoldingBuilt {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+        let data = blocks.read_data_borrowed(&mut frame)?;
+
+        match block_type {
+            BlockType::GroupStart => {
+                let mut group = B::parse_group(data)?;
+                if group_hold != GroupHold::Empty {
+                    return Err(ASEError::Invalid(ConformationError::GroupEnd));
+                }
+                // if the group has any sub-blocks then it has already been built
+                // and only a group-end block may follow it.
+                group_hold = if B::colors(&mut group).is_empty() {
+                    GroupHold::HoldingBuilding
+                } else {
+                    blocks.expect_additional_block();
+                    GroupHold::HoldingBuilt
+                };
+                group_hold_value = Some(group);
+            }
+            BlockType::GroupEnd => match group_hold {
+                GroupHold::HoldingBuilding | GroupHold::HoldingBuilt => {
+                    group_hold = GroupHold::Empty;
+                    groups.extend(group_hold_value.take());
+                }
+                GroupHold::Empty => return Err(ASEError::Invalid(ConformationError::GroupEnd)),
+            },
+            BlockType::ColorEntry => {
+                let block = B::parse_color(data)?;
+                match group_hold_value.as_mut() {
+                    Some(group) => B::colors(group).push(block),
+                    None => colors.push(block),
+                }
+            }
+            // rejected by the block reader
+            BlockType::Raw(_) => return Err(ASEError::BlockTypeError),
+        }
+    }
+
+    match group_hold {
+        // close the last group, even if no end was found
+        GroupHold::HoldingBuilding => groups.extend(group_hold_value),
+        // a built group, which was not terminated, is an error.
+        GroupHold::HoldingBuilt => return Err(ASEError::Invalid(ConformationError::GroupEnd)),
+        GroupHold::Empty => {}
+    }
+    Ok((groups, colors))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_ase, read_ase_with_options, test_util::named, ColorValue, ParseOptions};
+
+    use super::*;
+
+    /// Reads the input from bytes, comparing errors by their message.
+    fn read_owned(input: &[u8]) -> Result<(Vec<Group>, Vec<ColorBlock>), String> {
+        read_ase_bytes(input).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn it_reads_like_read_ase() {
+        let group = Group::new(
+            "Grün".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let empty = Group::new("empty".to_owned(), vec![]);
+        let color = named("😀", ColorValue::Lab(50.0, 10.0, -10.0));
+        let mut inputs = vec![
+            create_ase(vec![group, empty], vec![color]),
+            create_ase(vec![], vec![]),
+        ];
+        for kind in ["lenient", "invalid"] {
+            let dir = format!("{}/tests/fixtures/{kind}", env!("CARGO_MANIFEST_DIR"));
+            for entry in std::fs::read_dir(dir).unwrap() {
+                inputs.push(std::fs::read(entry.unwrap().path()).unwrap());
+            }
+        }
+
+        for input in inputs {
+            let expected = read_ase_with_options(&*input, &ParseOptions::DEFAULT)
+                .map_err(|err| err.to_string());
+            assert_eq!(read_owned(&input), expected);
+
+            let borrowed = read_ase_borrowed(&input).map(|(groups, colors)| {
+                (
+                    groups.into_iter().map(Group::from).collect(),
+                    colors.into_iter().map(ColorBlock::from).collect(),
+                )
+            });
+            assert_eq!(borrowed.map_err(|err| err.to_string()), expected);
+        }
+    }
+
+    #[test]
+    fn it_borrows_names() {
+        let group = Group::new("group".to_owned(), vec![named("a", ColorValue::Gray(0.5))]);
+        let input = create_ase(vec![group], vec![]);
+        let (groups, colors) = read_ase_borrowed(&input).unwrap();
+        assert!(colors.is_empty());
+        assert_eq!(groups[0].name_chars().collect::<String>(), "group");
+        assert_eq!(groups[0].blocks[0].raw_name(), &[0, b'a']);
+
+        // the names point into the input
+        let range = input.as_ptr_range();
+        assert!(range.contains(&groups[0].raw_name().as_ptr()));
+        assert!(range.contains(&groups[0].blocks[0].raw_name().as_ptr()));
+    }
+
+    #[test]
+    fn it_rejects_invalid_names() {
+        let mut input = create_ase(vec![], vec![named("a", ColorValue::Gray(0.5))]);
+        // replace the name with an unpaired surrogate
+        input[20..22].copy_from_slice(&[0xd8, 0x00]);
+        assert!(matches!(
+            read_ase_borrowed(&input)
+                .as_ref()
+                .map_err(ASEError::root_cause),
+            Err(ASEError::UTF16Error)
+        ));
+        assert_eq!(
+            read_owned(&input),
+            read_ase_with_options(&*input, &ParseOptions::DEFAULT).map_err(|err| err.to_string())
+        );
+    }
+}
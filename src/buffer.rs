@@ -1,3 +1,5 @@
+use crate::wire;
+
 /// Represents an infinite buffer designed to hold individual bytes (`u8`).
 ///
 /// It provides methods for easily writing other data types
@@ -15,6 +17,11 @@ impl Buffer {
         }
     }
 
+    /// Create a new Buffer, which appends to `data`.
+    pub(crate) fn from_vec(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
     /// Write the slice to self.
     pub fn write_slice(&mut self, src: &[u8]) {
         self.data.extend_from_slice(src);
@@ -22,23 +29,51 @@ impl Buffer {
 
     /// Write u32 to self.
     pub fn write_u32(&mut self, n: u32) {
-        self.write_slice(&n.to_be_bytes());
+        wire::put_u32_be(&mut self.data, n);
+    }
+
+    /// Overwrite the u32 at `position` with `n`.
+    ///
+    /// # Panics
+    /// Panics if less than four bytes were written after `position`.
+    #[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+    pub fn write_u32_at(&mut self, position: usize, n: u32) {
+        self.data[position..position + 4].copy_from_slice(&n.to_be_bytes());
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.data.len()
     }
 
     /// Write f32 to self.
     pub fn write_f32(&mut self, n: f32) {
-        self.write_slice(&n.to_be_bytes());
+        wire::put_f32_be(&mut self.data, n);
     }
 
     /// Write u16 to self.
     pub fn write_u16(&mut self, n: u16) {
-        self.write_slice(&n.to_be_bytes());
+        wire::put_u16_be(&mut self.data, n);
+    }
+
+    /// Write the length of the UTF16 String, followed by the null terminated String to self.
+    pub fn write_utf16_nul_str(&mut self, src: &str) {
+        wire::put_utf16_nul_str(&mut self.data, src);
     }
 
-    /// Write a null terminated UTF16 String to self.
-    pub fn write_null_terminated_utf_16_str(&mut self, src: &str) {
-        src.encode_utf16().for_each(|byte| self.write_u16(byte));
-        self.write_u16(0);
+    /// Returns the written bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Removes the bytes written after `position`.
+    pub fn truncate(&mut self, position: usize) {
+        self.data.truncate(position);
+    }
+
+    /// Removes all written bytes, keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.data.clear();
     }
 
     /// Returns the written buffer as a [`Vec<u8>`] of bytes.
@@ -46,3 +81,19 @@ impl Buffer {
         self.data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_writes_utf16_nul_str() {
+        let mut buf = Buffer::with_capacity(0);
+        buf.write_utf16_nul_str("aä😀");
+        buf.write_utf16_nul_str("");
+        assert_eq!(
+            buf.into_vec(),
+            vec![0, 5, 0, 97, 0, 228, 216, 61, 222, 0, 0, 0, 0, 1, 0, 0]
+        );
+    }
+}
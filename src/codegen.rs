@@ -0,0 +1,408 @@
+//! Generate source code with the colors of a palette as constants.
+//!
+//! Colors are converted to sRGB and named after their swatches.
+//! Names are turned into identifiers by splitting them into words at every character,
+//! which is not an ASCII letter or digit, so `Aurora Red` becomes `AURORA_RED` in Rust and
+//! `auroraRed` in TypeScript. Identifiers, which are empty, start with a digit or are
+//! keywords, are prefixed or suffixed with `_`, repeated identifiers are numbered.
+//!
+//! # Examples
+//! ```rust
+//! # use adobe_swatch_exchange::{codegen, Ase, ColorBlock, ColorType, ColorValue};
+//! let red = ColorBlock::new("Aurora Red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal);
+//! let ase = Ase::new(vec![], vec![red]);
+//! assert_eq!(
+//!     codegen::to_rust(&ase, &codegen::Options::default()),
+//!     "// @generated by adobe-swatch-exchange\n\npub const AURORA_RED: [f32; 3] = [1.0, 0.0, 0.0];\n"
+//! );
+//! assert_eq!(
+//!     codegen::to_typescript(&ase, &codegen::Options::default()),
+//!     "// @generated by adobe-swatch-exchange\n\nexport const auroraRed = [1, 0, 0] as const;\n"
+//! );
+//! ```
+
+#![allow(clippy::arithmetic_side_effects)]
+
+use std::{collections::HashSet, fmt::Write};
+
+use crate::{Ase, ColorBlock, ColorValue};
+
+/// Marks the generated code, so that it is not edited or formatted.
+const HEADER: &str = "// @generated by adobe-swatch-exchange\n";
+
+/// Options for [`to_rust`] and [`to_typescript`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// How the colors are written.
+    pub values: Values,
+    /// How the constants are laid out in Rust code, TypeScript always uses single constants.
+    pub layout: Layout,
+    /// Whether the colors of a group are prefixed with the name of the group, instead of
+    /// being placed into a module or object named after the group.
+    pub flatten_groups: bool,
+}
+
+/// How colors are written by [`to_rust`] and [`to_typescript`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Values {
+    /// Red, green and blue from `0.0` to `1.0`, like `[0.7490196, 0.38039216, 0.41568628]`.
+    #[default]
+    Floats,
+    /// Hex codes, like `"#bf616a"`.
+    Hex,
+}
+
+/// How [`to_rust`] lays out the constants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Layout {
+    /// A constant for each color, like `pub const AURORA_RED: [f32; 3] = [...];`.
+    #[default]
+    Constants,
+    /// A `SWATCHES` array of `Swatch` structs, containing the name and color, for the
+    /// single colors and for each group.
+    Table,
+}
+
+/// Returns Rust code with a constant for each color of the document, see the
+/// [module documentation](self).
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{codegen, Ase, ColorBlock, ColorType, ColorValue, Group};
+/// let red = ColorBlock::new("Red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal);
+/// let ase = Ase::new(vec![Group::new("Aurora".to_owned(), vec![red])], vec![]);
+/// let options = codegen::Options {
+///     values: codegen::Values::Hex,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     codegen::to_rust(&ase, &options),
+///     "// @generated by adobe-swatch-exchange\n\npub mod aurora {\n    pub const RED: &str = \"#ff0000\";\n}\n"
+/// );
+/// ```
+pub fn to_rust(ase: &Ase, options: &Options) -> String {
+    let mut code = HEADER.to_owned();
+    if options.layout == Layout::Table {
+        code.push_str("\npub struct Swatch {\n    pub name: &'static str,\n");
+        let color_type = match options.values {
+            Values::Floats => "[f32; 3]",
+            Values::Hex => "&'static str",
+        };
+        writeln!(code, "    pub color: {color_type},\n}}").expect(WRITE);
+    }
+
+    let mut root = Identifiers::default();
+    let mut modules = Identifiers::default();
+    for group in &ase.groups {
+        let words = words(&group.name);
+        if options.flatten_groups {
+            if !group.blocks.is_empty() {
+                code.push('\n');
+            }
+            write_rust_items(&mut code, &group.blocks, &words, &mut root, options, "");
+            continue;
+        }
+        let module = modules.unique(rust_module(&words), "_");
+        write!(code, "\npub mod {module} {{\n").expect(WRITE);
+        if options.layout == Layout::Table {
+            code.push_str("    use super::Swatch;\n\n");
+        }
+        let mut identifiers = Identifiers::default();
+        write_rust_items(
+            &mut code,
+            &group.blocks,
+            &[],
+            &mut identifiers,
+            options,
+            "    ",
+        );
+        code.push_str("}\n");
+    }
+    if !ase.colors.is_empty() {
+        code.push('\n');
+    }
+    write_rust_items(&mut code, &ase.colors, &[], &mut root, options, "");
+    code
+}
+
+/// Writes the constants for the colors, whose identifiers start with `prefix`.
+fn write_rust_items(
+    code: &mut String,
+    colors: &[ColorBlock],
+    prefix: &[String],
+    identifiers: &mut Identifiers,
+    options: &Options,
+    indent: &str,
+) {
+    let value = |color: &ColorValue| match options.values {
+        Values::Floats => {
+            let [r, g, b] = srgb(color);
+            format!("[{r:?}, {g:?}, {b:?}]")
+        }
+        Values::Hex => format!("{:?}", color.to_srgb_hex()),
+    };
+    match options.layout {
+        Layout::Constants => {
+            let color_type = rust_type(options);
+            for block in colors {
+                let words = [prefix, &words(&block.name)].concat();
+                let name = identifiers.unique(rust_constant(&words), "_");
+                let value = value(&block.color);
+                writeln!(code, "{indent}pub const {name}: {color_type} = {value};").expect(WRITE);
+            }
+        }
+        Layout::Table => {
+            let words = [prefix, &["swatches".to_owned()]].concat();
+            let name = identifiers.unique(rust_constant(&words), "_");
+            let length = colors.len();
+            writeln!(code, "{indent}pub const {name}: [Swatch; {length}] = [").expect(WRITE);
+            for block in colors {
+                let value = value(&block.color);
+                writeln!(
+                    code,
+                    "{indent}    Swatch {{ name: {:?}, color: {value} }},",
+                    block.name
+                )
+                .expect(WRITE);
+            }
+            writeln!(code, "{indent}];").expect(WRITE);
+        }
+    }
+}
+
+/// Returns TypeScript code with a constant for each color of the document, see the
+/// [module documentation](self).
+///
+/// Groups become objects, unless [`Options::flatten_groups`] is set.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{codegen, Ase, ColorBlock, ColorType, ColorValue, Group};
+/// let red = ColorBlock::new("Red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal);
+/// let ase = Ase::new(vec![Group::new("Aurora".to_owned(), vec![red])], vec![]);
+/// let options = codegen::Options {
+///     values: codegen::Values::Hex,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     codegen::to_typescript(&ase, &options),
+///     "// @generated by adobe-swatch-exchange\n\nexport const aurora = {\n  red: '#ff0000',\n} as const;\n"
+/// );
+/// ```
+pub fn to_typescript(ase: &Ase, options: &Options) -> String {
+    let value = |color: &ColorValue| match options.values {
+        Values::Floats => {
+            let [r, g, b] = srgb(color);
+            format!("[{r}, {g}, {b}]")
+        }
+        Values::Hex => format!("'{}'", color.to_srgb_hex()),
+    };
+    let mut code = HEADER.to_owned();
+    let mut root = Identifiers::default();
+    let constant = |code: &mut String, root: &mut Identifiers, words: &[String], value: String| {
+        let name = root.unique(camel_case(words), "");
+        write!(code, "\nexport const {name} = {value} as const;\n").expect(WRITE);
+    };
+    for group in &ase.groups {
+        let group_words = words(&group.name);
+        if options.flatten_groups {
+            for block in &group.blocks {
+                let words = [group_words.as_slice(), &words(&block.name)].concat();
+                constant(&mut code, &mut root, &words, value(&block.color));
+            }
+            continue;
+        }
+        let mut object = String::from("{\n");
+        let mut properties = Identifiers::default();
+        for block in &group.blocks {
+            let name = properties.unique(camel_case(&words(&block.name)), "");
+            writeln!(object, "  {name}: {},", value(&block.color)).expect(WRITE);
+        }
+        object.push('}');
+        constant(&mut code, &mut root, &group_words, object);
+    }
+    for block in &ase.colors {
+        constant(
+            &mut code,
+            &mut root,
+            &words(&block.name),
+            value(&block.color),
+        );
+    }
+    code
+}
+
+const WRITE: &str = "writing to a String cannot fail";
+
+/// Returns the Rust type of the colors.
+fn rust_type(options: &Options) -> &'static str {
+    match options.values {
+        Values::Floats => "[f32; 3]",
+        Values::Hex => "&str",
+    }
+}
+
+/// Returns the color as sRGB floats, clamped like [`ColorValue::to_srgb_hex`], NaN
+/// components become `0.0`.
+fn srgb(color: &ColorValue) -> [f32; 3] {
+    let (r, g, b) = color.to_srgb();
+    [r, g, b].map(|v| if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) })
+}
+
+/// Splits the name into lowercase words at characters, which are not ASCII letters or
+/// digits.
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// Words, which cannot be used as the name of a Rust module, even as raw identifier.
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Reserved words of TypeScript, which cannot be used as the name of a constant.
+const TYPESCRIPT_KEYWORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Returns the words as `snake_case` module name, like `aurora_red`.
+fn rust_module(words: &[String]) -> String {
+    let name = identifier(words.join("_"), "group");
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        return name + "_";
+    }
+    name
+}
+
+/// Returns the words as `SCREAMING_SNAKE_CASE` constant name, like `AURORA_RED`.
+fn rust_constant(words: &[String]) -> String {
+    identifier(words.join("_").to_ascii_uppercase(), "COLOR")
+}
+
+/// Returns the words as `camelCase` name, like `auroraRed`.
+fn camel_case(words: &[String]) -> String {
+    let mut name = String::new();
+    for (index, word) in words.iter().enumerate() {
+        let mut chars = word.chars();
+        if index > 0 {
+            name.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+        }
+        name.extend(chars);
+    }
+    let name = identifier(name, "color");
+    if TYPESCRIPT_KEYWORDS.contains(&name.as_str()) {
+        return name + "_";
+    }
+    name
+}
+
+/// Returns the name as identifier, using `fallback` for empty names and prefixing names
+/// starting with a digit.
+fn identifier(name: String, fallback: &str) -> String {
+    match name.chars().next() {
+        None => fallback.to_owned(),
+        Some(first) if first.is_ascii_digit() => format!("_{name}"),
+        Some(_) => name,
+    }
+}
+
+/// The identifiers used within a module or object.
+#[derive(Default)]
+struct Identifiers(HashSet<String>);
+
+impl Identifiers {
+    /// Returns the identifier, numbering it after `separator` if it is already used.
+    fn unique(&mut self, identifier: String, separator: &str) -> String {
+        let mut unique = identifier.clone();
+        let mut number = 1;
+        while self.0.contains(&unique) {
+            number += 1;
+            unique = format!("{identifier}{separator}{number}");
+        }
+        self.0.insert(unique.clone());
+        unique
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sanitizes_identifiers() {
+        let words = |name| words(name);
+        assert_eq!(rust_constant(&words("Aurora Red")), "AURORA_RED");
+        assert_eq!(rust_constant(&words("#BF616A")), "BF616A");
+        assert_eq!(rust_constant(&words("50% Gray")), "_50_GRAY");
+        assert_eq!(rust_constant(&words("  ")), "COLOR");
+        assert_eq!(rust_constant(&words("Café/Noir")), "CAF_NOIR");
+        assert_eq!(rust_module(&words("Brand-Primary")), "brand_primary");
+        assert_eq!(rust_module(&words("Type")), "type_");
+        assert_eq!(rust_module(&words("")), "group");
+        assert_eq!(camel_case(&words("aurora RED")), "auroraRed");
+        assert_eq!(camel_case(&words("2nd accent")), "_2ndAccent");
+        assert_eq!(camel_case(&words("Default")), "default_");
+        assert_eq!(camel_case(&words("---")), "color");
+    }
+
+    #[test]
+    fn it_numbers_repeated_identifiers() {
+        let mut identifiers = Identifiers::default();
+        assert_eq!(identifiers.unique("RED".to_owned(), "_"), "RED");
+        assert_eq!(identifiers.unique("RED".to_owned(), "_"), "RED_2");
+        assert_eq!(identifiers.unique("RED".to_owned(), "_"), "RED_3");
+        assert_eq!(identifiers.unique("RED_2".to_owned(), "_"), "RED_2_2");
+    }
+}
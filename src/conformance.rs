@@ -0,0 +1,161 @@
+//! Check collections of real-world files against the reader.
+//!
+//! Each file is read using [`read_ase`], the read document is written and read again to
+//! verify that it is stable, see [`assert_roundtrip`].
+
+#![allow(clippy::arithmetic_side_effects)]
+
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{assert_roundtrip, read_ase, ASEError, RoundtripError};
+
+/// The result of checking a single file.
+#[derive(Debug)]
+pub struct FileReport {
+    /// Path of the checked file.
+    pub path: PathBuf,
+    /// The error which occurred while reading the file, if any.
+    pub error: Option<ASEError>,
+    /// Number of groups read from the file.
+    pub groups: usize,
+    /// Number of colors read from the file, including those in groups.
+    pub colors: usize,
+    /// The mismatch found after writing and reading the document again, if any.
+    pub roundtrip: Option<RoundtripError>,
+}
+
+impl FileReport {
+    /// Returns `true` if the file was read without error and is stable when written again.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.roundtrip.is_none()
+    }
+}
+
+impl Display for FileReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.path.display())?;
+        match (&self.error, &self.roundtrip) {
+            (Some(err), _) => write!(f, "failed to read: {err}"),
+            (None, Some(err)) => write!(f, "unstable: {err}"),
+            (None, None) => write!(f, "{} groups, {} colors", self.groups, self.colors),
+        }
+    }
+}
+
+/// Summary of multiple [`FileReport`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Summary {
+    /// Number of checked files.
+    pub files: usize,
+    /// Number of files, which were read and are stable.
+    pub passed: usize,
+    /// Number of files, which could not be read.
+    pub failed: usize,
+    /// Number of files, which were read, but changed when written and read again.
+    pub unstable: usize,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} files: {} passed, {} failed, {} unstable",
+            self.files, self.passed, self.failed, self.unstable
+        )
+    }
+}
+
+/// Read the file at the given path and check that it is stable.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::conformance::check_file;
+/// let report = check_file("examples/aurora.ase");
+/// assert!(report.is_ok());
+/// # assert_eq!((report.groups, report.colors), (1, 5));
+/// ```
+pub fn check_file<P: AsRef<Path>>(path: P) -> FileReport {
+    let mut report = FileReport {
+        path: path.as_ref().to_path_buf(),
+        error: None,
+        groups: 0,
+        colors: 0,
+        roundtrip: None,
+    };
+
+    let (groups, colors) = match fs::read(&report.path)
+        .map_err(ASEError::from)
+        .and_then(|data| read_ase(&*data))
+    {
+        Ok(document) => document,
+        Err(err) => {
+            report.error = Some(err);
+            return report;
+        }
+    };
+
+    report.groups = groups.len();
+    report.colors = groups.iter().map(|group| group.blocks.len()).sum::<usize>() + colors.len();
+    report.roundtrip = assert_roundtrip(&groups, &colors).err();
+    report
+}
+
+/// Check every `.ase` file in the given directory and its subdirectories.
+///
+/// Reports are sorted by their path. Directories, which cannot be read, are reported
+/// as a failed file.
+pub fn check_dir<P: AsRef<Path>>(path: P) -> Vec<FileReport> {
+    let mut reports = Vec::new();
+    collect_reports(path.as_ref(), &mut reports);
+    reports.sort_by(|a, b| a.path.cmp(&b.path));
+    reports
+}
+
+fn collect_reports(dir: &Path, reports: &mut Vec<FileReport>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            reports.push(FileReport {
+                path: dir.to_path_buf(),
+                error: Some(err.into()),
+                groups: 0,
+                colors: 0,
+                roundtrip: None,
+            });
+            return;
+        }
+    };
+
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() {
+            collect_reports(&path, reports);
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension.eq_ignore_ascii_case("ase"))
+        {
+            reports.push(check_file(&path));
+        }
+    }
+}
+
+/// Summarize the given reports.
+pub fn summarize(reports: &[FileReport]) -> Summary {
+    reports
+        .iter()
+        .fold(Summary::default(), |mut summary, report| {
+            summary.files += 1;
+            match (&report.error, &report.roundtrip) {
+                (Some(_), _) => summary.failed += 1,
+                (None, Some(_)) => summary.unstable += 1,
+                (None, None) => summary.passed += 1,
+            }
+            summary
+        })
+}
@@ -2,6 +2,7 @@ use std::{array, fmt::Display, io, string};
 
 /// Indicates a failure in decoding the ASE.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ASEError {
     /// An error occurred while reading data from the provided source.
     Io(io::Error),
@@ -22,14 +23,107 @@ pub enum ASEError {
     BlockTypeError,
     /// An error occured while parsing the input data.
     InputDataParseError,
+    /// A group or color cannot be written.
+    InvalidBlock {
+        /// The name of the group or color.
+        name: String,
+        /// Why the block cannot be written.
+        reason: BlockError,
+    },
+    /// A hex code cannot be parsed into a color, see
+    /// [`ColorValue::from_hex`](crate::types::ColorValue::from_hex).
+    HexColor {
+        /// The hex code.
+        hex: String,
+        /// Why the hex code cannot be parsed.
+        reason: HexError,
+    },
+    /// An error occurred while reading a block of the file.
+    Block {
+        /// Offset of the block identifier from the start of the file.
+        offset: u64,
+        /// Index of the block in the file, counting from zero.
+        index: u32,
+        /// The error, which occurred while reading the block.
+        error: Box<ASEError>,
+    },
+    /// The file exceeds a limit on the resources used for reading it, see
+    /// [`ParseOptions::limits`](crate::ParseOptions::limits).
+    LimitExceeded {
+        /// The limit, which was exceeded.
+        limit: Limit,
+        /// The value of the limit.
+        maximum: u64,
+    },
+    /// An error occurred while reading a named group or color, after its name was read.
+    Swatch {
+        /// The name of the group or color.
+        name: String,
+        /// The error, which occurred while reading the group or color.
+        error: Box<ASEError>,
+    },
+    /// An error occurred while reading or writing a file.
+    File {
+        /// The path of the file.
+        path: std::path::PathBuf,
+        /// The error, which occurred while reading or writing the file.
+        error: Box<ASEError>,
+    },
+}
+
+impl ASEError {
+    /// Returns the error without the location, at which it occurred.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{read_ase_with_options, ASEError, ParseOptions};
+    /// // a block with an unknown type
+    /// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 0, 3];
+    /// let err = read_ase_with_options(&*source, &ParseOptions::DEFAULT).unwrap_err();
+    /// assert!(matches!(err, ASEError::Block { offset: 12, index: 0, .. }));
+    /// assert!(matches!(err.root_cause(), ASEError::BlockTypeError));
+    /// ```
+    pub fn root_cause(&self) -> &ASEError {
+        match self {
+            ASEError::Block { error, .. }
+            | ASEError::Swatch { error, .. }
+            | ASEError::File { error, .. } => error.root_cause(),
+            _ => self,
+        }
+    }
+
+    /// Returns the error without the location, at which it occurred, by value.
+    pub(crate) fn into_root_cause(self) -> ASEError {
+        match self {
+            ASEError::Block { error, .. }
+            | ASEError::Swatch { error, .. }
+            | ASEError::File { error, .. } => error.into_root_cause(),
+            _ => self,
+        }
+    }
+
+    /// Adds the name of the group or color, which was read when the error occurred.
+    ///
+    /// Errors are only named after the innermost group or color, unnamed ones are
+    /// returned as they are.
+    pub(crate) fn in_swatch(self, name: String) -> Self {
+        if name.is_empty() || matches!(self, ASEError::Swatch { .. }) {
+            return self;
+        }
+        ASEError::Swatch {
+            name,
+            error: Box::new(self),
+        }
+    }
 }
 
 /// Indicates the cause of the file being an invalid ase.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ConformationError {
     /// An invalid file version was detected.
     ///
-    /// Currently only version `1.0` of the format exists.
+    /// Only versions `1.x` of the format are supported.
     FileVersion,
     /// An invalid file signature was detected.
     ///
@@ -37,6 +131,228 @@ pub enum ConformationError {
     FileSignature,
     /// Groups must be terminated with a `GroupEnd` block.
     GroupEnd,
+    /// The file ends inside of a block, `missing` bytes before the end declared by the block.
+    Truncated {
+        /// The number of bytes missing from the block.
+        missing: u64,
+    },
+    /// The file continues after its last block with data, which is not zero padding.
+    TrailingData {
+        /// The number of bytes after the last block.
+        length: u64,
+    },
+    /// Reading the blocks did not advance through the file.
+    NoProgress,
+    /// The name of a group is not followed by its null terminator.
+    NameTerminator,
+    /// The length of a name does not count its null terminator.
+    NameLength,
+    /// A color entry continues after its color type, up to the length declared by the block.
+    ColorEntryLength {
+        /// The number of bytes after the color type.
+        length: u64,
+    },
+    /// The colors of a group block end before its data, like at a color, which cannot
+    /// be read.
+    GroupData {
+        /// The name of the group.
+        group: String,
+        /// The position within the data of the group block, at which the colors end.
+        position: u64,
+    },
+}
+
+/// A deviation from the format, which was tolerated while reading a file.
+///
+/// Offsets are counted in bytes from the start of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The major version of the file is not supported, its blocks were read like those
+    /// of version `1`.
+    FileVersion {
+        /// Major version of the file.
+        major: u16,
+        /// Minor version of the file.
+        minor: u16,
+    },
+    /// Zero bytes following a `GroupEnd` block were skipped.
+    ///
+    /// These are left by applications, which write the length of `GroupEnd` blocks.
+    GroupEndLength {
+        /// Offset of the skipped bytes.
+        offset: u64,
+        /// Number of skipped bytes.
+        length: u64,
+    },
+    /// The length of a group block only covers its name, its colors follow it as
+    /// separate blocks.
+    ///
+    /// This is the layout written by Adobe applications, see
+    /// [`GroupLayout::Flat`](crate::GroupLayout::Flat).
+    SeparateGroupColors {
+        /// Offset of the group block.
+        offset: u64,
+    },
+    /// A group was missing its `GroupEnd` block.
+    UnterminatedGroup {
+        /// Offset of the group block.
+        offset: u64,
+    },
+    /// Zero bytes between blocks, like padding to a 4- or 8-byte boundary, were skipped.
+    BlockPadding {
+        /// Offset of the skipped bytes.
+        offset: u64,
+        /// Number of skipped bytes.
+        length: u64,
+    },
+    /// A group starting within another group was read as a separate group.
+    NestedGroup {
+        /// Offset of the nested group block.
+        offset: u64,
+    },
+    /// A `GroupEnd` block outside of a group was skipped.
+    UnmatchedGroupEnd {
+        /// Offset of the `GroupEnd` block.
+        offset: u64,
+    },
+    /// The number of blocks in the file differs from the number declared in its header.
+    BlockCount {
+        /// The number of blocks declared in the header.
+        declared: u32,
+        /// The number of blocks read, counted like the declared blocks.
+        read: u32,
+    },
+    /// A block with an unknown type was skipped.
+    UnknownBlock {
+        /// Offset of the block.
+        offset: u64,
+        /// The type of the block.
+        block_type: u16,
+    },
+    /// The name of a group was read without its null terminator.
+    NameTerminator {
+        /// Offset of the group block.
+        offset: u64,
+    },
+    /// A color entry within a group, which cannot be read, was skipped.
+    MalformedColor {
+        /// Offset of the color entry.
+        offset: u64,
+        /// Index of the color entry within its group, counting from zero.
+        index: usize,
+    },
+    /// Data in a group block following its colors, like a color, which cannot be read,
+    /// and the colors after it, was skipped.
+    GroupData {
+        /// Offset of the skipped data.
+        offset: u64,
+        /// Number of skipped bytes.
+        length: u64,
+    },
+    /// The name of a block was read after its length, which does not count its null
+    /// terminator.
+    NameLength {
+        /// Offset of the block.
+        offset: u64,
+    },
+    /// The code of a color model was read, which differs from the format in case or a
+    /// null character in place of the trailing space.
+    ColorModelCode {
+        /// Offset of the color entry.
+        offset: u64,
+    },
+    /// The length of a color entry is smaller than any color entry, it was read up to the
+    /// end of its color type.
+    ColorEntrySize {
+        /// Offset of the color entry.
+        offset: u64,
+        /// The length declared by the block.
+        declared: u32,
+    },
+    /// Bytes following the color type of a color entry, up to the length declared by
+    /// the block, were skipped.
+    ColorEntryLength {
+        /// Offset of the color entry.
+        offset: u64,
+        /// Number of skipped bytes.
+        length: u64,
+    },
+    /// The name of a block was read as little-endian UTF-16, see
+    /// [`ParseOptions::name_byte_order`](crate::ParseOptions::name_byte_order).
+    NameByteOrder {
+        /// Offset of the block or color entry.
+        offset: u64,
+    },
+    /// RGB colors were read in the range from `0` to `255` and divided by `255`, see
+    /// [`ParseOptions::rgb_range`](crate::ParseOptions::rgb_range).
+    RgbRange {
+        /// The number of divided colors.
+        colors: usize,
+    },
+    /// Groups were merged into a preceding group with the same name, see
+    /// [`ParseOptions::merge_duplicate_groups`](crate::ParseOptions::merge_duplicate_groups).
+    MergedGroups {
+        /// The number of merged groups.
+        groups: usize,
+    },
+    /// The file continues after its last block with data, which is not zero padding.
+    TrailingData {
+        /// Offset of the data.
+        offset: u64,
+        /// Number of bytes after the last block.
+        length: u64,
+    },
+    /// Data from a block, which cannot be read, up to the next plausible block boundary
+    /// was skipped, see [`ParseOptions::resync_corrupt_blocks`](crate::ParseOptions::resync_corrupt_blocks).
+    CorruptBlock {
+        /// Offset of the block, which cannot be read.
+        offset: u64,
+        /// Number of skipped bytes.
+        length: u64,
+    },
+}
+
+/// A limit on the resources used for reading a file, see [`Limits`](crate::Limits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The number of blocks, see [`Limits::max_blocks`](crate::Limits::max_blocks).
+    Blocks,
+    /// The length of a name, see [`Limits::max_name_length`](crate::Limits::max_name_length).
+    NameLength,
+    /// The number of colors in a group, see
+    /// [`Limits::max_group_colors`](crate::Limits::max_group_colors).
+    GroupColors,
+    /// The number of decoded bytes, see
+    /// [`Limits::max_decoded_bytes`](crate::Limits::max_decoded_bytes).
+    DecodedBytes,
+}
+
+/// Indicates why a group or color cannot be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlockError {
+    /// The name contains a null character, which terminates names.
+    NullInName,
+    /// The group has fewer colors than required.
+    TooFewColors,
+    /// The name has more UTF-16 code units than can be stored in its length field.
+    NameTooLong,
+    /// A component of the color is outside of the range of its color model.
+    ComponentOutOfRange,
+    /// A component of the color is NaN or infinite.
+    NonFiniteComponent,
+}
+
+/// Indicates why a hex code cannot be parsed into a color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HexError {
+    /// The hex code does not have 3, 6 or 8 digits.
+    Length(usize),
+    /// The hex code contains a character, which is not a hex digit.
+    Digit(char),
 }
 
 impl Display for ASEError {
@@ -49,6 +365,22 @@ impl Display for ASEError {
             ASEError::ColorTypeError => write!(f, "Error converting ColorType"),
             ASEError::BlockTypeError => write!(f, "Error converting BlockType"),
             ASEError::InputDataParseError => write!(f, "Error parsing input data"),
+            ASEError::InvalidBlock { name, reason } => {
+                write!(f, "Invalid block {name:?}: {reason}")
+            }
+            ASEError::HexColor { hex, reason } => {
+                write!(f, "Invalid hex color {hex:?}: {reason}")
+            }
+            ASEError::Block {
+                offset,
+                index,
+                error,
+            } => write!(f, "{error} (block {index} at offset {offset})"),
+            ASEError::LimitExceeded { limit, maximum } => {
+                write!(f, "File exceeds the limit of {maximum} {limit}")
+            }
+            ASEError::Swatch { name, error } => write!(f, "{error} in \"{name}\""),
+            ASEError::File { path, error } => write!(f, "{}: {error}", path.display()),
         }
     }
 }
@@ -59,11 +391,193 @@ impl Display for ConformationError {
             ConformationError::FileVersion => write!(f, "File version is not supported"),
             ConformationError::FileSignature => write!(f, "Invalid file signature found"),
             ConformationError::GroupEnd => write!(f, "Blocks must end to be valid"),
+            ConformationError::Truncated { missing } => {
+                write!(f, "File is truncated, {missing} bytes are missing")
+            }
+            ConformationError::TrailingData { length } => {
+                write!(f, "File has {length} bytes of data after its last block")
+            }
+            ConformationError::NoProgress => write!(f, "Reading blocks did not make progress"),
+            ConformationError::NameTerminator => {
+                write!(f, "Group name is missing its null terminator")
+            }
+            ConformationError::NameLength => {
+                write!(f, "Name length does not count its null terminator")
+            }
+            ConformationError::ColorEntryLength { length } => {
+                write!(
+                    f,
+                    "Color entry has {length} bytes of data after its color type"
+                )
+            }
+            ConformationError::GroupData { group, position } => {
+                write!(
+                    f,
+                    "Group \"{group}\" has data after its colors at position {position}"
+                )
+            }
         }
     }
 }
 
-impl std::error::Error for ASEError {}
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseWarning::FileVersion { major, minor } => {
+                write!(f, "Read unsupported file version {major}.{minor}")
+            }
+            ParseWarning::GroupEndLength { offset, length } => {
+                write!(
+                    f,
+                    "Skipped {length} bytes after a group end at offset {offset}"
+                )
+            }
+            ParseWarning::SeparateGroupColors { offset } => {
+                write!(f, "Group at offset {offset} is followed by its colors")
+            }
+            ParseWarning::UnterminatedGroup { offset } => {
+                write!(f, "Group at offset {offset} is not terminated")
+            }
+            ParseWarning::BlockPadding { offset, length } => {
+                write!(
+                    f,
+                    "Skipped {length} zero bytes between blocks at offset {offset}"
+                )
+            }
+            ParseWarning::NestedGroup { offset } => {
+                write!(f, "Group at offset {offset} is nested within another group")
+            }
+            ParseWarning::UnmatchedGroupEnd { offset } => {
+                write!(f, "Group end at offset {offset} is outside of a group")
+            }
+            ParseWarning::BlockCount { declared, read } => {
+                write!(f, "Read {read} blocks, but {declared} blocks are declared")
+            }
+            ParseWarning::UnknownBlock { offset, block_type } => {
+                write!(
+                    f,
+                    "Skipped unknown block {block_type:#06x} at offset {offset}"
+                )
+            }
+            ParseWarning::NameTerminator { offset } => {
+                write!(
+                    f,
+                    "Group at offset {offset} is missing the null terminator of its name"
+                )
+            }
+            ParseWarning::MalformedColor { offset, index } => {
+                write!(
+                    f,
+                    "Skipped color entry {index} of a group at offset {offset}, which cannot be read"
+                )
+            }
+            ParseWarning::GroupData { offset, length } => {
+                write!(
+                    f,
+                    "Skipped {length} bytes after the colors of a group at offset {offset}"
+                )
+            }
+            ParseWarning::NameLength { offset } => {
+                write!(
+                    f,
+                    "Block at offset {offset} has a name length, which does not count its null terminator"
+                )
+            }
+            ParseWarning::ColorModelCode { offset } => {
+                write!(
+                    f,
+                    "Color entry at offset {offset} has a misspelled color model code"
+                )
+            }
+            ParseWarning::ColorEntrySize { offset, declared } => {
+                write!(
+                    f,
+                    "Color entry at offset {offset} declares a length of {declared} bytes, which is too small"
+                )
+            }
+            ParseWarning::ColorEntryLength { offset, length } => {
+                write!(
+                    f,
+                    "Skipped {length} bytes after the color type of the color entry at offset {offset}"
+                )
+            }
+            ParseWarning::NameByteOrder { offset } => {
+                write!(
+                    f,
+                    "Read the name of the block at offset {offset} as little-endian"
+                )
+            }
+            ParseWarning::RgbRange { colors } => {
+                write!(
+                    f,
+                    "Divided {colors} RGB colors stored in the range 0 to 255"
+                )
+            }
+            ParseWarning::MergedGroups { groups } => {
+                write!(f, "Merged {groups} groups into groups with the same name")
+            }
+            ParseWarning::CorruptBlock { offset, length } => {
+                write!(
+                    f,
+                    "Skipped {length} bytes of a corrupt block at offset {offset}"
+                )
+            }
+            ParseWarning::TrailingData { offset, length } => {
+                write!(
+                    f,
+                    "Ignored {length} bytes after the last block at offset {offset}"
+                )
+            }
+        }
+    }
+}
+
+impl Display for BlockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockError::NullInName => write!(f, "Name contains a null character"),
+            BlockError::TooFewColors => write!(f, "Group has too few colors"),
+            BlockError::NameTooLong => write!(f, "Name is too long"),
+            BlockError::ComponentOutOfRange => {
+                write!(f, "Color component is outside of the valid range")
+            }
+            BlockError::NonFiniteComponent => write!(f, "Color component is not a finite number"),
+        }
+    }
+}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HexError::Length(length) => {
+                write!(f, "Hex code has {length} digits instead of 3, 6 or 8")
+            }
+            HexError::Digit(digit) => write!(f, "{digit:?} is not a hex digit"),
+        }
+    }
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::Blocks => write!(f, "blocks"),
+            Limit::NameLength => write!(f, "code units in a name"),
+            Limit::GroupColors => write!(f, "colors in a group"),
+            Limit::DecodedBytes => write!(f, "decoded bytes"),
+        }
+    }
+}
+
+impl std::error::Error for ASEError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ASEError::Block { error, .. }
+            | ASEError::Swatch { error, .. }
+            | ASEError::File { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 impl From<io::Error> for ASEError {
     fn from(value: io::Error) -> Self {
@@ -0,0 +1,667 @@
+use std::{collections::VecDeque, io::Read, mem, ops::ControlFlow};
+
+use crate::{
+    error::{ASEError, ConformationError},
+    reader::BlockReader,
+    types::GroupHold,
+    BlockType, ColorBlock, ColorType, ColorValue, Group,
+};
+
+/// A single event emitted by [`parse_ase_events`].
+///
+/// Names are borrowed from a buffer, which is reused for the following events.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AseEvent<'a> {
+    /// Start of a named group, the following colors are part of it until [`AseEvent::GroupEnd`].
+    GroupStart {
+        /// The name of the group
+        name: &'a str,
+    },
+    /// A single color.
+    Color {
+        /// The name associated with the color
+        name: &'a str,
+        /// The specific color value
+        value: ColorValue,
+        /// The type of color
+        color_type: ColorType,
+    },
+    /// End of the current group.
+    GroupEnd,
+}
+
+/// Read the .ase file as a sequence of events, without collecting the groups and colors.
+///
+/// Files are read the same way as [`read_ase`](crate::read_ase) reads them, groups without
+/// a `GroupEnd` block at the end of the file are closed with an [`AseEvent::GroupEnd`].
+/// Apart from growing its buffers, no memory is allocated for each event.
+///
+/// The `handler` may return [`ControlFlow::Break`] to stop reading early. In this case
+/// the reader is left directly after the block, which caused the event; colors are
+/// stored in their group block for files written by this crate.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid.
+///
+/// # Examples
+/// ```rust
+/// # use std::ops::ControlFlow;
+/// # use adobe_swatch_exchange::{create_ase, parse_ase_events, AseEvent, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color]);
+///
+/// let mut colors = 0;
+/// parse_ase_events(&*source, |event| {
+///     if let AseEvent::Color { .. } = event {
+///         colors += 1;
+///     }
+///     ControlFlow::Continue(())
+/// })
+/// .unwrap();
+/// # assert_eq!(colors, 1);
+/// ```
+pub fn parse_ase_events<T: Read>(
+    reader: T,
+    mut handler: impl FnMut(AseEvent<'_>) -> ControlFlow<()>,
+) -> Result<(), ASEError> {
+    let mut blocks = BlockReader::new(reader, false)?;
+
+    // scratch buffers, which are reused for every block
+    let mut data = Vec::new();
+    let mut name = String::new();
+
+    let mut group_hold = GroupHold::Empty;
+
+    loop {
+        let mut frame = match blocks.next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            // the file ends in place of the group end, like `read_ase` rejects it
+            Err(err) if group_hold != GroupHold::Empty && blocks.is_missing_last_block(&err) => {
+                return Err(ASEError::Invalid(ConformationError::GroupEnd))
+            }
+            Err(err) => return Err(err),
+        };
+        let block_type = frame.block_type;
+
+        if block_type != BlockType::GroupEnd && group_hold == GroupHold::HoldingBuilt {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+
+        frame.data = mem::take(&mut data);
+        blocks.read_data(&mut frame)?;
+        data = frame.data;
+
+        let flow = match block_type {
+            BlockType::GroupStart => {
+                // validate the group before emitting anything, so that errors are
+                // reported the same way as by `read_ase`
+                let mut colors: usize = 0;
+                for entry in Group::parse_into(&data, &mut name)? {
+                    if ColorBlock::parse_into(entry?.data, &mut name).is_err() {
+                        break;
+                    }
+                    colors = colors.saturating_add(1);
+                }
+                if group_hold != GroupHold::Empty {
+                    return Err(ASEError::Invalid(ConformationError::GroupEnd));
+                }
+
+                let entries = Group::parse_into(&data, &mut name)?;
+                if handler(AseEvent::GroupStart { name: &name }).is_break() {
+                    return Ok(());
+                }
+
+                // if the group has any sub-blocks then it has already been built
+                // and only a group-end block may follow it.
+                group_hold = if colors == 0 {
+                    GroupHold::HoldingBuilding
+                } else {
+                    blocks.expect_additional_block();
+                    GroupHold::HoldingBuilt
+                };
+
+                let mut flow = ControlFlow::Continue(());
+                for entry in entries.take(colors) {
+                    let (value, color_type) = ColorBlock::parse_into(entry?.data, &mut name)?;
+                    flow = handler(AseEvent::Color {
+                        name: &name,
+                        value,
+                        color_type,
+                    });
+                    if flow.is_break() {
+                        break;
+                    }
+                }
+                flow
+            }
+            BlockType::GroupEnd => match group_hold {
+                GroupHold::HoldingBuilding | GroupHold::HoldingBuilt => {
+                    group_hold = GroupHold::Empty;
+                    handler(AseEvent::GroupEnd)
+                }
+                GroupHold::Empty => return Err(ASEError::Invalid(ConformationError::GroupEnd)),
+            },
+            BlockType::ColorEntry => {
+                let (value, color_type) = ColorBlock::parse_into(&data, &mut name)?;
+                handler(AseEvent::Color {
+                    name: &name,
+                    value,
+                    color_type,
+                })
+            }
+            // rejected by the block reader
+            BlockType::Raw(_) => return Err(ASEError::BlockTypeError),
+        };
+
+        if flow.is_break() {
+            return Ok(());
+        }
+    }
+
+    match group_hold {
+        // close the last group, even if no end was found
+        GroupHold::HoldingBuilding => {
+            let _ = handler(AseEvent::GroupEnd);
+            Ok(())
+        }
+        // a built group, which was not terminated, is an error.
+        GroupHold::HoldingBuilt => Err(ASEError::Invalid(ConformationError::GroupEnd)),
+        GroupHold::Empty => Ok(()),
+    }
+}
+
+/// A single event read by an [`AseReader`], like an [`AseEvent`], but owning its data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadEvent {
+    /// Start of a named group, the following colors are part of it until [`ReadEvent::GroupEnd`].
+    GroupStart(String),
+    /// A single color.
+    Color(ColorBlock),
+    /// End of the current group.
+    GroupEnd,
+}
+
+impl From<AseEvent<'_>> for ReadEvent {
+    fn from(value: AseEvent<'_>) -> Self {
+        match value {
+            AseEvent::GroupStart { name } => ReadEvent::GroupStart(name.to_owned()),
+            AseEvent::Color {
+                name,
+                value,
+                color_type,
+            } => ReadEvent::Color(ColorBlock::new(name.to_owned(), value, color_type)),
+            AseEvent::GroupEnd => ReadEvent::GroupEnd,
+        }
+    }
+}
+
+/// Collects the events into groups and single colors, like [`read_ase`](crate::read_ase)
+/// returns them.
+#[cfg(any(test, feature = "tokio"))]
+pub(crate) fn collect_events(
+    events: impl IntoIterator<Item = ReadEvent>,
+) -> (Vec<Group>, Vec<ColorBlock>) {
+    let mut groups = Vec::new();
+    let mut colors = Vec::new();
+    let mut group: Option<Group> = None;
+    for event in events {
+        match event {
+            ReadEvent::GroupStart(name) => group = Some(Group::new(name, Vec::new())),
+            ReadEvent::Color(color) => match group.as_mut() {
+                Some(group) => group.blocks.push(color),
+                None => colors.push(color),
+            },
+            ReadEvent::GroupEnd => groups.extend(group.take()),
+        }
+    }
+    (groups, colors)
+}
+
+/// Reads an .ase file as an iterator of [`ReadEvent`]s, reading one block at a time.
+///
+/// Files are read the same way as [`read_ase`](crate::read_ase) reads them, groups without
+/// a `GroupEnd` block at the end of the file are closed with a [`ReadEvent::GroupEnd`].
+/// After an error, the iterator returns `None`.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, AseReader, ColorBlock, ColorType, ColorValue, ReadEvent};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color.clone()]);
+///
+/// let found = AseReader::new(&*source)
+///     .unwrap()
+///     .find_map(|event| match event {
+///         Ok(ReadEvent::Color(block)) if block.name == "name" => Some(Ok(block)),
+///         Ok(_) => None,
+///         Err(err) => Some(Err(err)),
+///     });
+/// assert_eq!(found.unwrap().unwrap(), color);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseReader<T> {
+    blocks: BlockReader<T>,
+    group_hold: GroupHold,
+    /// Events read, but not returned yet.
+    pending: VecDeque<ReadEvent>,
+    done: bool,
+}
+
+impl<T> AseReader<T> {
+    /// Continues reading the events from another reader, keeping the current state.
+    pub(crate) fn with_reader<U>(self, reader: U) -> AseReader<U> {
+        AseReader {
+            blocks: self.blocks.with_reader(reader),
+            group_hold: self.group_hold,
+            pending: self.pending,
+            done: self.done,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub(crate) fn get_ref(&self) -> &T {
+        self.blocks.get_ref()
+    }
+
+    /// Adds the location of the block, which is currently read, to the error.
+    pub(crate) fn locate(&self, error: ASEError) -> ASEError {
+        self.blocks.locate(error)
+    }
+
+    /// Removes the events read, but not returned yet.
+    pub(crate) fn take_pending(&mut self) -> impl Iterator<Item = ReadEvent> + '_ {
+        self.pending.drain(..)
+    }
+}
+
+impl<T: Read> AseReader<T> {
+    /// Reads the header of the file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either a read to the given data fails,
+    /// or the file signature or version are invalid.
+    pub fn new(reader: T) -> Result<Self, ASEError> {
+        Ok(Self {
+            blocks: BlockReader::new(reader, false)?,
+            group_hold: GroupHold::Empty,
+            pending: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    /// Reads the next block, adding its events to the pending events.
+    ///
+    /// Returns `false` once all blocks have been read, the pending events may still
+    /// contain the end of the last group.
+    pub(crate) fn read_block(&mut self) -> Result<bool, ASEError> {
+        let frame = match self.blocks.next_frame() {
+            // the file ends in place of the group end, like `read_ase` rejects it
+            Err(err)
+                if self.group_hold != GroupHold::Empty
+                    && self.blocks.is_missing_last_block(&err) =>
+            {
+                return Err(ASEError::Invalid(ConformationError::GroupEnd));
+            }
+            frame => frame?,
+        };
+        let Some(mut frame) = frame else {
+            return match self.group_hold {
+                // close the last group, even if no end was found, which ends the file
+                GroupHold::HoldingBuilding => {
+                    self.group_hold = GroupHold::Empty;
+                    self.pending.push_back(ReadEvent::GroupEnd);
+                    Ok(false)
+                }
+                // a built group, which was not terminated, is an error.
+                GroupHold::HoldingBuilt => Err(ASEError::Invalid(ConformationError::GroupEnd)),
+                GroupHold::Empty => Ok(false),
+            };
+        };
+        let block_type = frame.block_type;
+
+        if block_type != BlockType::GroupEnd && self.group_hold == GroupHold::HoldingBuilt {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+        self.blocks.read_data(&mut frame)?;
+
+        match block_type {
+            BlockType::GroupStart => {
+                let group = Group::parse(&frame.data)?;
+                if self.group_hold != GroupHold::Empty {
+                    return Err(ASEError::Invalid(ConformationError::GroupEnd));
+                }
+                // if the group has any sub-blocks then it has already been built
+                // and only a group-end block may follow it.
+                self.group_hold = if group.blocks.is_empty() {
+                    GroupHold::HoldingBuilding
+                } else {
+                    self.blocks.expect_additional_block();
+                    GroupHold::HoldingBuilt
+                };
+                self.pending.push_back(ReadEvent::GroupStart(group.name));
+                self.pending
+                    .extend(group.blocks.into_iter().map(ReadEvent::Color));
+            }
+            BlockType::GroupEnd => match self.group_hold {
+                GroupHold::HoldingBuilding | GroupHold::HoldingBuilt => {
+                    self.group_hold = GroupHold::Empty;
+                    self.pending.push_back(ReadEvent::GroupEnd);
+                }
+                GroupHold::Empty => return Err(ASEError::Invalid(ConformationError::GroupEnd)),
+            },
+            BlockType::ColorEntry => {
+                let block = ColorBlock::parse(&frame.data)?;
+                self.pending.push_back(ReadEvent::Color(block));
+            }
+            // rejected by the block reader
+            BlockType::Raw(_) => return Err(ASEError::BlockTypeError),
+        }
+        Ok(true)
+    }
+}
+
+impl<T: Read> Iterator for AseReader<T> {
+    type Item = Result<ReadEvent, ASEError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+            match self.read_block() {
+                Ok(more) => self.done = !more,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(self.locate(err)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{create_ase, read_ase, read_ase_with_options, test_util::named, ParseOptions};
+
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Statistics {
+        groups: usize,
+        colors: usize,
+        grouped_colors: usize,
+        name_length: usize,
+        open: bool,
+    }
+
+    impl Statistics {
+        fn add(&mut self, event: AseEvent<'_>) -> ControlFlow<()> {
+            match event {
+                AseEvent::GroupStart { name } => {
+                    assert!(!self.open, "groups must not be nested");
+                    self.open = true;
+                    self.groups += 1;
+                    self.name_length += name.len();
+                }
+                AseEvent::Color { name, .. } => {
+                    self.colors += 1;
+                    self.grouped_colors += usize::from(self.open);
+                    self.name_length += name.len();
+                }
+                AseEvent::GroupEnd => {
+                    assert!(self.open, "group end without start");
+                    self.open = false;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn collect(input: &[u8]) -> Result<Vec<String>, ASEError> {
+        let mut events = Vec::new();
+        parse_ase_events(input, |event| {
+            events.push(format!("{event:?}"));
+            ControlFlow::Continue(())
+        })?;
+        Ok(events)
+    }
+
+    fn group_and_color() -> Vec<u8> {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        create_ase(vec![group], vec![block])
+    }
+
+    #[test]
+    fn it_emits_group_and_single_color() {
+        assert_eq!(
+            collect(&group_and_color()).unwrap(),
+            vec![
+                "GroupStart { name: \"group name\" }",
+                "Color { name: \"light grey\", value: Gray(0.5), color_type: Normal }",
+                "Color { name: \"dark red\", value: Rgb(0.5, 0.3, 0.1), color_type: Normal }",
+                "GroupEnd",
+                "Color { name: \"name\", value: Gray(0.5), color_type: Normal }",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_emits_group_block_name_only_size() {
+        let input_ase_bytes = group_and_color();
+        let mut modified_ase_bytes = Vec::new();
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..]);
+
+        assert_eq!(
+            collect(&modified_ase_bytes).unwrap(),
+            collect(&input_ase_bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_closes_unterminated_group() {
+        let input_ase_bytes = group_and_color();
+        let mut modified_ase_bytes = Vec::new();
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(3_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..126]);
+
+        let events = collect(&modified_ase_bytes).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[3], "GroupEnd");
+
+        // the group end closes the file
+        let mut reader = AseReader::new(&*modified_ase_bytes).unwrap();
+        assert_eq!(reader.by_ref().count(), 4);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn it_aggregates_large_file() {
+        let groups = (0..100)
+            .map(|group| {
+                Group::new(
+                    format!("group {group}"),
+                    (0..100)
+                        .map(|color| named(&format!("color {color}"), ColorValue::Gray(0.5)))
+                        .collect(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let colors = (0..1000)
+            .map(|color| named(&format!("single {color}"), ColorValue::Gray(0.5)))
+            .collect::<Vec<_>>();
+        let input = create_ase(groups, colors);
+
+        let mut statistics = Statistics::default();
+        parse_ase_events(&*input, |event| statistics.add(event)).unwrap();
+
+        let (groups, colors) = read_ase(&*input).unwrap();
+        let grouped_colors = groups.iter().map(|group| group.blocks.len()).sum::<usize>();
+        let name_length = groups
+            .iter()
+            .flat_map(|group| {
+                std::iter::once(&group.name).chain(group.blocks.iter().map(|b| &b.name))
+            })
+            .chain(colors.iter().map(|block| &block.name))
+            .map(String::len)
+            .sum();
+        assert_eq!(
+            statistics,
+            Statistics {
+                groups: groups.len(),
+                colors: grouped_colors + colors.len(),
+                grouped_colors,
+                name_length,
+                open: false,
+            }
+        );
+    }
+
+    #[test]
+    fn it_stops_early() {
+        let input = group_and_color();
+        let mut cursor = Cursor::new(&input);
+        let mut events = 0;
+        parse_ase_events(&mut cursor, |_| {
+            events += 1;
+            ControlFlow::Break(())
+        })
+        .unwrap();
+        assert_eq!(events, 1);
+        // the reader is left after the group block
+        assert_eq!(cursor.position(), 126);
+
+        // stopping on the last nested color leaves the reader at the same position
+        let mut cursor = Cursor::new(&input);
+        let mut events = 0;
+        parse_ase_events(&mut cursor, |_| {
+            events += 1;
+            if events == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert_eq!(events, 3);
+        assert_eq!(cursor.position(), 126);
+    }
+
+    /// Returns the events read by an [`AseReader`] and [`parse_ase_events`].
+    fn read_events(input: &[u8]) -> (Result<Vec<ReadEvent>, ASEError>, Vec<ReadEvent>) {
+        let mut events = Vec::new();
+        let _ = parse_ase_events(input, |event| {
+            events.push(event.into());
+            ControlFlow::Continue(())
+        });
+        (
+            AseReader::new(input).and_then(|reader| reader.collect()),
+            events,
+        )
+    }
+
+    #[test]
+    fn it_iterates_events() {
+        let (events, expected) = read_events(&group_and_color());
+        let events = events.unwrap();
+        assert_eq!(events, expected);
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0], ReadEvent::GroupStart("group name".to_owned()));
+        assert_eq!(events[3], ReadEvent::GroupEnd);
+
+        for entry in std::fs::read_dir(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/lenient"
+        ))
+        .unwrap()
+        {
+            let input = std::fs::read(entry.unwrap().path()).unwrap();
+            let (events, expected) = read_events(&input);
+            let events = events.unwrap();
+            assert_eq!(events, expected);
+            let (groups, colors) = read_ase(&*input).unwrap();
+            let read_colors = events
+                .iter()
+                .filter(|event| matches!(event, ReadEvent::Color(_)))
+                .count();
+            assert_eq!(
+                read_colors,
+                colors.len() + groups.iter().map(|group| group.blocks.len()).sum::<usize>()
+            );
+        }
+
+        for file in [
+            "missing_group_end.ase",
+            "truncated.ase",
+            "unknown_block_type.ase",
+            "group_end_chopped_flat.ase",
+        ] {
+            let input = std::fs::read(format!(
+                "{}/tests/fixtures/invalid/{file}",
+                env!("CARGO_MANIFEST_DIR")
+            ))
+            .unwrap();
+            let mut reader = AseReader::new(&*input).unwrap();
+            let err = reader.find_map(Result::err).expect(file);
+            assert_eq!(
+                err.to_string(),
+                read_ase_with_options(&*input, &ParseOptions::DEFAULT)
+                    .unwrap_err()
+                    .to_string(),
+                "{file}"
+            );
+            // nothing is read after an error
+            assert!(reader.next().is_none());
+        }
+    }
+
+    #[test]
+    fn it_iterates_lazily() {
+        let input = group_and_color();
+        let mut cursor = Cursor::new(&input);
+        let mut reader = AseReader::new(&mut cursor).unwrap();
+        assert_eq!(
+            reader.nth(1).unwrap().unwrap(),
+            ReadEvent::Color(named("light grey", ColorValue::Gray(0.5)))
+        );
+        drop(reader);
+        // only the group block was read
+        assert_eq!(cursor.position(), 126);
+    }
+
+    #[test]
+    fn it_returns_error_on_missing_group_end() {
+        let input = group_and_color();
+        let mut modified = input[..126].to_vec();
+        modified.extend_from_slice(&input[128..]);
+        assert!(
+            matches!(
+                collect(&modified),
+                Err(ASEError::Invalid(ConformationError::GroupEnd))
+            ),
+            "Only ASEError::Invalid(error::ConformationError::GroupEnd) should be returned"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::types;
+
+/// Signature of Adobe Color Book (ACB) files.
+const ACB_SIGNATURE: &[u8; 4] = b"8BCB";
+
+/// Color space identifiers, which may appear in Adobe Color (ACO) files.
+///
+/// These are RGB, HSB, CMYK, Lab, Grayscale and wide CMYK.
+const ACO_COLOR_SPACES: [u16; 6] = [0, 1, 2, 7, 8, 9];
+
+/// The format of a swatch file, as detected by [`detect_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwatchFormat {
+    /// Adobe Swatch Exchange, starting with the `ASEF` signature.
+    Ase,
+    /// Photoshop Adobe Color swatches, with the given version.
+    Aco {
+        /// Version of the file, either `1` or `2`.
+        version: u16,
+    },
+    /// Adobe Color Book, starting with the `8BCB` signature.
+    Acb,
+    /// Not a known swatch format.
+    Unknown,
+}
+
+/// Detect the format of a swatch file from its first bytes.
+///
+/// ACO files have no signature, instead they are recognized by their version word,
+/// followed by the number of colors and, if there are any, a known color space.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{detect_format, SwatchFormat};
+/// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0];
+/// assert_eq!(detect_format(&source), SwatchFormat::Ase);
+/// ```
+pub fn detect_format(data: &[u8]) -> SwatchFormat {
+    if is_ase(data) {
+        return SwatchFormat::Ase;
+    }
+
+    if data.starts_with(ACB_SIGNATURE) {
+        return SwatchFormat::Acb;
+    }
+
+    let read_u16 = |index: usize| {
+        data.get(index..index.saturating_add(2))
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u16::from_be_bytes)
+    };
+
+    match (read_u16(0), read_u16(2)) {
+        (Some(version @ (1 | 2)), Some(0)) => SwatchFormat::Aco { version },
+        (Some(version @ (1 | 2)), Some(_))
+            if read_u16(4).map_or(false, |space| ACO_COLOR_SPACES.contains(&space)) =>
+        {
+            SwatchFormat::Aco { version }
+        }
+        _ => SwatchFormat::Unknown,
+    }
+}
+
+/// Returns `true` if the data starts with the ASE file signature.
+///
+/// This does not validate anything beyond the signature, use [`read_ase`](crate::read_ase)
+/// for that.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::is_ase;
+/// assert!(is_ase(b"ASEF"));
+/// assert!(!is_ase(b"8BCB"));
+/// ```
+pub fn is_ase(data: &[u8]) -> bool {
+    data.starts_with(types::FILE_SIGNATURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_detects_ase() {
+        let data = crate::create_ase(vec![], vec![]);
+        assert!(is_ase(&data));
+        assert_eq!(detect_format(&data), SwatchFormat::Ase);
+    }
+
+    #[test]
+    fn it_detects_aco() {
+        // version 1, one RGB color
+        let data = [0, 1, 0, 1, 0, 0, 255, 255, 0, 0, 0, 0, 0, 0];
+        assert!(!is_ase(&data));
+        assert_eq!(detect_format(&data), SwatchFormat::Aco { version: 1 });
+        // version 2, no colors
+        assert_eq!(
+            detect_format(&[0, 2, 0, 0]),
+            SwatchFormat::Aco { version: 2 }
+        );
+    }
+
+    #[test]
+    fn it_detects_acb() {
+        let data = b"8BCB\0\x01\0\0";
+        assert!(!is_ase(data));
+        assert_eq!(detect_format(data), SwatchFormat::Acb);
+    }
+
+    #[test]
+    fn it_rejects_short_input() {
+        for data in [&b""[..], b"A", b"ASE", b"8BC", &[0, 1], &[0, 1, 0]] {
+            assert!(!is_ase(data));
+            assert_eq!(detect_format(data), SwatchFormat::Unknown);
+        }
+    }
+
+    #[test]
+    fn it_rejects_unknown_aco_color_space() {
+        // version 1, one color with color space 3, which is not used by ACO
+        let data = [0, 1, 0, 1, 0, 3, 255, 255, 0, 0, 0, 0, 0, 0];
+        assert_eq!(detect_format(&data), SwatchFormat::Unknown);
+    }
+
+    #[test]
+    fn it_rejects_random_bytes() {
+        // simple xorshift, so that the test is deterministic
+        let mut state = 0x2545_f491_u32;
+        for _ in 0..64 {
+            let data: Vec<u8> = (0..32)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect();
+            assert!(!is_ase(&data));
+            assert_eq!(detect_format(&data), SwatchFormat::Unknown);
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{
+    error::ASEError, reader::BlockReader, types, BlockProvenance, BlockType, ColorBlock,
+    ColorEntry, Document, EntryLayout, Group, MalformedEntry, Owned, ParseOptions,
+    ProvenanceTarget, METADATA_GROUP_PREFIX,
+};
+
+/// The location of a group or single color in an .ase file, see [`AseIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// Type of the first block, either [`BlockType::GroupStart`] or [`BlockType::ColorEntry`].
+    pub block_type: BlockType,
+    /// Offset of the block identifier from the start of the file.
+    pub offset: u64,
+    /// Number of bytes from the offset up to the next group or color, including the colors
+    /// and the end of a group.
+    pub length: u64,
+    /// The name of the group or color.
+    pub name: String,
+}
+
+/// Locates the groups and single colors of an .ase file, so that they can be read one
+/// at a time, without reading the whole file again.
+///
+/// # Examples
+/// ```rust
+/// # use std::io::Cursor;
+/// # use adobe_swatch_exchange::{create_ase, AseIndex, ColorBlock, ColorType, ColorValue, Group};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let groups = vec![
+///     Group::new("first".to_owned(), vec![color.clone()]),
+///     Group::new("second".to_owned(), vec![color.clone()]),
+/// ];
+/// let mut source = Cursor::new(create_ase(groups.clone(), vec![color]));
+///
+/// let index = AseIndex::build(&mut source).unwrap();
+/// assert_eq!(index.group(1).unwrap().name, "second");
+/// assert_eq!(index.read_group(&mut source, 1).unwrap(), Some(groups[1].clone()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseIndex {
+    /// The groups and single colors in the order they are stored.
+    entries: Vec<IndexEntry>,
+    /// Positions of the groups in the entries.
+    groups: Vec<usize>,
+    /// Positions of the single colors in the entries.
+    colors: Vec<usize>,
+    /// Position of the file in the reader.
+    start: u64,
+    /// The options used for reading the file.
+    options: ParseOptions,
+}
+
+impl AseIndex {
+    /// Reads the file like [`read_ase`](crate::read_ase), keeping only the location and
+    /// name of every group and single color.
+    ///
+    /// The file is read from the current position of the reader, which should be buffered.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either a read to the given data fails,
+    /// or the ASE file is invalid.
+    pub fn build<R: Read + Seek>(reader: R) -> Result<Self, ASEError> {
+        Self::build_with_options(reader, &ParseOptions::DEFAULT)
+    }
+
+    /// Reads the file like [`read_ase_with_options`](crate::read_ase_with_options),
+    /// keeping only the location and name of every group and single color.
+    ///
+    /// The groups and colors are read with the same options.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either a read to the given data fails,
+    /// or the ASE file is invalid.
+    pub fn build_with_options<R: Read + Seek>(
+        mut reader: R,
+        options: &ParseOptions,
+    ) -> Result<Self, ASEError> {
+        let start = reader.stream_position()?;
+        let mut blocks = BlockReader::with_options(reader, options)?;
+        let mut document = Names(Owned::new(options));
+        let mut provenance = Vec::new();
+        let read = crate::parse_blocks(
+            &mut blocks,
+            &mut document,
+            options,
+            Some(&mut provenance),
+            false,
+        )
+        .map_err(|err| blocks.locate(err))?;
+
+        let mut index = Self {
+            entries: Vec::new(),
+            groups: Vec::new(),
+            colors: Vec::new(),
+            start,
+            options: options.clone(),
+        };
+        let spans = spans(&provenance, read.groups.len(), read.info.bytes_read);
+        let mut groups: Vec<_> = read.groups.into_iter().map(Some).collect();
+        let mut colors: Vec<_> = read.colors.into_iter().map(Some).collect();
+        for (target, offset, end) in spans {
+            let (block_type, name, positions) = match target {
+                ProvenanceTarget::Group(position) => {
+                    let Some(group) = groups.get_mut(position).and_then(Option::take) else {
+                        continue;
+                    };
+                    // metadata groups are not sanitized, see `Ase::sanitize_names`
+                    let metadata =
+                        group.colors.is_empty() && group.name.starts_with(METADATA_GROUP_PREFIX);
+                    let name = index_name(group.name, options.sanitize_names && !metadata);
+                    (BlockType::GroupStart, name, &mut index.groups)
+                }
+                ProvenanceTarget::Color(position) => {
+                    let Some(name) = colors.get_mut(position).and_then(Option::take) else {
+                        continue;
+                    };
+                    let name = index_name(name, options.sanitize_names);
+                    (BlockType::ColorEntry, name, &mut index.colors)
+                }
+                ProvenanceTarget::GroupColor(..) => continue,
+            };
+            positions.push(index.entries.len());
+            index.entries.push(IndexEntry {
+                block_type,
+                offset,
+                length: end.saturating_sub(offset),
+                name,
+            });
+        }
+        Ok(index)
+    }
+
+    /// Returns the groups and single colors in the order they are stored.
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// Returns the group at `index`, in the order [`read_ase`](crate::read_ase) returns
+    /// the groups.
+    pub fn group(&self, index: usize) -> Option<&IndexEntry> {
+        self.groups
+            .get(index)
+            .and_then(|position| self.entries.get(*position))
+    }
+
+    /// Returns the single color at `index`, in the order [`read_ase`](crate::read_ase)
+    /// returns the colors.
+    pub fn color(&self, index: usize) -> Option<&IndexEntry> {
+        self.colors
+            .get(index)
+            .and_then(|position| self.entries.get(*position))
+    }
+
+    /// Reads the group at `index` from the file the index was built from, returns `None`
+    /// if there is no such group.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either seeking or a read to the given data
+    /// fails, or the group is invalid.
+    pub fn read_group<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        index: usize,
+    ) -> Result<Option<Group>, ASEError> {
+        let Some(entry) = self.group(index) else {
+            return Ok(None);
+        };
+        // nested groups are read after the group containing them
+        self.read_entry(reader, entry)?
+            .groups
+            .into_iter()
+            .next()
+            .map(Some)
+            .ok_or(ASEError::InputDataParseError)
+    }
+
+    /// Reads the single color at `index` from the file the index was built from, returns
+    /// `None` if there is no such color.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either seeking or a read to the given data
+    /// fails, or the color is invalid.
+    pub fn read_color<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        index: usize,
+    ) -> Result<Option<ColorBlock>, ASEError> {
+        let Some(entry) = self.color(index) else {
+            return Ok(None);
+        };
+        self.read_entry(reader, entry)?
+            .colors
+            .into_iter()
+            .next()
+            .map(Some)
+            .ok_or(ASEError::InputDataParseError)
+    }
+
+    /// Reads the blocks of the entry, the same way they were read when building the index.
+    fn read_entry<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        entry: &IndexEntry,
+    ) -> Result<crate::Blocks, ASEError> {
+        reader.seek(SeekFrom::Start(self.start.saturating_add(entry.offset)))?;
+        let mut blocks = BlockReader::at(reader.take(entry.length), entry.offset, &self.options);
+        crate::read_document(&mut blocks, &self.options, None, false)
+    }
+}
+
+/// Returns the name, sanitized like [`ParseOptions::sanitize_names`] if `sanitize` is set.
+fn index_name(mut name: String, sanitize: bool) -> String {
+    if sanitize {
+        types::sanitize_name(&mut name);
+    }
+    name
+}
+
+/// Returns the groups and single colors in the order they are stored, together with the
+/// offset of their first block and the offset, at which they end.
+///
+/// Every group or color ends at the first group or color following its last block, so
+/// the end of a group and any skipped blocks belong to the preceding group or color.
+fn spans(
+    provenance: &[BlockProvenance],
+    groups: usize,
+    end: u64,
+) -> Vec<(ProvenanceTarget, u64, u64)> {
+    // the offset of the last block of every group
+    let mut last_blocks = vec![0; groups];
+    let mut starts = Vec::new();
+    for block in provenance {
+        match block.target {
+            ProvenanceTarget::Group(index) | ProvenanceTarget::GroupColor(index, _) => {
+                if let Some(last) = last_blocks.get_mut(index) {
+                    *last = block.offset.max(*last);
+                }
+            }
+            ProvenanceTarget::Color(_) => {}
+        }
+        if !matches!(block.target, ProvenanceTarget::GroupColor(..)) {
+            starts.push((block.target, block.offset));
+        }
+    }
+
+    starts
+        .iter()
+        .map(|&(target, offset)| {
+            let last = match target {
+                ProvenanceTarget::Group(index) => last_blocks.get(index).copied(),
+                _ => None,
+            }
+            .unwrap_or(offset);
+            let next = starts.partition_point(|(_, start)| *start <= last);
+            let end = starts.get(next).map_or(end, |(_, start)| *start);
+            (target, offset, end)
+        })
+        .collect()
+}
+
+/// A group, of which only the names are kept, see [`Names`].
+#[derive(Default)]
+struct NamedGroup {
+    name: String,
+    colors: Vec<String>,
+}
+
+/// Builds groups and colors like [`Owned`], keeping only their names.
+struct Names(Owned);
+
+impl Document for Names {
+    type Group = NamedGroup;
+    type Color = String;
+
+    fn parse_group(
+        &mut self,
+        data: &[u8],
+        malformed: MalformedEntry,
+        inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<(Self::Group, usize), ASEError> {
+        let (group, end) = self.0.parse_group(data, malformed, inspect)?;
+        let colors = group.blocks.into_iter().map(|block| block.name).collect();
+        Ok((
+            NamedGroup {
+                name: group.name,
+                colors,
+            },
+            end,
+        ))
+    }
+
+    fn parse_color(&mut self, data: &[u8]) -> Result<(Self::Color, EntryLayout), ASEError> {
+        let (color, layout) = self.0.parse_color(data)?;
+        Ok((color.name, layout))
+    }
+
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color> {
+        &mut group.colors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        create_ase, create_ase_with_options, read_ase, test_util::named, ColorValue, GroupLayout,
+        WriteOptions,
+    };
+
+    use super::*;
+
+    fn groups(count: usize) -> Vec<Group> {
+        (0..count)
+            .map(|index| {
+                Group::new(
+                    format!("group {index}"),
+                    vec![
+                        named(&format!("light {index}"), ColorValue::Gray(0.75)),
+                        named(
+                            &format!("dark {index}"),
+                            ColorValue::Gray(index as f32 / 1000.0),
+                        ),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_reads_groups_of_large_files() {
+        let colors = vec![named("single", ColorValue::Rgb(1.0, 0.0, 0.0))];
+        let groups = groups(1000);
+        let mut source = Cursor::new(create_ase(groups.clone(), colors.clone()));
+
+        let index = AseIndex::build(&mut source).unwrap();
+        assert_eq!(index.entries().len(), 1001);
+        assert_eq!(index.group(500).unwrap().name, "group 500");
+        assert_eq!(index.color(0).unwrap().name, "single");
+        for position in [0, 500, 999] {
+            assert_eq!(
+                index.read_group(&mut source, position).unwrap().as_ref(),
+                Some(&groups[position])
+            );
+        }
+        assert_eq!(
+            index.read_color(&mut source, 0).unwrap(),
+            Some(colors[0].clone())
+        );
+        assert_eq!(index.read_group(&mut source, 1000).unwrap(), None);
+        assert_eq!(index.read_color(&mut source, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn it_reads_groups_with_separate_colors() {
+        let options = WriteOptions {
+            group_layout: GroupLayout::Flat,
+            ..Default::default()
+        };
+        let groups = groups(3);
+        let colors = vec![named("single", ColorValue::Gray(0.5))];
+        let source = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+
+        let index = AseIndex::build(Cursor::new(&source)).unwrap();
+        let entry = index.group(1).unwrap();
+        // the group start, both colors and the group end
+        assert_eq!(entry.length, 24 + 34 + 32 + 2);
+        assert_eq!(entry.block_type, BlockType::GroupStart);
+
+        let mut source = Cursor::new(source);
+        assert_eq!(
+            index.read_group(&mut source, 2).unwrap(),
+            Some(groups[2].clone())
+        );
+        assert_eq!(
+            index.read_color(&mut source, 0).unwrap(),
+            Some(colors[0].clone())
+        );
+    }
+
+    #[test]
+    fn it_reads_from_the_position_of_the_file() {
+        let groups = groups(2);
+        let mut source = b"prefix".to_vec();
+        source.extend(create_ase(groups.clone(), vec![]));
+        let mut source = Cursor::new(source);
+        source.set_position(6);
+
+        let index = AseIndex::build(&mut source).unwrap();
+        assert_eq!(index.group(0).unwrap().offset, 12);
+        assert_eq!(
+            index.read_group(&mut source, 1).unwrap(),
+            Some(groups[1].clone())
+        );
+    }
+
+    #[test]
+    fn it_reads_groups_like_read_ase() {
+        // the first group is followed by the zero length of its group end
+        let source = include_bytes!("../tests/fixtures/lenient/group_end_length.ase");
+        let (groups, _) = read_ase(&source[..]).unwrap();
+        let mut source = Cursor::new(&source[..]);
+
+        let index = AseIndex::build(&mut source).unwrap();
+        for (position, group) in groups.iter().enumerate() {
+            assert_eq!(index.group(position).unwrap().name, group.name);
+            assert_eq!(
+                index.read_group(&mut source, position).unwrap().as_ref(),
+                Some(group)
+            );
+        }
+    }
+}
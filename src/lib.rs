@@ -1,17 +1,74 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(unsafe_code)]
+// reading runs on untrusted input, so it must not panic
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::indexing_slicing,
+        clippy::arithmetic_side_effects,
+        clippy::unwrap_used
+    )
+)]
 #![doc = include_str!("../README.md")]
 
-pub use error::{ASEError, ConformationError};
-use types::{BlockType, GroupHold};
-pub use types::{ColorBlock, ColorType, ColorValue, Group};
+#[cfg(feature = "tokio")]
+pub use async_io::{read_ase_async, write_ase_async, AsyncAseWriter};
+pub use borrowed::{read_ase_borrowed, read_ase_bytes};
+pub use error::{ASEError, BlockError, ConformationError, HexError, Limit, ParseWarning};
+pub use events::{parse_ase_events, AseEvent, AseReader, ReadEvent};
+pub use format::{detect_format, is_ase, SwatchFormat};
+pub use index::{AseIndex, IndexEntry};
+pub use metadata::{
+    metadata_path, read_ase_with_metadata, write_ase_with_metadata, PaletteMetadata,
+    METADATA_GROUP_PREFIX,
+};
+pub use parser::AseParser;
+pub use reader::{Limits, NameByteOrder, ParseOptions};
+pub use roundtrip::{assert_roundtrip, RoundtripError};
+use trace::{trace_event, trace_record, trace_span};
+pub use types::{
+    escape_path_segment, Ase, AseHeader, Block, BlockProvenance, BlockType, ColorBlock,
+    ColorBlockRef, ColorModel, ColorType, ColorValue, ContrastPair, CssGradientOptions, FileInfo,
+    GgrBlend, GrayPolarity, Group, GroupRef, HueBucket, MatchBy, NameMatch, ProvenanceTarget,
+    RawBlock, RgbRange, FILE_VERSION,
+};
+use types::{ColorEntries, ColorEntry, EntryLayout, GroupHold, GroupName, MalformedEntry};
+pub use validate::{ase_statistics, validate_ase, AseStats, AseSummary, ConformanceLevel};
+pub use visitor::{visit_blocks, BlockVisitor};
+use wire::Terminator;
+pub use writer::{
+    AseWriter, ColorTypeMap, GroupLayout, LabScaling, NameEncoding, NameReplacement, WriteOptions,
+    WriteReport,
+};
 
+#[cfg(feature = "tokio")]
+mod async_io;
+mod borrowed;
 mod buffer;
+pub mod codegen;
+pub mod conformance;
 mod error;
+mod events;
+mod format;
+mod index;
+mod metadata;
+mod parser;
+mod reader;
+mod roundtrip;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+mod trace;
 mod types;
+mod validate;
+mod visitor;
+pub mod wire;
+mod writer;
 
 /// Creates an Adobe Swatch Exchange (ASE) file.
 ///
+/// Names with more than `65534` UTF-16 code units are truncated to fit into their length
+/// field, use [`create_ase_checked`] or [`create_ase_with_options`] to reject them instead.
+///
 /// # Examples
 /// ```rust
 /// # use adobe_swatch_exchange::ColorBlock;
@@ -22,33 +79,493 @@ mod types;
 /// let ase = create_ase(vec![], vec![color]);
 /// # assert_eq!( ase, vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2]);
 /// ```
-pub fn create_ase(groups: Vec<Group>, colors: Vec<ColorBlock>) -> Vec<u8> {
-    let group_size: u32 = groups.iter().map(Group::calculate_length).sum();
-    let color_size: u32 = colors.iter().map(ColorBlock::calculate_length).sum();
-    // we slightly over-estimate the required amount of space here, to avoid a costly resizing
-    let mut buf = buffer::Buffer::with_capacity((8 + group_size * 2 + color_size) as usize);
-
-    // file metadata
-    buf.write_slice(types::FILE_SIGNATURE);
-    buf.write_u32(types::VERSION);
-    // number of blocks
-    buf.write_u32((groups.len() + colors.len()) as u32);
+pub fn create_ase(mut groups: Vec<Group>, mut colors: Vec<ColorBlock>) -> Vec<u8> {
+    writer::truncate_long_names(&mut groups, &mut colors);
+    encode(&groups, &colors, &WriteOptions::default(), Vec::new())
+        .expect("names are written as they are by default, once they fit")
+        .0
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file, like [`create_ase`], without taking
+/// ownership of the groups and colors.
+///
+/// # Errors
+///
+/// This function will return an [`ASEError::InvalidBlock`] for the first group or color,
+/// whose name has more than `65534` UTF-16 code units.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, create_ase_ref, ASEError, BlockError, ColorBlock, ColorType, ColorValue};
+/// let colors = vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)];
+/// let ase = create_ase_ref(&[], &colors).unwrap();
+/// assert_eq!(ase, create_ase(vec![], colors));
+///
+/// let colors = vec![ColorBlock::new("a".repeat(70_000), ColorValue::Gray(0.5), ColorType::Normal)];
+/// assert!(matches!(
+///     create_ase_ref(&[], &colors),
+///     Err(ASEError::InvalidBlock { reason: BlockError::NameTooLong, .. })
+/// ));
+/// ```
+pub fn create_ase_ref(groups: &[Group], colors: &[ColorBlock]) -> Result<Vec<u8>, ASEError> {
+    encode(groups, colors, &WriteOptions::default(), Vec::new()).map(|(ase, _)| ase)
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file, writing groups and single colors in
+/// the given order.
+///
+/// [`create_ase`] writes all groups before the single colors.
+///
+/// # Errors
+///
+/// This function will return an [`ASEError::InvalidBlock`] for the first group or color,
+/// whose name has more than `65534` UTF-16 code units.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase_blocks, Block, ColorBlock, ColorType, ColorValue, Group};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let group = Group::new("group".to_owned(), vec![color.clone()]);
+/// let ase = create_ase_blocks(vec![Block::Color(color.clone()), group.into(), color.into()]).unwrap();
+/// // the file starts with a single color
+/// assert_eq!(ase[12..14], [0, 1]);
+/// ```
+#[allow(clippy::arithmetic_side_effects)]
+pub fn create_ase_blocks(blocks: Vec<Block>) -> Result<Vec<u8>, ASEError> {
+    let options = WriteOptions::default();
+    let size = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Group(group) => writer::group_size(group, &options),
+            Block::Color(block) => writer::color_size(block, &options),
+            // block type and length
+            Block::Raw(block) => 2 + 4 + block.data.len(),
+        })
+        .sum::<usize>()
+        + writer::HEADER_SIZE;
+    let mut writer = writer::Writer::new(size, &options);
+    let count = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Group(group) => writer.group_block_count(group.blocks.len()),
+            Block::Color(_) | Block::Raw(_) => 1,
+        })
+        .sum();
+
+    writer
+        .write_file_header(count)
+        .expect("the default version is valid");
+    for block in blocks {
+        match block {
+            Block::Group(group) => group.write(&mut writer),
+            Block::Color(block) => block.write(&mut writer),
+            Block::Raw(block) => block.write(&mut writer),
+        }?;
+    }
+    let bytes = writer.into_vec();
+    debug_assert_eq!(bytes.len(), size, "the encoded size is exact");
+    Ok(bytes)
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file from iterators, like [`create_ase`].
+///
+/// The number of blocks is written before the blocks, so `groups` and `colors` are
+/// collected into vectors first.
+///
+/// # Errors
+///
+/// This function will return an [`ASEError::InvalidBlock`] for the first group or color,
+/// whose name has more than `65534` UTF-16 code units.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, create_ase_from_iter, ColorBlock, ColorType, ColorValue};
+/// let colors = vec![
+///     ColorBlock::new("gray".to_owned(), ColorValue::Gray(0.5), ColorType::Normal),
+///     ColorBlock::new("red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal),
+/// ];
+/// let ase = create_ase_from_iter([], colors.iter().filter(|block| block.name == "red").cloned());
+/// assert_eq!(ase.unwrap(), create_ase(vec![], vec![colors[1].clone()]));
+/// ```
+pub fn create_ase_from_iter(
+    groups: impl IntoIterator<Item = Group>,
+    colors: impl IntoIterator<Item = ColorBlock>,
+) -> Result<Vec<u8>, ASEError> {
+    let groups: Vec<Group> = groups.into_iter().collect();
+    let colors: Vec<ColorBlock> = colors.into_iter().collect();
+    create_ase_ref(&groups, &colors)
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file in `out`, like [`create_ase`].
+///
+/// The previous content of `out` is cleared, its allocation is reused and only grown
+/// if it is too small for the file.
+///
+/// # Errors
+///
+/// This function will return an [`ASEError::InvalidBlock`] for the first group or color,
+/// whose name has more than `65534` UTF-16 code units, `out` is left empty then.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, create_ase_into, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let mut ase = Vec::with_capacity(1024);
+/// create_ase_into(vec![], vec![color.clone()], &mut ase).unwrap();
+/// assert_eq!(ase, create_ase(vec![], vec![color]));
+/// assert_eq!(ase.capacity(), 1024);
+/// ```
+pub fn create_ase_into(
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+    out: &mut Vec<u8>,
+) -> Result<(), ASEError> {
+    let buf = std::mem::take(out);
+    *out = encode(&groups, &colors, &WriteOptions::default(), buf)?.0;
+    Ok(())
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file, after validating all groups and colors.
+///
+/// Names must not contain null characters and fit into their length field, color
+/// components must be finite numbers within the range of their model, see
+/// [`ColorValue::is_finite`] and [`ColorValue::is_in_range`].
+///
+/// # Errors
+///
+/// This function will return an [`ASEError::InvalidBlock`] for the first group or color,
+/// which is invalid.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase_checked, ASEError, BlockError, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Rgb(2.0, 0.0, 0.0), ColorType::Normal);
+/// let res = create_ase_checked(vec![], vec![color]);
+/// assert!(matches!(
+///     res,
+///     Err(ASEError::InvalidBlock { reason: BlockError::ComponentOutOfRange, .. })
+/// ));
+/// ```
+pub fn create_ase_checked(
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+) -> Result<Vec<u8>, ASEError> {
+    let invalid = |name: &str, reason| ASEError::InvalidBlock {
+        name: name.to_owned(),
+        reason,
+    };
+    let check_name = |name: &str| {
+        if name.contains('\0') {
+            Err(invalid(name, BlockError::NullInName))
+        } else if writer::is_name_too_long(name) {
+            Err(invalid(name, BlockError::NameTooLong))
+        } else {
+            Ok(())
+        }
+    };
+
+    let check_block = |block: &ColorBlock| {
+        check_name(&block.name)?;
+        if !block.color.is_finite() {
+            Err(invalid(&block.name, BlockError::NonFiniteComponent))
+        } else if !block.color.is_in_range() {
+            Err(invalid(&block.name, BlockError::ComponentOutOfRange))
+        } else {
+            Ok(())
+        }
+    };
+
+    for group in &groups {
+        check_name(&group.name)?;
+        group.blocks.iter().try_for_each(check_block)?;
+    }
+    colors.iter().try_for_each(check_block)?;
+    Ok(create_ase(groups, colors))
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file, using the given options.
+///
+/// # Errors
+///
+/// This function will return an error if a group or color is rejected by the options.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase_with_options, ASEError, ColorBlock, ColorType, ColorValue, NameEncoding, WriteOptions};
+/// let color = ColorBlock::new("bad\0name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let options = WriteOptions {
+///     name_encoding: NameEncoding::Strict,
+///     ..Default::default()
+/// };
+/// let res = create_ase_with_options(vec![], vec![color], &options);
+/// assert!(matches!(res, Err(ASEError::InvalidBlock { .. })));
+/// ```
+pub fn create_ase_with_options(
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+    options: &WriteOptions,
+) -> Result<Vec<u8>, ASEError> {
+    create_ase_with_report(groups, colors, options).map(|(ase, _)| ase)
+}
+
+/// Creates an Adobe Swatch Exchange (ASE) file, using the given options, and reports
+/// the changes made while writing it.
+///
+/// # Errors
+///
+/// This function will return an error if a group or color is rejected by the options.
+pub fn create_ase_with_report(
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+    options: &WriteOptions,
+) -> Result<(Vec<u8>, WriteReport), ASEError> {
+    encode(&groups, &colors, options, Vec::new())
+}
+
+/// Encodes the groups and colors into `buf`, after clearing it.
+fn encode(
+    groups: &[Group],
+    colors: &[ColorBlock],
+    options: &WriteOptions,
+    mut buf: Vec<u8>,
+) -> Result<(Vec<u8>, WriteReport), ASEError> {
+    let canonical;
+    let (groups, colors) = if options.canonical {
+        canonical = writer::canonical_order(groups, colors);
+        (&canonical.0[..], &canonical.1[..])
+    } else {
+        (groups, colors)
+    };
+    let size = writer::encoded_size(groups, colors, options);
+    buf.clear();
+    buf.reserve_exact(size);
+    let mut writer = writer::Writer::from_vec(buf, options);
+    let names = groups.iter().flat_map(|group| &group.blocks).chain(colors);
+    writer.reserve_color_names(names.map(|block| block.name.as_str()));
+    write_blocks(&mut writer, groups, colors, |_| Ok(()))?;
+
+    let report = std::mem::take(&mut writer.report);
+    let bytes = writer.into_vec();
+    // placeholders are longer than the empty names they replace
+    debug_assert!(
+        bytes.len() == size || options.placeholder_names,
+        "the encoded size is exact"
+    );
+    Ok((bytes, report))
+}
+
+/// Writes an Adobe Swatch Exchange (ASE) file to `writer`.
+///
+/// Blocks are passed to the writer one at a time, instead of keeping the whole file in memory.
+///
+/// # Errors
+///
+/// This function will return an error if either writing to `writer` fails, or a name is too long.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, write_ase, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let mut file = Vec::new();
+/// write_ase(&mut file, vec![], vec![color.clone()]).unwrap();
+/// assert_eq!(file, create_ase(vec![], vec![color]));
+/// ```
+pub fn write_ase<W: std::io::Write>(
+    mut writer: W,
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+) -> Result<(), ASEError> {
+    let options = WriteOptions::default();
+    let mut blocks = writer::Writer::new(0, &options);
+    write_blocks(&mut blocks, &groups, &colors, |blocks| {
+        writer.write_all(blocks.buf.as_slice())?;
+        blocks.buf.clear();
+        Ok(())
+    })
+}
+
+/// Writes an Adobe Swatch Exchange (ASE) file to `path`, replacing it atomically.
+///
+/// The file is first written to a temporary file in the same directory, which is then
+/// renamed to `path`. If writing fails, an existing file at `path` is left untouched.
+///
+/// # Errors
+///
+/// This function will return an error if either the file cannot be written or renamed,
+/// or a name is too long.
+pub fn write_ase_to_path<P: AsRef<std::path::Path>>(
+    path: P,
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+) -> Result<(), ASEError> {
+    let path = path.as_ref();
+    let temp_path = temp_path(path)?;
+    let result = std::fs::File::create(&temp_path)
+        .map_err(ASEError::from)
+        .and_then(|file| {
+            let mut file = std::io::BufWriter::new(file);
+            write_ase(&mut file, groups, colors)?;
+            let file = file.into_inner().map_err(|err| err.into_error())?;
+            file.sync_all()?;
+            Ok(())
+        })
+        .and_then(|_| Ok(std::fs::rename(&temp_path, path)?));
+    if result.is_err() {
+        // the temporary file may not have been created
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Writes an Adobe Swatch Exchange (ASE) file to `path`, like [`write_ase_to_path`].
+///
+/// # Errors
+///
+/// This function will return an error if either the file cannot be written or renamed,
+/// or a name is too long. Errors are wrapped in an [`ASEError::File`], containing the path.
+pub fn write_ase_file<P: AsRef<std::path::Path>>(
+    path: P,
+    groups: Vec<Group>,
+    colors: Vec<ColorBlock>,
+) -> Result<(), ASEError> {
+    let path = path.as_ref();
+    write_ase_to_path(path, groups, colors).map_err(|err| file_error(path, err))
+}
+
+/// Adds the path of the file, which was read or written, to the error.
+fn file_error(path: &std::path::Path, error: ASEError) -> ASEError {
+    ASEError::File {
+        path: path.to_path_buf(),
+        error: Box::new(error),
+    }
+}
+
+/// Returns a path next to `path`, which is unique to this process and call.
+fn temp_path(path: &std::path::Path) -> Result<std::path::PathBuf, ASEError> {
+    static COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    ));
+    Ok(path.with_file_name(temp_name))
+}
+
+/// Writes the header, groups and single colors using `writer`, calling `flush`
+/// after the header and each block.
+fn write_blocks(
+    writer: &mut writer::Writer,
+    groups: &[Group],
+    colors: &[ColorBlock],
+    mut flush: impl FnMut(&mut writer::Writer) -> Result<(), ASEError>,
+) -> Result<(), ASEError> {
+    write_header(writer, groups, colors)?;
+    flush(writer)?;
 
     // write groups
-    groups.into_iter().for_each(|group| group.write(&mut buf));
+    for group in groups {
+        group.write(writer)?;
+        flush(writer)?;
+    }
 
     // write single colors
-    colors.into_iter().for_each(|block| block.write(&mut buf));
+    for block in colors {
+        block.write(writer)?;
+        flush(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes the signature, version and number of blocks using `writer`.
+///
+/// # Errors
+///
+/// This function will return an error if the version is rejected by the options.
+#[allow(clippy::arithmetic_side_effects)]
+fn write_header(
+    writer: &mut writer::Writer,
+    groups: &[Group],
+    colors: &[ColorBlock],
+) -> Result<(), ASEError> {
+    let group_blocks: u32 = groups
+        .iter()
+        .map(|group| writer.group_block_count(group.blocks.len()))
+        .sum();
+    writer.write_file_header(group_blocks + colors.len() as u32)
+}
+
+/// Appends single colors to an existing .ase file, without encoding it again.
+///
+/// The existing blocks are copied as they are, only the number of blocks in the header is
+/// updated. Data following the blocks, which are read by [`read_ase`], is not kept.
+///
+/// # Errors
+///
+/// This function will return an error if either the existing file is invalid, or a
+/// name is too long.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{append_to_ase, create_ase, read_ase, ColorBlock, ColorType, ColorValue};
+/// let red = ColorBlock::new("red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal);
+/// let blue = ColorBlock::new("blue".to_owned(), ColorValue::Rgb(0.0, 0.0, 1.0), ColorType::Normal);
+/// let existing = create_ase(vec![], vec![red.clone()]);
+/// let appended = append_to_ase(&existing, &[blue.clone()]).unwrap();
+/// assert_eq!(read_ase(&*appended).unwrap(), (vec![], vec![red, blue]));
+/// ```
+pub fn append_to_ase(existing: &[u8], new_colors: &[ColorBlock]) -> Result<Vec<u8>, ASEError> {
+    // validates the file and finds the end of the blocks, which are read with the declared count
+    let blocks = read_blocks(existing, &ParseOptions::DEFAULT, None, false)?;
+    let end = usize::try_from(blocks.info.bytes_read).map_err(|_| ASEError::InputDataParseError)?;
+    let content = existing.get(..end).ok_or(ASEError::InputDataParseError)?;
 
-    buf.into_vec()
+    let options = WriteOptions::default();
+    let mut writer = writer::Writer::new(0, &options);
+    let mut block_count = blocks.info.declared_blocks;
+    // otherwise the colors would be read as part of the group
+    if blocks.unterminated_group {
+        writer.write_group_end();
+        block_count = block_count.saturating_add(1);
+    }
+    for block in new_colors {
+        block.write(&mut writer)?;
+    }
+    let block_count = u32::try_from(new_colors.len())
+        .ok()
+        .and_then(|colors| block_count.checked_add(colors))
+        .ok_or(ASEError::InputDataParseError)?;
+
+    let appended = writer.into_vec();
+    let mut ase = Vec::with_capacity(content.len().saturating_add(appended.len()));
+    ase.extend_from_slice(content);
+    ase.extend_from_slice(&appended);
+    // the block count follows the signature and version
+    ase.get_mut(8..12)
+        .ok_or(ASEError::InputDataParseError)?
+        .copy_from_slice(&block_count.to_be_bytes());
+    Ok(ase)
 }
 
 /// Read groups and single colors from the .ase file.
 ///
+/// The order of single colors relative to groups is not kept, use [`read_ase_blocks`]
+/// to read the blocks in the order they are stored.
+///
+/// Some deviations from the format are tolerated, see [`ParseOptions::DEFAULT`]. This
+/// includes names missing their null terminator and names, whose length does not count
+/// the terminator, which were rejected by earlier versions. Use [`read_ase_with_options`]
+/// with [`ParseOptions::STRICT`] to reject them.
+///
 /// # Errors
 ///
 /// This function will return an error if either a read to the given data fails,
-/// or the ASE file is invalid.
+/// or the ASE file is invalid. The error is returned without the location, at which it
+/// occurred, use [`read_ase_with_options`] to get it wrapped in an [`ASEError::Block`].
 ///
 /// # Examples
 /// ```rust
@@ -58,212 +575,1455 @@ pub fn create_ase(groups: Vec<Group>, colors: Vec<ColorBlock>) -> Vec<u8> {
 /// let (groups, colors) = read_ase(&*source).unwrap();
 /// # assert_eq!((groups, colors), (vec![], vec![]));
 /// ```
-pub fn read_ase<T: std::io::Read>(mut ase: T) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
-    let mut buf_u32 = [0; 4];
+pub fn read_ase<T: std::io::Read>(ase: T) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    read_blocks(ase, &ParseOptions::DEFAULT, None, false)
+        .map(|blocks| (blocks.groups, blocks.colors))
+        .map_err(ASEError::into_root_cause)
+}
+
+/// Read the header of the .ase file, without reading its blocks.
+///
+/// Only the first twelve bytes are read. The header is validated like by [`read_ase`].
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the file signature or version are invalid.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::read_ase_header;
+/// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 212];
+/// let header = read_ase_header(&*source).unwrap();
+/// assert_eq!((header.version_major, header.version_minor), (1, 0));
+/// assert_eq!(header.block_count, 212);
+/// ```
+pub fn read_ase_header<T: std::io::Read>(mut ase: T) -> Result<AseHeader, ASEError> {
+    reader::read_header(&mut ase, false)
+}
+
+/// Read groups and single colors from the .ase file at `path`.
+///
+/// # Errors
+///
+/// This function will return an error if either the file cannot be read, or the ASE
+/// file is invalid. Errors are wrapped in an [`ASEError::File`], containing the path.
+///
+/// # Examples
+/// ```rust,no_run
+/// # use adobe_swatch_exchange::read_ase_file;
+/// let (groups, colors) = read_ase_file("palette.ase").unwrap();
+/// ```
+pub fn read_ase_file<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    let path = path.as_ref();
+    std::fs::File::open(path)
+        .map_err(ASEError::from)
+        .and_then(|file| {
+            read_ase_with_options(std::io::BufReader::new(file), &ParseOptions::DEFAULT)
+        })
+        .map_err(|err| file_error(path, err))
+}
+
+/// Read groups and single colors from the .ase file, tolerating the ways, in which files
+/// do not follow the format, selected by the options.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid under the given options. Errors after the header are
+/// wrapped in an [`ASEError::Block`], locating the block, which could not be read.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{read_ase_with_options, ParseOptions};
+/// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0];
+/// let (groups, colors) = read_ase_with_options(&*source, &ParseOptions::STRICT).unwrap();
+/// # assert_eq!((groups, colors), (vec![], vec![]));
+/// ```
+pub fn read_ase_with_options<T: std::io::Read>(
+    ase: T,
+    options: &ParseOptions,
+) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    read_blocks(ase, options, None, false).map(|blocks| (blocks.groups, blocks.colors))
+}
+
+/// Read groups, single colors and unknown blocks from the .ase file, in the order they
+/// are stored in it.
+///
+/// Unknown blocks are only returned, if [`ParseOptions::keep_unknown_blocks`] is set. Together
+/// with [`create_ase_blocks`], these files are written back unchanged.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid under the given options.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase_blocks, read_ase_blocks, Block, ColorBlock, ColorType, ColorValue, ParseOptions, RawBlock};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let raw = RawBlock { type_id: 0x0042, data: vec![1, 2, 3] };
+/// let source = create_ase_blocks(vec![raw.clone().into(), color.clone().into()]).unwrap();
+///
+/// let options = ParseOptions { keep_unknown_blocks: true, ..Default::default() };
+/// let blocks = read_ase_blocks(&*source, &options).unwrap();
+/// assert_eq!(blocks, vec![Block::Raw(raw), Block::Color(color)]);
+/// assert_eq!(create_ase_blocks(blocks).unwrap(), source);
+/// ```
+pub fn read_ase_blocks<T: std::io::Read>(
+    ase: T,
+    options: &ParseOptions,
+) -> Result<Vec<Block>, ASEError> {
+    let blocks = read_blocks(ase, options, None, true)?;
+    let mut groups = blocks.groups.into_iter();
+    let mut colors = blocks.colors.into_iter();
+    let mut raw = blocks.raw.into_iter();
+    Ok(blocks
+        .order
+        .into_iter()
+        .filter_map(|kind| match kind {
+            BlockKind::Group => groups.next().map(Block::Group),
+            BlockKind::Color => colors.next().map(Block::Color),
+            BlockKind::Raw => raw.next().map(Block::Raw),
+        })
+        .collect())
+}
+
+/// Read groups and single colors from the .ase file, together with the deviations from
+/// the format, which were tolerated while reading it.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid under the given options.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase_with_options, read_ase_with_warnings, ColorBlock, ColorType, ColorValue, Group, GroupLayout, ParseOptions, ParseWarning, WriteOptions};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let group = Group::new("group".to_owned(), vec![color]);
+/// let options = WriteOptions {
+///     group_layout: GroupLayout::Flat,
+///     ..Default::default()
+/// };
+/// let source = create_ase_with_options(vec![group], vec![], &options).unwrap();
+/// let (groups, _, warnings) = read_ase_with_warnings(&*source, &ParseOptions::default()).unwrap();
+/// assert_eq!(groups.len(), 1);
+/// assert_eq!(warnings, vec![ParseWarning::SeparateGroupColors { offset: 12 }]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_ase_with_warnings<T: std::io::Read>(
+    ase: T,
+    options: &ParseOptions,
+) -> Result<(Vec<Group>, Vec<ColorBlock>, Vec<ParseWarning>), ASEError> {
+    read_blocks(ase, options, None, false)
+        .map(|blocks| (blocks.groups, blocks.colors, blocks.warnings))
+}
+
+/// Read groups and single colors from a seekable .ase source, like a file.
+///
+/// Before parsing, the declared lengths of all blocks are compared to the length of
+/// the source, so truncated files are rejected without parsing them first.
+///
+/// # Errors
+///
+/// This function will return an error if either seeking or a read to the given data fails,
+/// or the ASE file is invalid. If a block extends past the end of the source,
+/// [`ConformationError::Truncated`] reports the number of missing bytes.
+///
+/// # Examples
+/// ```rust
+/// # use std::io::Cursor;
+/// # use adobe_swatch_exchange::{create_ase, read_ase_seek, ASEError, ColorBlock, ColorType, ColorValue, ConformationError};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let mut source = create_ase(vec![], vec![color]);
+/// source.truncate(source.len() - 3);
+/// assert!(matches!(
+///     read_ase_seek(Cursor::new(source)),
+///     Err(ASEError::Invalid(ConformationError::Truncated { missing: 3 }))
+/// ));
+/// ```
+pub fn read_ase_seek<T: std::io::Read + std::io::Seek>(
+    mut ase: T,
+) -> Result<(Vec<Group>, Vec<ColorBlock>), ASEError> {
+    reader::check_truncation(&mut ase)?;
+    read_ase(ase)
+}
+
+/// Read groups and single colors from the .ase file, together with the location of
+/// each of them in the file.
+///
+/// The provenance is returned in document order, colors stored inside of their group
+/// block are listed after that group.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, read_ase_traced, ColorBlock, ColorType, ColorValue, ProvenanceTarget};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color]);
+/// let (_, colors, provenance) = read_ase_traced(&*source).unwrap();
+/// assert_eq!(colors.len(), 1);
+/// assert_eq!(provenance[0].offset, 12);
+/// assert_eq!(provenance[0].target, ProvenanceTarget::Color(0));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_ase_traced<T: std::io::Read>(
+    ase: T,
+) -> Result<(Vec<Group>, Vec<ColorBlock>, Vec<BlockProvenance>), ASEError> {
+    let mut provenance = Vec::new();
+    let blocks = read_blocks(ase, &ParseOptions::DEFAULT, Some(&mut provenance), false)?;
+    Ok((blocks.groups, blocks.colors, provenance))
+}
+
+/// Read groups and single colors from the .ase file, together with information about
+/// the file itself.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE file is invalid.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::read_ase_info;
+/// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0];
+/// let (_, _, info) = read_ase_info(&*source).unwrap();
+/// assert_eq!((info.version_major, info.version_minor), (1, 0));
+/// assert_eq!(info.bytes_read, 12);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn read_ase_info<T: std::io::Read>(
+    ase: T,
+) -> Result<(Vec<Group>, Vec<ColorBlock>, FileInfo), ASEError> {
+    read_blocks(ase, &ParseOptions::DEFAULT, None, false)
+        .map(|blocks| (blocks.groups, blocks.colors, blocks.info))
+}
+
+/// The content of an .ase file, see [`read_blocks`].
+struct Blocks<D: Document = Owned> {
+    groups: Vec<D::Group>,
+    colors: Vec<D::Color>,
+    info: FileInfo,
+    /// Whether the file ended without the `GroupEnd` block of the last group.
+    unterminated_group: bool,
+    /// The deviations from the format, which were tolerated.
+    warnings: Vec<ParseWarning>,
+    /// Unknown blocks, which were kept.
+    raw: Vec<RawBlock>,
+    /// The kinds of the blocks in the order they were read, if requested.
+    order: Vec<BlockKind>,
+}
+
+/// The kind of a block read by [`read_blocks`].
+#[derive(Debug, Clone, Copy)]
+enum BlockKind {
+    Group,
+    Color,
+    Raw,
+}
+
+/// How groups and colors are built from the blocks read by [`parse_blocks`].
+trait Document {
+    type Group: Default;
+    type Color;
+
+    /// Parses the data of a group start block, like [`Group::parse_with_entries`],
+    /// returning the group and the offset in the data, at which its colors end, see
+    /// [`ColorEntries::end`].
+    fn parse_group(
+        &mut self,
+        data: &[u8],
+        malformed: MalformedEntry,
+        inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<(Self::Group, usize), ASEError>;
+    /// Parses the data of a color entry block, like [`ColorBlock::parse_entry`].
+    fn parse_color(&mut self, data: &[u8]) -> Result<(Self::Color, EntryLayout), ASEError>;
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color>;
+}
+
+/// Builds [`Group`]s and [`ColorBlock`]s.
+struct Owned {
+    /// The byte order, in which names are decoded.
+    name_byte_order: NameByteOrder,
+    /// Whether codes of color models are matched loosely.
+    recover_color_model: bool,
+    /// Whether color entries, whose declared length is too small, are read by their structure.
+    derive_color_entry_length: bool,
+}
+
+impl Owned {
+    /// Builds groups and colors as set by the options.
+    fn new(options: &ParseOptions) -> Self {
+        Self {
+            name_byte_order: options.name_byte_order,
+            recover_color_model: options.recover_color_model,
+            derive_color_entry_length: options.derive_color_entry_length,
+        }
+    }
+}
+
+impl Document for Owned {
+    type Group = Group;
+    type Color = ColorBlock;
+
+    fn parse_group(
+        &mut self,
+        data: &[u8],
+        malformed: MalformedEntry,
+        inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<(Self::Group, usize), ASEError> {
+        let group_name = GroupName::parse(data)?;
+        let mut name = String::new();
+        self.name_byte_order
+            .decode_into(group_name.units, &mut name)?;
+        let mut entries =
+            ColorEntries::new(data, group_name.end).derive_lengths(self.derive_color_entry_length);
+        let blocks = match entries.parse_colors(malformed, |data| self.parse_color(data), inspect) {
+            Ok(blocks) => blocks,
+            Err(err) => return Err(err.in_swatch(name)),
+        };
+        Ok((Group::new(name, blocks), entries.end()))
+    }
+
+    fn parse_color(&mut self, data: &[u8]) -> Result<(Self::Color, EntryLayout), ASEError> {
+        let parts = ColorBlock::parse_parts(data, self.recover_color_model)?;
+        let mut name = String::new();
+        let mut layout = parts.layout;
+        layout.little_endian_name = self.name_byte_order.decode_into(parts.name, &mut name)?;
+        Ok((ColorBlock::new(name, parts.color, parts.color_type), layout))
+    }
+
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color> {
+        &mut group.blocks
+    }
+}
+
+fn read_blocks<T: std::io::Read>(
+    ase: T,
+    options: &ParseOptions,
+    provenance: Option<&mut Vec<BlockProvenance>>,
+    keep_order: bool,
+) -> Result<Blocks, ASEError> {
+    let mut blocks = reader::BlockReader::with_options(ase, options)?;
+    read_document(&mut blocks, options, provenance, keep_order)
+}
+
+/// Reads groups and colors from the blocks, applying the options to the parsed colors
+/// and names.
+fn read_document<T: std::io::Read>(
+    blocks: &mut reader::BlockReader<T>,
+    options: &ParseOptions,
+    provenance: Option<&mut Vec<BlockProvenance>>,
+    keep_order: bool,
+) -> Result<Blocks, ASEError> {
+    let mut document = Owned::new(options);
+    let mut read = parse_blocks(blocks, &mut document, options, provenance, keep_order)
+        .map_err(|err| blocks.locate(err))?;
+    if options.gray_polarity != GrayPolarity::ZeroIsBlack {
+        let grouped_colors = read.groups.iter_mut().flat_map(|group| &mut group.blocks);
+        for block in grouped_colors.chain(&mut read.colors) {
+            block.color = block.color.with_gray_polarity(options.gray_polarity);
+        }
+    }
+    let grouped_colors = read.groups.iter_mut().flat_map(|group| &mut group.blocks);
+    let colors = grouped_colors.chain(&mut read.colors);
+    let normalized = options
+        .rgb_range
+        .normalize(colors.map(|block| &mut block.color));
+    if normalized > 0 {
+        warn(
+            &mut read.warnings,
+            ParseWarning::RgbRange { colors: normalized },
+        );
+    }
+    if options.sanitize_names {
+        types::sanitize_names(&mut read.groups, &mut read.colors);
+    }
+    if let Some(names) = options.merge_duplicate_groups {
+        let positions = types::merge_groups(&mut read.groups, names);
+        let merged = positions.len().saturating_sub(read.groups.len());
+        if merged > 0 {
+            merge_order(&positions, &mut read.order);
+            warn(
+                &mut read.warnings,
+                ParseWarning::MergedGroups { groups: merged },
+            );
+        }
+    }
+    Ok(read)
+}
+
+/// Removes the groups merged into another one from the block order, using the positions
+/// returned by [`types::merge_groups`].
+fn merge_order(positions: &[(usize, usize)], order: &mut Vec<BlockKind>) {
+    // groups are merged into the first one, so a group is kept if it is the next one
+    let mut groups = positions.iter();
+    let mut kept = 0;
+    order.retain(|kind| {
+        if !matches!(kind, BlockKind::Group) {
+            return true;
+        }
+        let keep = groups.next().map_or(true, |&(index, _)| index == kept);
+        if keep {
+            kept = kept.saturating_add(1);
+        }
+        keep
+    });
+}
+
+/// Adds a tolerated deviation from the format to the warnings.
+fn warn(warnings: &mut Vec<ParseWarning>, warning: ParseWarning) {
+    trace_event!(%warning, "tolerated deviation from the format");
+    warnings.push(warning);
+}
+
+/// Returns whether the error is caused by the data of a block, so that reading can
+/// continue after it, see [`ParseOptions::resync_corrupt_blocks`].
+fn is_corrupt(error: &ASEError) -> bool {
+    match error.root_cause() {
+        ASEError::Io(err) => err.kind() == std::io::ErrorKind::UnexpectedEof,
+        ASEError::LimitExceeded { .. }
+        | ASEError::Invalid(error::ConformationError::TrailingData { .. }) => false,
+        _ => true,
+    }
+}
+
+/// Reports data after the last block as a warning, unless it is rejected.
+fn tolerate_trailing_data(
+    error: ASEError,
+    options: &ParseOptions,
+    offset: u64,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), ASEError> {
+    match error {
+        ASEError::Invalid(error::ConformationError::TrailingData { length })
+            if !options.reject_trailing_data =>
+        {
+            warn(warnings, ParseWarning::TrailingData { offset, length });
+            Ok(())
+        }
+        err => Err(err),
+    }
+}
 
-    // read magic bytes
-    ase.read_exact(&mut buf_u32)?;
-    if &buf_u32 != types::FILE_SIGNATURE {
-        return Err(ASEError::Invalid(error::ConformationError::FileSignature));
+/// Reports deviations in the layout of a color entry as warnings, unless they are rejected.
+///
+/// `length` is the length of the data declared by the block.
+fn check_color_entry(
+    options: &ParseOptions,
+    offset: u64,
+    length: u64,
+    layout: &EntryLayout,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), ASEError> {
+    Limits::check(
+        Limit::NameLength,
+        layout.name_length as u64,
+        options.limits.max_name_length as u64,
+    )?;
+    check_terminator(options, offset, layout.terminator, warnings)?;
+    if options.derive_color_entry_length && length < types::MIN_ENTRY_LENGTH.into() {
+        warn(
+            warnings,
+            ParseWarning::ColorEntrySize {
+                offset,
+                declared: length as u32,
+            },
+        );
+    }
+    if layout.little_endian_name {
+        warn(warnings, ParseWarning::NameByteOrder { offset });
     }
+    if layout.recovered_model {
+        warn(warnings, ParseWarning::ColorModelCode { offset });
+    }
+    let length = length.saturating_sub(layout.length as u64);
+    if length == 0 {
+        return Ok(());
+    }
+    if options.reject_color_entry_data {
+        return Err(ASEError::Invalid(
+            error::ConformationError::ColorEntryLength { length },
+        ));
+    }
+    warn(warnings, ParseWarning::ColorEntryLength { offset, length });
+    Ok(())
+}
+
+/// Reports a name, which was read despite its null terminator, as a warning, unless it
+/// is rejected.
+fn check_terminator(
+    options: &ParseOptions,
+    offset: u64,
+    terminator: Terminator,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), ASEError> {
+    match terminator {
+        Terminator::Counted => {}
+        Terminator::Missing if options.recover_name_terminator => {
+            warn(warnings, ParseWarning::NameTerminator { offset });
+        }
+        Terminator::Uncounted if options.recover_name_length => {
+            warn(warnings, ParseWarning::NameLength { offset });
+        }
+        Terminator::Missing => {
+            return Err(ASEError::Invalid(error::ConformationError::NameTerminator))
+        }
+        Terminator::Uncounted => {
+            return Err(ASEError::Invalid(error::ConformationError::NameLength))
+        }
+    }
+    Ok(())
+}
 
-    // read version, should be 1.0
-    ase.read_exact(&mut buf_u32)?;
-    if buf_u32 != types::VERSION.to_be_bytes() {
-        return Err(ASEError::Invalid(error::ConformationError::FileVersion));
+/// Stores the held group, returning whether it has been added to the groups.
+///
+/// A group, which has been stored before its nested groups, is put back in its place.
+fn store_group<G: Default>(
+    groups: &mut Vec<G>,
+    group: &mut G,
+    reopened: &mut Option<usize>,
+) -> bool {
+    let group = std::mem::take(group);
+    match reopened.take().and_then(|index| groups.get_mut(index)) {
+        Some(stored) => {
+            *stored = group;
+            false
+        }
+        None => {
+            groups.push(group);
+            true
+        }
     }
+}
 
-    ase.read_exact(&mut buf_u32)?;
-    let number_of_blocks = u32::from_be_bytes(buf_u32);
+/// Holds the group containing the closed group again, if it was nested.
+fn resume_outer_group<G: Default>(
+    groups: &mut [G],
+    group: &mut G,
+    group_offset: &mut u64,
+    outer_groups: &mut Vec<(usize, u64)>,
+    reopened: &mut Option<usize>,
+) -> GroupHold {
+    let Some((index, offset)) = outer_groups.pop() else {
+        return GroupHold::Empty;
+    };
+    let Some(outer) = groups.get_mut(index) else {
+        return GroupHold::Empty;
+    };
+    *group = std::mem::take(outer);
+    *group_offset = offset;
+    *reopened = Some(index);
+    GroupHold::HoldingBuilding
+}
 
+fn parse_blocks<D: Document, T: std::io::Read>(
+    blocks: &mut reader::BlockReader<T>,
+    document: &mut D,
+    options: &ParseOptions,
+    mut provenance: Option<&mut Vec<BlockProvenance>>,
+    keep_order: bool,
+) -> Result<Blocks<D>, ASEError> {
     let mut groups = Vec::new();
     let mut color_blocks = Vec::new();
-    let mut buf_u16 = [0; 2];
 
     // temporary group to handle nonconformant group blocks
     let mut group_hold = GroupHold::Empty;
-    let mut group_hold_value = Group::default();
+    let mut group_hold_value = D::Group::default();
+    let mut group_offset = 0;
+    // the index and offset of the groups containing the held group, see `flatten_nested_groups`
+    let mut outer_groups: Vec<(usize, u64)> = Vec::new();
+    // the index of the held group, if it has been stored before its nested groups
+    let mut reopened = None;
+    let mut warnings = Vec::new();
+    let mut raw = Vec::new();
+    let mut order = Vec::new();
+    // scratch buffer for the block data, which is reused for every block
+    let mut data = Vec::new();
+    let mut record = |kind: BlockKind| {
+        if keep_order {
+            order.push(kind);
+        }
+    };
 
-    let mut blocks_to_read = number_of_blocks;
+    if !blocks.is_supported_version() {
+        warn(
+            &mut warnings,
+            ParseWarning::FileVersion {
+                major: (blocks.version >> 16) as u16,
+                minor: blocks.version as u16,
+            },
+        );
+    }
 
-    // allow skipping of empty blocks when a group-end block has a size field
-    let mut skipped = 0;
-    let mut safe_to_skip = false;
+    let tracing = provenance.is_some();
+    let mut trace = |offset: u64, length: u32, target: ProvenanceTarget| {
+        if let Some(provenance) = provenance.as_deref_mut() {
+            provenance.push(BlockProvenance {
+                index: provenance.len(),
+                offset,
+                length,
+                target,
+            });
+        }
+    };
 
-    while blocks_to_read > 0 {
-        ase.read_exact(&mut buf_u16)?;
+    // continues after a block, which cannot be read, at the next block boundary, see
+    // `ParseOptions::resync_corrupt_blocks`, `consumed` is the data read last
+    macro_rules! resync_on_error {
+        ($result:expr, $offset:expr, $consumed:expr) => {
+            match $result {
+                Ok(value) => value,
+                Err(err) if options.resync_corrupt_blocks && is_corrupt(&err) => {
+                    let offset = $offset;
+                    let length = blocks.resync($consumed)?.saturating_sub(offset);
+                    if length > 0 {
+                        warn(&mut warnings, ParseWarning::CorruptBlock { offset, length });
+                    }
+                    continue;
+                }
+                Err(err) => return Err(err),
+            }
+        };
+    }
 
-        // only skip if the next two bytes were zero and we haven't skipped two already.
-        if buf_u16 == [0, 0] && skipped < 2 && safe_to_skip {
-            skipped += 1;
+    loop {
+        let frame = match blocks.next_frame() {
+            Ok(frame) => frame,
+            // the file ends in place of the group end of the held group, which is only
+            // closed below when recovering the nesting of groups
+            Err(err) if group_hold != GroupHold::Empty && blocks.is_missing_last_block(&err) => {
+                if !options.recover_group_nesting {
+                    return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+                }
+                None
+            }
+            Err(err) => {
+                let offset = blocks.block_offset();
+                resync_on_error!(
+                    tolerate_trailing_data(err, options, offset, &mut warnings),
+                    offset,
+                    &[]
+                );
+                None
+            }
+        };
+        let Some(mut frame) = frame else {
+            break;
+        };
+        // the length is recorded once the data of the block is read
+        trace_span!(
+            span,
+            "block",
+            block_type = ?frame.block_type,
+            offset = frame.offset,
+            length = tracing::field::Empty
+        );
+        // reuse the allocation of the previous block
+        frame.data = std::mem::take(&mut data);
+        let block_type = frame.block_type;
+        if frame.padding > 0 {
+            let offset = frame.offset.saturating_sub(frame.padding);
+            let length = frame.padding;
+            warn(
+                &mut warnings,
+                if frame.block_padding {
+                    ParseWarning::BlockPadding { offset, length }
+                } else {
+                    ParseWarning::GroupEndLength { offset, length }
+                },
+            );
+        }
+        // unknown blocks are only returned, if they are skipped
+        if let BlockType::Raw(block_type) = block_type {
+            if let Err(err) = blocks.read_data(&mut frame) {
+                resync_on_error!(
+                    tolerate_trailing_data(err, options, frame.offset, &mut warnings),
+                    frame.offset,
+                    &frame.data
+                );
+                break;
+            }
+            trace_record!(span, "length", frame.length);
+            if keep_order && options.keep_unknown_blocks {
+                record(BlockKind::Raw);
+                raw.push(RawBlock {
+                    type_id: block_type,
+                    data: frame.data,
+                });
+            } else {
+                warn(
+                    &mut warnings,
+                    ParseWarning::UnknownBlock {
+                        offset: frame.offset,
+                        block_type,
+                    },
+                );
+                data = frame.data;
+            }
             continue;
         }
 
-        let block_type = BlockType::try_from(u16::from_be_bytes(buf_u16))?;
-
         if block_type != BlockType::GroupEnd && group_hold == GroupHold::HoldingBuilt {
-            return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+            if !options.recover_group_nesting {
+                return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+            }
+            // the missing group end was expected in addition to the declared blocks
+            blocks.forget_additional_block();
+            warn(
+                &mut warnings,
+                ParseWarning::UnterminatedGroup {
+                    offset: group_offset,
+                },
+            );
+            if store_group(&mut groups, &mut group_hold_value, &mut reopened) {
+                record(BlockKind::Group);
+            }
+            group_hold = resume_outer_group(
+                &mut groups,
+                &mut group_hold_value,
+                &mut group_offset,
+                &mut outer_groups,
+                &mut reopened,
+            );
         }
 
-        // block length for GroupEnd blocks should always be zero, the `skipped`
-        // variable above is intended to help us avoid the issue where the size
-        // is specified.
-        let block_length = if block_type == BlockType::GroupEnd {
-            safe_to_skip = true;
-            skipped = 0;
-            0
-        } else {
-            ase.read_exact(&mut buf_u32)?;
-            let block_length = u32::from_be_bytes(buf_u32);
-            safe_to_skip = false;
-            block_length
-        };
-
-        let mut block = vec![0; block_length as usize];
-        ase.read_exact(&mut block)?;
+        if let Err(err) = blocks.read_data(&mut frame) {
+            resync_on_error!(
+                tolerate_trailing_data(err, options, frame.offset, &mut warnings),
+                frame.offset,
+                &frame.data
+            );
+            break;
+        }
+        trace_record!(span, "length", frame.length);
 
         // parse block data and add it appropriate vec
         match block_type {
             BlockType::GroupStart => {
-                let block = Group::parse(&block)?;
-                if group_hold != GroupHold::Empty {
-                    return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+                let malformed = if options.skip_malformed_colors {
+                    MalformedEntry::Skip
+                } else if options.reject_malformed_colors {
+                    MalformedEntry::Fail
+                } else {
+                    MalformedEntry::Stop
+                };
+                let mut entries = Vec::new();
+                let (block, end) = resync_on_error!(
+                    document.parse_group(&frame.data, malformed, |entry, layout| {
+                        entries.push((entry.offset, entry.length, layout.copied()));
+                    }),
+                    frame.offset,
+                    &frame.data
+                );
+                let name =
+                    resync_on_error!(GroupName::parse(&frame.data), frame.offset, &frame.data);
+                Limits::check(
+                    Limit::NameLength,
+                    (name.units.len() / 2) as u64,
+                    options.limits.max_name_length as u64,
+                )?;
+                Limits::check(
+                    Limit::GroupColors,
+                    entries.len() as u64,
+                    options.limits.max_group_colors as u64,
+                )?;
+                resync_on_error!(
+                    check_terminator(options, frame.offset, name.terminator, &mut warnings),
+                    frame.offset,
+                    &frame.data
+                );
+                if options.name_byte_order.is_little_endian(name.units) {
+                    warn(
+                        &mut warnings,
+                        ParseWarning::NameByteOrder {
+                            offset: frame.offset,
+                        },
+                    );
+                }
+                for (index, (offset, length, layout)) in entries.iter().enumerate() {
+                    // the color entries follow the identifier and length of the group block
+                    let offset = frame
+                        .offset
+                        .saturating_add(6)
+                        .saturating_add(*offset as u64);
+                    match layout {
+                        Some(layout) => {
+                            let checked = check_color_entry(
+                                options,
+                                offset,
+                                (*length).into(),
+                                layout,
+                                &mut warnings,
+                            )
+                            .map_err(|err| {
+                                err.in_swatch(wire::decode_utf16_units_lossy(name.units))
+                            });
+                            resync_on_error!(checked, frame.offset, &frame.data);
+                        }
+                        None => warn(
+                            &mut warnings,
+                            ParseWarning::MalformedColor { offset, index },
+                        ),
+                    }
                 }
+                let skipped = frame.data.len().saturating_sub(end);
+                if skipped > 0 {
+                    if options.reject_group_data {
+                        let mut group = String::new();
+                        options
+                            .name_byte_order
+                            .decode_into(name.units, &mut group)?;
+                        return Err(ASEError::Invalid(error::ConformationError::GroupData {
+                            group,
+                            position: end as u64,
+                        }));
+                    }
+                    warn(
+                        &mut warnings,
+                        ParseWarning::GroupData {
+                            // the data follows the identifier and length of the group block
+                            offset: frame.offset.saturating_add(6).saturating_add(end as u64),
+                            length: skipped as u64,
+                        },
+                    );
+                }
+                if group_hold == GroupHold::HoldingBuilding && options.flatten_nested_groups {
+                    warn(
+                        &mut warnings,
+                        ParseWarning::NestedGroup {
+                            offset: frame.offset,
+                        },
+                    );
+                    let index = reopened.unwrap_or(groups.len());
+                    if store_group(&mut groups, &mut group_hold_value, &mut reopened) {
+                        record(BlockKind::Group);
+                    }
+                    outer_groups.push((index, group_offset));
+                } else if group_hold != GroupHold::Empty {
+                    if !options.recover_group_nesting {
+                        return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+                    }
+                    warn(
+                        &mut warnings,
+                        ParseWarning::UnterminatedGroup {
+                            offset: group_offset,
+                        },
+                    );
+                    if store_group(&mut groups, &mut group_hold_value, &mut reopened) {
+                        record(BlockKind::Group);
+                    }
+                }
+                group_offset = frame.offset;
                 // if the parsed block has any sub-blocks then it has already been built
                 // and only a group-end block may follow it. Otherwise we are free to
                 // add colors as they appear until a group-end block is encountered.
-                group_hold = if block.blocks.is_empty() {
+                group_hold = if entries.is_empty() {
                     GroupHold::HoldingBuilding
                 } else {
-                    blocks_to_read += 1;
+                    blocks.expect_additional_block();
                     GroupHold::HoldingBuilt
                 };
+
+                trace(
+                    frame.offset,
+                    frame.length,
+                    ProvenanceTarget::Group(groups.len()),
+                );
+                if tracing {
+                    // the colors follow the identifier and length of the group block
+                    let colors = entries.iter().filter(|(_, _, layout)| layout.is_some());
+                    for (index, (offset, length, _)) in colors.enumerate() {
+                        trace(
+                            frame
+                                .offset
+                                .saturating_add(6)
+                                .saturating_add(*offset as u64),
+                            *length,
+                            ProvenanceTarget::GroupColor(groups.len(), index),
+                        );
+                    }
+                }
                 group_hold_value = block;
             }
             // read by the group end
             BlockType::GroupEnd => match group_hold {
                 GroupHold::HoldingBuilding | GroupHold::HoldingBuilt => {
-                    groups.push(group_hold_value.clone());
-                    group_hold = GroupHold::Empty;
+                    if store_group(&mut groups, &mut group_hold_value, &mut reopened) {
+                        record(BlockKind::Group);
+                    }
+                    group_hold = resume_outer_group(
+                        &mut groups,
+                        &mut group_hold_value,
+                        &mut group_offset,
+                        &mut outer_groups,
+                        &mut reopened,
+                    );
+                }
+                GroupHold::Empty if options.recover_group_nesting => {
+                    warn(
+                        &mut warnings,
+                        ParseWarning::UnmatchedGroupEnd {
+                            offset: frame.offset,
+                        },
+                    );
                 }
                 GroupHold::Empty => {
                     return Err(ASEError::Invalid(error::ConformationError::GroupEnd))
                 }
             },
             BlockType::ColorEntry => {
-                let block = ColorBlock::parse(&block)?;
+                let (block, layout) =
+                    resync_on_error!(document.parse_color(&frame.data), frame.offset, &frame.data);
+                resync_on_error!(
+                    check_color_entry(
+                        options,
+                        frame.offset,
+                        frame.length.into(),
+                        &layout,
+                        &mut warnings,
+                    ),
+                    frame.offset,
+                    &frame.data
+                );
                 match group_hold {
-                    GroupHold::HoldingBuilding => group_hold_value.blocks.push(block),
-                    GroupHold::Empty => color_blocks.push(block),
+                    GroupHold::HoldingBuilding => {
+                        let colors = D::colors(&mut group_hold_value);
+                        Limits::check(
+                            Limit::GroupColors,
+                            (colors.len() as u64).saturating_add(1),
+                            options.limits.max_group_colors as u64,
+                        )?;
+                        if colors.is_empty() {
+                            warn(
+                                &mut warnings,
+                                ParseWarning::SeparateGroupColors {
+                                    offset: group_offset,
+                                },
+                            );
+                        }
+                        trace(
+                            frame.offset,
+                            frame.length,
+                            ProvenanceTarget::GroupColor(
+                                reopened.unwrap_or(groups.len()),
+                                colors.len(),
+                            ),
+                        );
+                        colors.push(block);
+                    }
+                    GroupHold::Empty => {
+                        trace(
+                            frame.offset,
+                            frame.length,
+                            ProvenanceTarget::Color(color_blocks.len()),
+                        );
+                        record(BlockKind::Color);
+                        color_blocks.push(block);
+                    }
                     GroupHold::HoldingBuilt => {
                         return Err(ASEError::Invalid(error::ConformationError::GroupEnd))
                     }
                 }
             }
+            // skipped above
+            BlockType::Raw(_) => {}
         };
+        data = frame.data;
+    }
 
-        blocks_to_read -= 1;
+    // otherwise the data after the blocks has already been read
+    if options.reject_trailing_data && !options.ignore_block_count {
+        blocks.finish()?;
     }
 
     // if we haven't saved the last group, even if no end was found, go ahead and add it.
-    if group_hold == GroupHold::HoldingBuilding {
-        groups.push(group_hold_value);
+    let unterminated_group = group_hold == GroupHold::HoldingBuilding;
+    let close_group = match group_hold {
+        GroupHold::HoldingBuilding => options.close_unterminated_groups,
+        // if we received a built group, but it was not terminated, it is an error.
+        GroupHold::HoldingBuilt => options.recover_group_nesting,
+        GroupHold::Empty => false,
+    };
+    if close_group {
+        if group_hold == GroupHold::HoldingBuilt {
+            blocks.forget_additional_block();
+        }
+        warn(
+            &mut warnings,
+            ParseWarning::UnterminatedGroup {
+                offset: group_offset,
+            },
+        );
+        if store_group(&mut groups, &mut group_hold_value, &mut reopened) {
+            record(BlockKind::Group);
+        }
+        // the groups containing it are not terminated either
+        for (_, offset) in outer_groups.drain(..).rev() {
+            warn(&mut warnings, ParseWarning::UnterminatedGroup { offset });
+        }
+    } else if group_hold != GroupHold::Empty {
+        return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+    }
+    if let Some(read) = blocks.count_mismatch() {
+        warn(
+            &mut warnings,
+            ParseWarning::BlockCount {
+                declared: blocks.block_count,
+                read,
+            },
+        );
+    }
+
+    let grouped_colors: usize = groups.iter_mut().map(|group| D::colors(group).len()).sum();
+    let info = FileInfo {
+        version_major: (blocks.version >> 16) as u16,
+        version_minor: blocks.version as u16,
+        declared_blocks: blocks.block_count,
+        parsed_blocks: groups
+            .len()
+            .saturating_add(grouped_colors)
+            .saturating_add(color_blocks.len()) as u32,
+        bytes_read: blocks.offset(),
+    };
+    Ok(Blocks {
+        groups,
+        colors: color_blocks,
+        info,
+        unterminated_group,
+        warnings,
+        raw,
+        order,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ConformationError, test_util::named};
+
+    use super::*;
+
+    #[test]
+    fn it_writes_empty_args() {
+        assert_eq!(
+            create_ase(vec![], vec![]),
+            vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0]
+        )
+    }
+
+    #[test]
+    fn it_writes_single_color() {
+        let block = named("name", ColorValue::Gray(0.5));
+        assert_eq!(
+            create_ase(vec![], vec![block]),
+            vec![
+                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0,
+                109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2
+            ]
+        )
+    }
+
+    #[test]
+    fn it_writes_group_color() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        assert_eq!(
+            create_ase(vec![group], vec![]),
+            vec![
+                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0,
+                114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 0, 1, 0,
+                0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0, 104, 0, 116, 0, 32, 0, 103, 0, 114, 0,
+                101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0, 1, 0, 0, 0, 38, 0, 9, 0,
+                100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114, 0, 101, 0, 100, 0, 0, 82, 71, 66, 32,
+                63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204, 205, 0, 2, 192, 2
+            ]
+        )
+    }
+
+    #[test]
+    fn it_writes_group_and_single_color() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        assert_eq!(
+            create_ase(vec![group], vec![block]),
+            vec![
+                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 2, 192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0,
+                114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 0, 1, 0,
+                0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0, 104, 0, 116, 0, 32, 0, 103, 0, 114, 0,
+                101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0, 1, 0, 0, 0, 38, 0, 9, 0,
+                100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114, 0, 101, 0, 100, 0, 0, 82, 71, 66, 32,
+                63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204, 205, 0, 2, 192, 2, 0, 1, 0, 0, 0, 22,
+                0, 5, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2
+            ]
+        )
+    }
+
+    #[test]
+    fn it_round_trips_non_ascii_names() {
+        let names = ["Grün", "Weiß und Öl", "赤", "青い空", "😀", "a😀b"];
+        for name in names {
+            let group = Group::new(name.to_owned(), vec![named(name, ColorValue::Gray(0.5))]);
+            let block = named(name, ColorValue::Rgb(0.1, 0.2, 0.3));
+            let bytes = create_ase(vec![group.clone()], vec![block.clone()]);
+            assert_eq!(
+                read_ase(&*bytes).unwrap(),
+                (vec![group], vec![block]),
+                "{name}"
+            );
+
+            // the name length counts UTF-16 code units, including the null terminator
+            let units = name.encode_utf16().count();
+            assert_eq!(
+                usize::from(u16::from_be_bytes([bytes[18], bytes[19]])),
+                units + 1,
+                "{name}"
+            );
+            // the color block length includes the name length, name, model, values and type
+            let color_start = 12 + 6 + 2 + (units + 1) * 2;
+            assert_eq!(
+                u32::from_be_bytes(bytes[color_start + 2..color_start + 6].try_into().unwrap()),
+                (2 + (units + 1) * 2 + 4 + 4 + 2) as u32,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_validates_blocks() {
+        let invalid_reason = |groups: Vec<Group>, colors: Vec<ColorBlock>| match create_ase_checked(
+            groups, colors,
+        ) {
+            Err(ASEError::InvalidBlock { name, reason }) => Some((name, reason)),
+            Ok(_) => None,
+            Err(err) => panic!("unexpected error {err:?}"),
+        };
+
+        let valid = vec![
+            named("cmyk", ColorValue::Cmyk(0.0, 0.5, 1.0, 1.0)),
+            named("lab", ColorValue::Lab(100.0, -128.0, 127.0)),
+            named("Grün 😀", ColorValue::Rgb(0.0, 1.0, 0.5)),
+        ];
+        let groups = vec![Group::new("group".to_owned(), valid.clone())];
+        assert_eq!(
+            create_ase_checked(groups.clone(), valid.clone()).unwrap(),
+            create_ase(groups, valid)
+        );
+
+        assert_eq!(
+            invalid_reason(vec![Group::new("bad\0group".to_owned(), vec![])], vec![]),
+            Some(("bad\0group".to_owned(), BlockError::NullInName))
+        );
+        let long_name = "a".repeat(usize::from(u16::MAX));
+        assert_eq!(
+            invalid_reason(vec![], vec![named(&long_name, ColorValue::Gray(0.5))]),
+            Some((long_name, BlockError::NameTooLong))
+        );
+        // the longest possible name
+        let longest_name = "😀".repeat(usize::from(u16::MAX / 2));
+        assert_eq!(
+            invalid_reason(vec![], vec![named(&longest_name, ColorValue::Gray(0.5))]),
+            None
+        );
+        for color in [
+            ColorValue::Gray(-0.1),
+            ColorValue::Rgb(0.0, 1.5, 0.0),
+            ColorValue::Lab(101.0, 0.0, 0.0),
+            ColorValue::Lab(50.0, 0.0, -129.0),
+        ] {
+            let group = Group::new("group".to_owned(), vec![named("out of range", color)]);
+            assert_eq!(
+                invalid_reason(vec![group], vec![]),
+                Some(("out of range".to_owned(), BlockError::ComponentOutOfRange))
+            );
+        }
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            for color in [
+                ColorValue::Gray(value),
+                ColorValue::Rgb(0.0, value, 0.0),
+                ColorValue::Cmyk(0.0, 0.0, 0.0, value),
+                ColorValue::Lab(value, 0.0, 0.0),
+                ColorValue::Lab(50.0, 0.0, value),
+            ] {
+                assert_eq!(
+                    invalid_reason(vec![], vec![named("not finite", color)]),
+                    Some(("not finite".to_owned(), BlockError::NonFiniteComponent))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_appends_colors() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        let new_colors = [
+            named("new", ColorValue::Rgb(0.1, 0.2, 0.3)),
+            named("newer", ColorValue::Cmyk(0.1, 0.2, 0.3, 0.4)),
+        ];
+        let existing = create_ase(vec![group.clone()], vec![block.clone()]);
+
+        let appended = append_to_ase(&existing, &new_colors).unwrap();
+        assert_eq!(appended[12..existing.len()], existing[12..]);
+        assert_eq!(
+            appended,
+            create_ase(
+                vec![group.clone()],
+                vec![block.clone(), new_colors[0].clone(), new_colors[1].clone()]
+            )
+        );
+        assert_eq!(append_to_ase(&existing, &[]).unwrap(), existing);
+
+        // same layout as in `it_reads_group_and_single_color_with_group_block_name_only_size`
+        let mut modified_ase_bytes = vec![0; 0];
+        modified_ase_bytes.extend_from_slice(&existing[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&existing[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&existing[18..]);
+        // trailing data is not kept
+        modified_ase_bytes.extend_from_slice(&[0; 4]);
+
+        let appended = append_to_ase(&modified_ase_bytes, &new_colors).unwrap();
+        assert_eq!(appended[..12], [65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 7]);
+        assert_eq!(
+            appended[12..existing.len()],
+            modified_ase_bytes[12..existing.len()]
+        );
+        assert_eq!(
+            read_ase(&*appended).unwrap(),
+            (
+                vec![group],
+                vec![block, new_colors[0].clone(), new_colors[1].clone()]
+            )
+        );
+    }
+
+    #[test]
+    fn it_appends_colors_after_unterminated_group() {
+        let group = Group::new(
+            "group".to_owned(),
+            vec![named("light grey", ColorValue::Gray(0.5))],
+        );
+        // a group block only containing its name, followed by a color without group end
+        let mut existing = create_ase(vec![], vec![]);
+        existing[11] = 2;
+        existing.extend_from_slice(&[192, 1, 0, 0, 0, 14, 0, 6]);
+        existing.extend_from_slice(&[0, 103, 0, 114, 0, 111, 0, 117, 0, 112, 0, 0]);
+        existing.extend_from_slice(&create_ase(vec![], group.blocks.clone())[12..]);
+        assert_eq!(read_ase(&*existing).unwrap(), (vec![group.clone()], vec![]));
+
+        let color = named("new", ColorValue::Gray(0.1));
+        let appended = append_to_ase(&existing, std::slice::from_ref(&color)).unwrap();
+        assert_eq!(read_ase(&*appended).unwrap(), (vec![group], vec![color]));
+    }
+
+    #[test]
+    fn it_does_not_append_to_invalid_files() {
+        let color = named("new", ColorValue::Gray(0.1));
+        assert!(matches!(
+            append_to_ase(b"ASEX\0\x01\0\0\0\0\0\0", std::slice::from_ref(&color)),
+            Err(ASEError::Invalid(ConformationError::FileSignature))
+        ));
+        assert!(matches!(
+            append_to_ase(b"ASEF\0\x02\0\0\0\0\0\0", &[color]),
+            Err(ASEError::Invalid(ConformationError::FileVersion))
+        ));
     }
 
-    // if we received a built group, but it was terminated, it is an error.
-    if group_hold == GroupHold::HoldingBuilt {
-        return Err(ASEError::Invalid(error::ConformationError::GroupEnd));
+    #[test]
+    fn it_creates_from_iterators() {
+        let colors = vec![
+            named("light grey", ColorValue::Gray(0.5)),
+            named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+        ];
+        let group = Group::new("group name".to_owned(), colors.clone());
+
+        assert_eq!(
+            create_ase_from_iter(
+                std::iter::once(group.clone()),
+                colors
+                    .iter()
+                    .filter(|block| block.name.contains("red"))
+                    .cloned()
+            )
+            .unwrap(),
+            create_ase(vec![group], vec![colors[1].clone()])
+        );
+        assert_eq!(
+            create_ase_from_iter(std::iter::empty(), std::iter::empty()).unwrap(),
+            create_ase(vec![], vec![])
+        );
+    }
+
+    #[test]
+    fn it_creates_from_references() {
+        let groups = vec![
+            Group::new(
+                "group name".to_owned(),
+                vec![named("light grey", ColorValue::Gray(0.5))],
+            ),
+            Group::new("empty".to_owned(), vec![]),
+        ];
+        let colors = vec![named("name", ColorValue::Lab(50.0, 0.3, 0.1))];
+
+        let ase = create_ase_ref(&groups, &colors).unwrap();
+        // the groups and colors are still usable
+        assert_eq!(read_ase(&*ase).unwrap(), (groups.clone(), colors.clone()));
+        assert_eq!(ase, create_ase(groups, colors));
     }
 
-    Ok((groups, color_blocks))
-}
+    #[test]
+    fn it_reuses_the_output_vector() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![named("light grey", ColorValue::Gray(0.5))],
+        );
+        let colors = vec![named("name", ColorValue::Lab(50.0, 0.3, 0.1))];
 
-#[cfg(test)]
-mod tests {
-    use crate::error::ConformationError;
+        let mut out = vec![1, 2, 3];
+        out.reserve(1024);
+        let (pointer, capacity) = (out.as_ptr(), out.capacity());
+        create_ase_into(vec![group.clone()], colors.clone(), &mut out).unwrap();
+        assert_eq!(out, create_ase(vec![group.clone()], colors.clone()));
+        // no reallocation happened
+        assert_eq!((out.as_ptr(), out.capacity()), (pointer, capacity));
 
-    use super::*;
+        create_ase_into(vec![], colors.clone(), &mut out).unwrap();
+        assert_eq!(out, create_ase(vec![], colors));
+        assert_eq!((out.as_ptr(), out.capacity()), (pointer, capacity));
 
-    #[test]
-    fn it_writes_empty_args() {
-        assert_eq!(
-            create_ase(vec![], vec![]),
-            vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0]
-        )
+        // grows too small vectors
+        let mut out = Vec::with_capacity(1);
+        create_ase_into(vec![group.clone()], vec![], &mut out).unwrap();
+        assert_eq!(out, create_ase(vec![group], vec![]));
     }
 
     #[test]
-    fn it_writes_single_color() {
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    fn it_reads_names_up_to_null() {
+        let group = Group::new(
+            "group\0name".to_owned(),
+            vec![named("bad\0name", ColorValue::Gray(0.5))],
+        );
+        let color = named("\0", ColorValue::Gray(0.1));
+        let options = WriteOptions {
+            name_encoding: NameEncoding::Raw,
+            ..Default::default()
+        };
+        let ase = create_ase_with_options(vec![group], vec![color], &options).unwrap();
         assert_eq!(
-            create_ase(vec![], vec![block]),
-            vec![
-                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0,
-                109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2
-            ]
-        )
+            read_ase(&*ase).unwrap(),
+            (
+                vec![Group::new(
+                    "group".to_owned(),
+                    vec![named("bad", ColorValue::Gray(0.5))]
+                )],
+                vec![named("", ColorValue::Gray(0.1))]
+            )
+        );
     }
 
     #[test]
-    fn it_writes_group_color() {
+    fn it_creates_interleaved_blocks() {
+        let first = named("first", ColorValue::Gray(0.5));
         let group = Group::new(
-            "group name".to_owned(),
-            vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
-            ],
+            "group".to_owned(),
+            vec![named("grouped", ColorValue::Rgb(0.5, 0.3, 0.1))],
         );
+        let last = named("last", ColorValue::Gray(0.1));
+        let ase = create_ase_blocks(vec![
+            Block::Color(first.clone()),
+            Block::Group(group.clone()),
+            Block::Color(last.clone()),
+        ])
+        .unwrap();
+
+        // the blocks keep their order
+        let header = 12;
+        let group_start = header + 6 + first.calculate_length() as usize;
+        let last_start = group_start + 6 + group.calculate_length() as usize + 2;
+        assert_eq!(ase[header..header + 2], [0, 1]);
+        assert_eq!(ase[group_start..group_start + 2], [192, 1]);
+        assert_eq!(ase[last_start - 2..last_start + 2], [192, 2, 0, 1]);
         assert_eq!(
-            create_ase(vec![group], vec![]),
-            vec![
-                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0,
-                114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 0, 1, 0,
-                0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0, 104, 0, 116, 0, 32, 0, 103, 0, 114, 0,
-                101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0, 1, 0, 0, 0, 38, 0, 9, 0,
-                100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114, 0, 101, 0, 100, 0, 0, 82, 71, 66, 32,
-                63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204, 205, 0, 2, 192, 2
-            ]
-        )
+            ase[..12],
+            create_ase(vec![group.clone()], vec![first.clone(), last.clone()])[..12]
+        );
+
+        let mut names = Vec::new();
+        parse_ase_events(&*ase, |event| {
+            match event {
+                AseEvent::GroupStart { name } | AseEvent::Color { name, .. } => {
+                    names.push(name.to_owned())
+                }
+                AseEvent::GroupEnd => names.push("end".to_owned()),
+            }
+            std::ops::ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(names, ["first", "group", "grouped", "end", "last"]);
+        assert_eq!(read_ase(&*ase).unwrap(), (vec![group], vec![first, last]));
     }
 
     #[test]
-    fn it_writes_group_and_single_color() {
+    fn it_streams_into_writer() {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
-        assert_eq!(
-            create_ase(vec![group], vec![block]),
-            vec![
-                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 2, 192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0,
-                114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 0, 1, 0,
-                0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0, 104, 0, 116, 0, 32, 0, 103, 0, 114, 0,
-                101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0, 1, 0, 0, 0, 38, 0, 9, 0,
-                100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114, 0, 101, 0, 100, 0, 0, 82, 71, 66, 32,
-                63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204, 205, 0, 2, 192, 2, 0, 1, 0, 0, 0, 22,
-                0, 5, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2
-            ]
-        )
+        let groups = vec![group, Group::new("empty".to_owned(), vec![])];
+        let colors = vec![named("name", ColorValue::Lab(50.0, 0.3, 0.1))];
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        write_ase(&mut cursor, groups.clone(), colors.clone()).unwrap();
+        let bytes = cursor.into_inner();
+        assert_eq!(bytes, create_ase(groups.clone(), colors.clone()));
+        assert_eq!(read_ase(&*bytes).unwrap(), (groups, colors));
+    }
+
+    #[test]
+    fn it_returns_io_error_from_writer() {
+        struct Full;
+        impl std::io::Write for Full {
+            fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::WriteZero.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let res = write_ase(Full, vec![], vec![named("name", ColorValue::Gray(0.5))]);
+        assert!(
+            matches!(res, Err(ASEError::Io(ref err)) if err.kind() == std::io::ErrorKind::WriteZero),
+            "Only ASEError::Io should be returned"
+        );
     }
 
     #[test]
@@ -275,7 +2035,7 @@ mod tests {
 
     #[test]
     fn it_reads_single_color() {
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let block = named("name", ColorValue::Gray(0.5));
         let res = read_ase(&*create_ase(vec![], vec![block.clone()]));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -288,16 +2048,8 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
         let res = read_ase(&*create_ase(vec![group.clone()], vec![]));
@@ -312,19 +2064,11 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let block = named("name", ColorValue::Gray(0.5));
         let res = read_ase(&*create_ase(vec![group.clone()], vec![block.clone()]));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -338,19 +2082,11 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let block = named("name", ColorValue::Gray(0.5));
         let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
         let mut modified_ase_bytes = vec![0; 0];
 
@@ -379,19 +2115,11 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let block = named("name", ColorValue::Gray(0.5));
         let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
         let mut modified_ase_bytes = vec![0; 0];
 
@@ -430,19 +2158,11 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let block = named("name", ColorValue::Gray(0.5));
         let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
         let mut modified_ase_bytes = vec![0; 0];
 
@@ -479,6 +2199,472 @@ mod tests {
         assert_eq!(res, (vec![group], vec![block]));
     }
 
+    #[test]
+    fn it_round_trips_unknown_blocks() {
+        let color = named("color", ColorValue::Gray(0.5));
+        let group = Group::new(
+            "group".to_owned(),
+            vec![named("grouped", ColorValue::Gray(0.1))],
+        );
+
+        let mut input = create_ase(vec![], vec![]);
+        input[8..12].copy_from_slice(&3u32.to_be_bytes());
+        input.extend(color.to_bytes());
+        input.extend([0x00, 0x42, 0, 0, 0, 5, 1, 2, 3, 4, 5]);
+        input.extend(group.to_bytes());
+
+        let options = ParseOptions {
+            keep_unknown_blocks: true,
+            ..Default::default()
+        };
+        let blocks = read_ase_blocks(&*input, &options).unwrap();
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Color(color.clone()),
+                Block::Raw(RawBlock {
+                    type_id: 0x0042,
+                    data: vec![1, 2, 3, 4, 5]
+                }),
+                Block::Group(group.clone())
+            ]
+        );
+        assert_eq!(create_ase_blocks(blocks).unwrap(), input);
+
+        // other functions skip the block
+        assert_eq!(
+            read_ase_with_options(&*input, &options).unwrap(),
+            (vec![group], vec![color])
+        );
+        assert!(read_ase_blocks(&*input, &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn it_locates_errors() {
+        let group = Group::new(
+            "group".to_owned(),
+            vec![named("grouped", ColorValue::Gray(0.5))],
+        );
+        let colors = (0..3)
+            .map(|i| named(&format!("color {i}"), ColorValue::Gray(0.5)))
+            .collect();
+        let mut input = create_ase(vec![group], colors);
+        let (_, _, provenance) = read_ase_traced(&*input).unwrap();
+        let color = provenance
+            .iter()
+            .find(|block| block.target == ProvenanceTarget::Color(1))
+            .unwrap();
+        // the color type is the last byte of the color
+        let corrupted = color.offset as usize + 6 + color.length as usize - 1;
+        input[corrupted] = 7;
+
+        let err = read_ase_with_options(&*input, &ParseOptions::DEFAULT).unwrap_err();
+        // the group start, its end and the first color come before it
+        assert!(matches!(
+            err,
+            ASEError::Block { offset, index: 3, ref error }
+                if offset == color.offset && matches!(err.root_cause(), ASEError::ColorTypeError)
+                && matches!(**error, ASEError::Swatch { ref name, .. } if name == "color 1")
+        ));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error converting ColorType in \"color 1\" (block 3 at offset {})",
+                color.offset
+            )
+        );
+
+        // the wrapped errors are exposed as sources
+        let mut sources = Vec::new();
+        let mut source = std::error::Error::source(&err);
+        while let Some(error) = source {
+            sources.push(error.to_string());
+            source = error.source();
+        }
+        assert_eq!(
+            sources,
+            [
+                "Error converting ColorType in \"color 1\"",
+                "Error converting ColorType"
+            ]
+        );
+
+        let err = file_error(std::path::Path::new("palette.ase"), err);
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(matches!(
+            source.downcast_ref::<ASEError>(),
+            Some(ASEError::Block { index: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn it_recovers_group_nesting() {
+        let first = Group::new("first".to_owned(), vec![named("a", ColorValue::Gray(0.1))]);
+        let second = Group::new("second".to_owned(), vec![named("b", ColorValue::Gray(0.2))]);
+        let single = named("c", ColorValue::Gray(0.3));
+
+        let unterminated = |group: &Group| {
+            let mut bytes = group.to_bytes();
+            bytes.truncate(bytes.len() - 2);
+            bytes
+        };
+
+        // groups without group ends and a group end outside of a group
+        let mut input = create_ase(vec![], vec![]);
+        input[8..12].copy_from_slice(&4u32.to_be_bytes());
+        input.extend([0xc0, 0x02]);
+        input.extend(unterminated(&first));
+        input.extend(unterminated(&second));
+        input.extend(single.to_bytes());
+
+        assert!(matches!(
+            read_ase(&*input),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+        let res = read_ase_with_options(&*input, &ParseOptions::LENIENT);
+        assert_eq!(res.unwrap(), (vec![first.clone(), second], vec![single]));
+
+        // the end of the file closes the group
+        let mut input = create_ase(vec![], vec![]);
+        input[8..12].copy_from_slice(&1u32.to_be_bytes());
+        input.extend(unterminated(&first));
+        assert!(read_ase(&*input).is_err());
+        let res = read_ase_with_options(&*input, &ParseOptions::LENIENT);
+        assert_eq!(res.unwrap(), (vec![first], vec![]));
+    }
+
+    fn provenance(
+        index: usize,
+        offset: u64,
+        length: u32,
+        target: ProvenanceTarget,
+    ) -> BlockProvenance {
+        BlockProvenance {
+            index,
+            offset,
+            length,
+            target,
+        }
+    }
+
+    #[test]
+    fn it_reads_empty_group_and_single_color() {
+        let group = Group::new("empty".to_owned(), vec![]);
+        let block = named("name", ColorValue::Gray(0.5));
+        let res = read_ase(&*create_ase(vec![group.clone()], vec![block.clone()]));
+        assert_eq!(res.unwrap(), (vec![group], vec![block]));
+    }
+
+    #[test]
+    fn it_traces_group_and_single_color() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
+
+        let (groups, colors, trace) = read_ase_traced(&*input_ase_bytes).unwrap();
+        assert_eq!((groups, colors), read_ase(&*input_ase_bytes).unwrap());
+        assert_eq!(
+            trace,
+            vec![
+                provenance(0, 12, 108, ProvenanceTarget::Group(0)),
+                provenance(1, 42, 34, ProvenanceTarget::GroupColor(0, 0)),
+                provenance(2, 82, 38, ProvenanceTarget::GroupColor(0, 1)),
+                provenance(3, 128, 22, ProvenanceTarget::Color(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_traces_group_block_name_only_size_and_explicit_group_end_size() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
+        // same layout as in
+        // `it_reads_group_and_single_color_with_group_block_name_only_size_and_explicit_group_end_size`
+        let mut modified_ase_bytes = vec![0; 0];
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..128]);
+        modified_ase_bytes.extend_from_slice(&[0; 4]);
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[128..]);
+
+        let (groups, colors, trace) = read_ase_traced(&*modified_ase_bytes).unwrap();
+        // provenance does not affect the parsed values
+        assert_eq!((groups, colors), (vec![group], vec![block]));
+        assert_eq!(
+            trace,
+            vec![
+                provenance(0, 12, 24, ProvenanceTarget::Group(0)),
+                provenance(1, 42, 34, ProvenanceTarget::GroupColor(0, 0)),
+                provenance(2, 82, 38, ProvenanceTarget::GroupColor(0, 1)),
+                provenance(3, 132, 22, ProvenanceTarget::Color(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reads_info() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        let input_ase_bytes = create_ase(vec![group.clone()], vec![block.clone()]);
+
+        let (groups, colors, info) = read_ase_info(&*input_ase_bytes).unwrap();
+        assert_eq!((groups, colors), (vec![group], vec![block]));
+        assert_eq!(
+            info,
+            FileInfo {
+                version_major: 1,
+                version_minor: 0,
+                declared_blocks: 2,
+                parsed_blocks: 4,
+                bytes_read: input_ase_bytes.len() as u64,
+            }
+        );
+    }
+
+    #[test]
+    fn it_reads_info_with_group_block_name_only_size() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        let input_ase_bytes = create_ase(vec![group], vec![block]);
+        // same layout as in `it_reads_group_and_single_color_with_group_block_name_only_size`
+        let mut modified_ase_bytes = vec![0; 0];
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..]);
+        // trailing data is not read
+        modified_ase_bytes.extend_from_slice(&[0; 4]);
+
+        let (_, _, info) = read_ase_info(&*modified_ase_bytes).unwrap();
+        // the group end is declared, but not a parsed group or color
+        assert_eq!(info.declared_blocks, 5);
+        assert_eq!(info.parsed_blocks, 4);
+        assert_eq!(info.bytes_read, input_ase_bytes.len() as u64);
+    }
+
+    #[test]
+    fn it_detects_truncation_before_parsing() {
+        use std::io::Cursor;
+
+        let groups = (0..50)
+            .map(|i| {
+                let colors = (0..20)
+                    .map(|j| named(&format!("color {i}-{j}"), ColorValue::Gray(0.5)))
+                    .collect();
+                Group::new(format!("group {i}"), colors)
+            })
+            .collect();
+        let colors = (0..100)
+            .map(|i| named(&format!("color {i}"), ColorValue::Rgb(0.1, 0.2, 0.3)))
+            .collect();
+        let bytes = create_ase(groups, colors);
+        assert_eq!(
+            read_ase_seek(Cursor::new(&bytes)).unwrap(),
+            read_ase(&*bytes).unwrap()
+        );
+
+        let (_, _, provenance) = read_ase_traced(&*bytes).unwrap();
+        let top_level: Vec<_> = provenance
+            .iter()
+            .filter(|p| !matches!(p.target, ProvenanceTarget::GroupColor(..)))
+            .collect();
+        for block in [top_level[0], top_level[25], top_level[49], top_level[149]] {
+            let block_end = block.offset as usize + 6 + block.length as usize;
+            for cut in [block.offset as usize + 6, block_end - 1] {
+                let res = read_ase_seek(Cursor::new(&bytes[..cut]));
+                assert!(
+                    matches!(
+                        res,
+                        Err(ASEError::Invalid(ConformationError::Truncated { missing })) if missing == (block_end - cut) as u64
+                    ),
+                    "expected truncation at {cut}, got {res:?}"
+                );
+            }
+        }
+
+        // truncated between blocks, which is reported while parsing
+        let cut = top_level[1].offset as usize;
+        assert!(matches!(
+            read_ase_seek(Cursor::new(&bytes[..cut]))
+                .as_ref()
+                .map_err(ASEError::root_cause),
+            Err(ASEError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn it_reads_seekable_with_group_block_name_only_size() {
+        use std::io::{Cursor, Seek, SeekFrom};
+
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let input_ase_bytes = create_ase(vec![group], vec![named("name", ColorValue::Gray(0.5))]);
+        let mut modified_ase_bytes = vec![0; 3];
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..]);
+        modified_ase_bytes.extend_from_slice(&[0; 4]);
+
+        // reading starts at the current position
+        let mut source = Cursor::new(&modified_ase_bytes);
+        source.seek(SeekFrom::Start(3)).unwrap();
+        assert_eq!(
+            read_ase_seek(source).unwrap(),
+            read_ase(&input_ase_bytes[..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_round_trips_gray_polarity() {
+        let gray = named("gray", ColorValue::Gray(0.25));
+        let group = Group::new("group".to_owned(), vec![gray.clone()]);
+        for (polarity, stored) in [
+            (GrayPolarity::ZeroIsBlack, 0.25_f32),
+            (GrayPolarity::ZeroIsWhite, 0.75_f32),
+        ] {
+            let write_options = WriteOptions {
+                gray_polarity: polarity,
+                ..Default::default()
+            };
+            let ase =
+                create_ase_with_options(vec![group.clone()], vec![gray.clone()], &write_options)
+                    .unwrap();
+            // both colors end with the gray value, followed by their color type
+            let value = stored.to_be_bytes();
+            assert_eq!(ase.windows(4).filter(|bytes| *bytes == value).count(), 2);
+
+            let (_, colors) = read_ase(&*ase).unwrap();
+            assert_eq!(colors[0].color, ColorValue::Gray(stored));
+
+            let parse_options = ParseOptions {
+                gray_polarity: polarity,
+                ..Default::default()
+            };
+            let (groups, colors) = read_ase_with_options(&*ase, &parse_options).unwrap();
+            assert_eq!(groups, vec![group.clone()]);
+            assert_eq!(colors, vec![gray.clone()]);
+        }
+    }
+
+    #[test]
+    fn it_sanitizes_names_while_reading() {
+        let group = Group::new(
+            "\u{FEFF}group".to_owned(),
+            vec![named("\u{FEFF}light\u{200B} grey ", ColorValue::Gray(0.5))],
+        );
+        let color = named("dark\u{200B}red", ColorValue::Rgb(0.5, 0.3, 0.1));
+        let ase = create_ase(vec![group.clone()], vec![color.clone()]);
+
+        // names are read as they are by default
+        assert_eq!(read_ase(&*ase).unwrap(), (vec![group], vec![color]));
+
+        let options = ParseOptions {
+            sanitize_names: true,
+            ..Default::default()
+        };
+        let (groups, colors) = read_ase_with_options(&*ase, &options).unwrap();
+        assert_eq!(groups[0].name, "group");
+        assert_eq!(groups[0].blocks[0].name, "light grey");
+        assert_eq!(colors[0].name, "darkred");
+    }
+
+    /// Generates deterministic data for [`arbitrary`] using a simple xorshift.
+    #[cfg(feature = "arbitrary")]
+    fn xorshift_data(seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn it_round_trips_arbitrary_documents() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        for seed in 1..=64_u32 {
+            let data = xorshift_data(seed);
+            let mut u = Unstructured::new(&data);
+            let groups = Vec::<Group>::arbitrary(&mut u).unwrap();
+            let colors = Vec::<ColorBlock>::arbitrary(&mut u).unwrap();
+
+            if let Err(err) = assert_roundtrip(&groups, &colors) {
+                panic!("seed {seed}: {err}");
+            }
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn it_round_trips_mutated_documents() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        // like the `differential` fuzz target, every mutated file, which can be read,
+        // has to be read the same after being written again
+        let mut read = 0;
+        for seed in 1..=64_u32 {
+            let data = xorshift_data(seed);
+            let mut u = Unstructured::new(&data);
+            let groups = Vec::<Group>::arbitrary(&mut u).unwrap();
+            let colors = Vec::<ColorBlock>::arbitrary(&mut u).unwrap();
+            let mutations = Vec::<(u16, u8)>::arbitrary(&mut u).unwrap();
+
+            let mut ase = create_ase(groups, colors);
+            // only a few bytes are flipped, so that most files can still be read
+            for (position, mask) in mutations.into_iter().take(4) {
+                let position = usize::from(position) % ase.len();
+                ase[position] ^= mask;
+            }
+            if let Ok((groups, colors)) = read_ase(&*ase) {
+                read += 1;
+                if let Err(err) = assert_roundtrip(&groups, &colors) {
+                    panic!("seed {seed}: {err}");
+                }
+            }
+        }
+        assert!(read > 0);
+    }
+
     #[test]
     fn it_returns_incorrect_block_type_error() {
         let input_bad_block_type = vec![
@@ -565,6 +2751,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_reads_minor_versions() {
+        let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let with_version = |major: u16, minor: u16| {
+            let mut bytes = create_ase(vec![], vec![color.clone()]);
+            bytes[4..6].copy_from_slice(&major.to_be_bytes());
+            bytes[6..8].copy_from_slice(&minor.to_be_bytes());
+            bytes
+        };
+
+        for minor in [0, 1] {
+            let input = with_version(1, minor);
+            let (_, colors, info) = read_ase_info(&*input).unwrap();
+            assert_eq!(colors, vec![color.clone()]);
+            assert_eq!((info.version_major, info.version_minor), (1, minor));
+            let (_, _, warnings) = read_ase_with_warnings(&*input, &ParseOptions::STRICT).unwrap();
+            assert!(warnings.is_empty());
+        }
+
+        let input = with_version(2, 0);
+        for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+            assert!(matches!(
+                read_ase_with_options(&*input, &options),
+                Err(ASEError::Invalid(ConformationError::FileVersion))
+            ));
+        }
+        let (_, colors, warnings) =
+            read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+        assert_eq!(colors, vec![color]);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::FileVersion { major: 2, minor: 0 }]
+        );
+    }
+
+    #[test]
+    fn it_reads_header() {
+        let group = Group::new(
+            "group".to_owned(),
+            vec![ColorBlock::new(
+                "color".to_owned(),
+                ColorValue::Gray(0.5),
+                ColorType::Normal,
+            )],
+        );
+        let mut input = std::io::Cursor::new(create_ase(vec![group], vec![]));
+        assert_eq!(
+            read_ase_header(&mut input).unwrap(),
+            AseHeader {
+                version_major: 1,
+                version_minor: 0,
+                block_count: 1,
+            }
+        );
+        assert_eq!(input.position(), 12);
+
+        assert!(matches!(
+            read_ase_header(&b"ASEX\0\x01\0\0\0\0\0\0"[..]),
+            Err(ASEError::Invalid(ConformationError::FileSignature))
+        ));
+        assert!(matches!(
+            read_ase_header(&b"ASEF\0\x02\0\0\0\0\0\0"[..]),
+            Err(ASEError::Invalid(ConformationError::FileVersion))
+        ));
+        assert!(matches!(
+            read_ase_header(&b"ASEF\0\x01"[..]),
+            Err(ASEError::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+    }
+
     #[test]
     fn it_returns_incorrect_block_end_error() {
         let input_bad_group_end = vec![
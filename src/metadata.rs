@@ -0,0 +1,598 @@
+//! Descriptive metadata, which is not part of the ASE format.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{create_ase_ref, read_ase, ASEError, Ase, Group};
+
+/// Prefix of the name of the empty group, which stores metadata inside the palette.
+///
+/// The zero width space makes it unlikely that the name clashes with a regular group.
+pub const METADATA_GROUP_PREFIX: &str = "\u{200B}ase-meta:";
+
+/// Descriptive information about a palette.
+///
+/// ASE files have no fields for this, so it is either stored in a JSON file next to the
+/// palette, see [`write_ase_with_metadata`], or in a specially named empty group, see
+/// [`Ase::set_metadata`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PaletteMetadata {
+    /// The title of the palette
+    pub title: Option<String>,
+    /// The author of the palette
+    pub author: Option<String>,
+    /// A description of the palette
+    pub description: Option<String>,
+    /// The license of the palette
+    pub license: Option<String>,
+    /// Tags used for organizing palettes
+    pub tags: Vec<String>,
+    /// Creation time, in any format
+    pub created: Option<String>,
+    /// Time of the last modification, in any format
+    pub modified: Option<String>,
+}
+
+impl PaletteMetadata {
+    /// Encodes the metadata as a JSON object.
+    ///
+    /// Fields without a value are omitted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::PaletteMetadata;
+    /// let metadata = PaletteMetadata {
+    ///     title: Some("Aurora".to_owned()),
+    ///     tags: vec!["green".to_owned()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(metadata.to_json(), "{\"title\":\"Aurora\",\"tags\":[\"green\"]}");
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        let fields = [
+            ("title", &self.title),
+            ("author", &self.author),
+            ("description", &self.description),
+            ("license", &self.license),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                write_key(&mut json, key);
+                write_string(&mut json, value);
+            }
+        }
+        if !self.tags.is_empty() {
+            write_key(&mut json, "tags");
+            json.push('[');
+            for (index, tag) in self.tags.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                write_string(&mut json, tag);
+            }
+            json.push(']');
+        }
+        for (key, value) in [("created", &self.created), ("modified", &self.modified)] {
+            if let Some(value) = value {
+                write_key(&mut json, key);
+                write_string(&mut json, value);
+            }
+        }
+        json.push('}');
+        json
+    }
+
+    /// Decodes metadata from a JSON object, as written by [`PaletteMetadata::to_json`].
+    ///
+    /// Unknown keys are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an [`ASEError::InputDataParseError`] if the input is not a
+    /// JSON object, or a known key has a value of the wrong type.
+    pub fn from_json(json: &str) -> Result<Self, ASEError> {
+        let mut parser = Parser {
+            bytes: json.as_bytes(),
+            pointer: 0,
+        };
+        let mut metadata = Self::default();
+
+        parser.expect(b'{')?;
+        if !parser.consume(b'}') {
+            loop {
+                let key = parser.string()?;
+                parser.expect(b':')?;
+                match key.as_str() {
+                    "title" => metadata.title = parser.optional_string()?,
+                    "author" => metadata.author = parser.optional_string()?,
+                    "description" => metadata.description = parser.optional_string()?,
+                    "license" => metadata.license = parser.optional_string()?,
+                    "tags" => metadata.tags = parser.strings()?,
+                    "created" => metadata.created = parser.optional_string()?,
+                    "modified" => metadata.modified = parser.optional_string()?,
+                    _ => parser.skip_value(MAX_DEPTH)?,
+                }
+
+                if !parser.consume(b',') {
+                    parser.expect(b'}')?;
+                    break;
+                }
+            }
+        }
+
+        parser.skip_whitespace();
+        if parser.pointer != parser.bytes.len() {
+            return Err(ASEError::InputDataParseError);
+        }
+        Ok(metadata)
+    }
+}
+
+impl Ase {
+    /// Stores the metadata in an empty group at the start of the palette, replacing
+    /// any previously stored metadata.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{create_ase, read_ase, Ase, PaletteMetadata};
+    /// let metadata = PaletteMetadata {
+    ///     author: Some("Jane Doe".to_owned()),
+    ///     ..Default::default()
+    /// };
+    /// let mut ase = Ase::default();
+    /// ase.set_metadata(&metadata);
+    ///
+    /// let (groups, colors) = read_ase(&*create_ase(ase.groups, ase.colors)).unwrap();
+    /// let mut ase = Ase::new(groups, colors);
+    /// assert_eq!(ase.take_metadata().unwrap(), Some(metadata));
+    /// assert!(ase.groups.is_empty());
+    /// ```
+    pub fn set_metadata(&mut self, metadata: &PaletteMetadata) {
+        self.groups.retain(|group| !is_metadata_group(group));
+        self.groups.insert(
+            0,
+            Group::new(
+                format!("{METADATA_GROUP_PREFIX}{}", metadata.to_json()),
+                vec![],
+            ),
+        );
+    }
+
+    /// Removes all groups storing metadata from the palette, returning the metadata of
+    /// the first one.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the stored metadata is invalid, in which
+    /// case the palette is left unchanged.
+    pub fn take_metadata(&mut self) -> Result<Option<PaletteMetadata>, ASEError> {
+        let metadata = self
+            .groups
+            .iter()
+            .find(|group| is_metadata_group(group))
+            .map(|group| PaletteMetadata::from_json(&group.name[METADATA_GROUP_PREFIX.len()..]))
+            .transpose()?;
+        self.groups.retain(|group| !is_metadata_group(group));
+        Ok(metadata)
+    }
+}
+
+pub(crate) fn is_metadata_group(group: &Group) -> bool {
+    group.blocks.is_empty() && group.name.starts_with(METADATA_GROUP_PREFIX)
+}
+
+/// Returns the path of the JSON file storing the metadata of the palette at `path`.
+///
+/// # Examples
+/// ```rust
+/// # use std::path::Path;
+/// # use adobe_swatch_exchange::metadata_path;
+/// assert_eq!(metadata_path("palettes/aurora.ase"), Path::new("palettes/aurora.ase.json"));
+/// ```
+pub fn metadata_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let mut path = path.as_ref().as_os_str().to_owned();
+    path.push(".json");
+    PathBuf::from(path)
+}
+
+/// Writes the palette to `path` and its metadata to a JSON file next to it,
+/// see [`metadata_path`].
+///
+/// # Errors
+///
+/// This function will return an error if writing either file fails, or a name is too long.
+pub fn write_ase_with_metadata<P: AsRef<Path>>(
+    path: P,
+    ase: &Ase,
+    metadata: &PaletteMetadata,
+) -> Result<(), ASEError> {
+    let path = path.as_ref();
+    fs::write(path, create_ase_ref(&ase.groups, &ase.colors)?)?;
+    fs::write(metadata_path(path), metadata.to_json())?;
+    Ok(())
+}
+
+/// Reads the palette at `path` and the metadata stored next to it, see [`metadata_path`].
+///
+/// The metadata is `None` if there is no metadata file.
+///
+/// # Errors
+///
+/// This function will return an error if either file cannot be read, or is invalid.
+pub fn read_ase_with_metadata<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Ase, Option<PaletteMetadata>), ASEError> {
+    let path = path.as_ref();
+    let ase = Ase::from(read_ase(fs::File::open(path)?)?);
+    let metadata = match fs::read_to_string(metadata_path(path)) {
+        Ok(json) => Some(PaletteMetadata::from_json(&json)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    Ok((ase, metadata))
+}
+
+fn write_key(json: &mut String, key: &str) {
+    if json.len() > 1 {
+        json.push(',');
+    }
+    write_string(json, key);
+    json.push(':');
+}
+
+fn write_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// A minimal JSON parser, only decoding strings.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pointer: usize,
+}
+
+/// Maximum nesting of skipped arrays and objects.
+const MAX_DEPTH: usize = 64;
+
+// metadata is read from untrusted files, so parsing must not panic
+impl Parser<'_> {
+    fn advance(&mut self, count: usize) {
+        self.pointer = self.pointer.saturating_add(count);
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(
+            self.bytes.get(self.pointer),
+            Some(b' ' | b'\n' | b'\r' | b'\t')
+        ) {
+            self.advance(1);
+        }
+    }
+
+    /// Skips whitespace and consumes the given byte, if it is next.
+    fn consume(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.bytes.get(self.pointer) == Some(&byte) {
+            self.advance(1);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), ASEError> {
+        if self.consume(byte) {
+            Ok(())
+        } else {
+            Err(ASEError::InputDataParseError)
+        }
+    }
+
+    fn consume_literal(&mut self, literal: &[u8]) -> bool {
+        self.skip_whitespace();
+        let matches = self
+            .bytes
+            .get(self.pointer..)
+            .map_or(false, |rest| rest.starts_with(literal));
+        if matches {
+            self.advance(literal.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, ASEError> {
+        if self.consume_literal(b"null") {
+            return Ok(None);
+        }
+        self.string().map(Some)
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, ASEError> {
+        let mut strings = Vec::new();
+        self.expect(b'[')?;
+        if self.consume(b']') {
+            return Ok(strings);
+        }
+        loop {
+            strings.push(self.string()?);
+            if !self.consume(b',') {
+                self.expect(b']')?;
+                return Ok(strings);
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ASEError> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            let start = self.pointer;
+            while !matches!(self.bytes.get(self.pointer), Some(b'"' | b'\\') | None) {
+                self.advance(1);
+            }
+            // the input is valid UTF-8 and only split at ASCII characters
+            string.push_str(
+                self.bytes
+                    .get(start..self.pointer)
+                    .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                    .ok_or(ASEError::InputDataParseError)?,
+            );
+
+            match self.next()? {
+                b'"' => return Ok(string),
+                _ => match self.next()? {
+                    b'"' => string.push('"'),
+                    b'\\' => string.push('\\'),
+                    b'/' => string.push('/'),
+                    b'b' => string.push('\u{8}'),
+                    b'f' => string.push('\u{c}'),
+                    b'n' => string.push('\n'),
+                    b'r' => string.push('\r'),
+                    b't' => string.push('\t'),
+                    b'u' => {
+                        let unit = self.hex()?;
+                        let mut units = vec![unit];
+                        // surrogate pairs are written as two escapes
+                        if (0xd800..0xdc00).contains(&unit) && self.consume_literal(b"\\u") {
+                            units.push(self.hex()?);
+                        }
+                        string.push_str(
+                            &String::from_utf16(&units)
+                                .map_err(|_| ASEError::InputDataParseError)?,
+                        );
+                    }
+                    _ => return Err(ASEError::InputDataParseError),
+                },
+            }
+        }
+    }
+
+    fn hex(&mut self) -> Result<u16, ASEError> {
+        let digits = self
+            .bytes
+            .get(self.pointer..self.pointer.saturating_add(4))
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u16::from_str_radix(digits, 16).ok())
+            .ok_or(ASEError::InputDataParseError)?;
+        self.advance(4);
+        Ok(digits)
+    }
+
+    fn next(&mut self) -> Result<u8, ASEError> {
+        let byte = *self
+            .bytes
+            .get(self.pointer)
+            .ok_or(ASEError::InputDataParseError)?;
+        self.advance(1);
+        Ok(byte)
+    }
+
+    /// Skips a value of any type, nested at most `depth` levels deep.
+    fn skip_value(&mut self, depth: usize) -> Result<(), ASEError> {
+        let depth = depth.checked_sub(1).ok_or(ASEError::InputDataParseError)?;
+        self.skip_whitespace();
+        match self.bytes.get(self.pointer) {
+            Some(b'"') => self.string().map(|_| ()),
+            Some(b'[') => {
+                self.advance(1);
+                if self.consume(b']') {
+                    return Ok(());
+                }
+                loop {
+                    self.skip_value(depth)?;
+                    if !self.consume(b',') {
+                        return self.expect(b']');
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.advance(1);
+                if self.consume(b'}') {
+                    return Ok(());
+                }
+                loop {
+                    self.string()?;
+                    self.expect(b':')?;
+                    self.skip_value(depth)?;
+                    if !self.consume(b',') {
+                        return self.expect(b'}');
+                    }
+                }
+            }
+            Some(_) => {
+                // numbers and literals
+                let start = self.pointer;
+                while matches!(
+                    self.bytes.get(self.pointer),
+                    Some(b'a'..=b'z' | b'0'..=b'9' | b'-' | b'+' | b'.' | b'E')
+                ) {
+                    self.advance(1);
+                }
+                if self.pointer == start {
+                    return Err(ASEError::InputDataParseError);
+                }
+                Ok(())
+            }
+            None => Err(ASEError::InputDataParseError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_ase, test_util::named, ColorValue};
+
+    use super::*;
+
+    fn metadata() -> PaletteMetadata {
+        PaletteMetadata {
+            title: Some("Aurora".to_owned()),
+            author: Some("Jane \"JD\" Doe".to_owned()),
+            description: Some("Greens and blues\n\tof the night sky 🌌".to_owned()),
+            license: Some("CC-BY-4.0".to_owned()),
+            tags: vec!["green".to_owned(), "blue".to_owned()],
+            created: Some("2024-01-01T00:00:00Z".to_owned()),
+            modified: None,
+        }
+    }
+
+    fn palette() -> Ase {
+        Ase::new(
+            vec![Group::new(
+                "group name".to_owned(),
+                vec![named("light grey", ColorValue::Gray(0.5))],
+            )],
+            vec![named("name", ColorValue::Gray(0.5))],
+        )
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ase-metadata-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn it_encodes_json() {
+        let metadata = metadata();
+        assert_eq!(
+            metadata.to_json(),
+            "{\"title\":\"Aurora\",\"author\":\"Jane \\\"JD\\\" Doe\",\"description\":\"Greens and blues\\n\\tof the night sky 🌌\",\"license\":\"CC-BY-4.0\",\"tags\":[\"green\",\"blue\"],\"created\":\"2024-01-01T00:00:00Z\"}"
+        );
+        assert_eq!(
+            PaletteMetadata::from_json(&metadata.to_json()).unwrap(),
+            metadata
+        );
+        assert_eq!(PaletteMetadata::default().to_json(), "{}");
+    }
+
+    #[test]
+    fn it_decodes_json() {
+        let json = r#" {
+            "title": "Aé🌌\/",
+            "version": [1, 2.5e3, {"nested": true}, null],
+            "author": null,
+            "tags": []
+        } "#;
+        assert_eq!(
+            PaletteMetadata::from_json(json).unwrap(),
+            PaletteMetadata {
+                title: Some("Aé🌌/".to_owned()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn it_returns_error_on_invalid_json() {
+        for json in [
+            "",
+            "[]",
+            "{",
+            "{\"title\": 1}",
+            "{\"tags\": \"green\"}",
+            "{\"title\": \"unterminated}",
+            "{} trailing",
+            &format!("{{\"nested\": {}}}", "[".repeat(100_000)),
+        ] {
+            assert!(
+                matches!(
+                    PaletteMetadata::from_json(json),
+                    Err(ASEError::InputDataParseError)
+                ),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_writes_and_reads_sidecar() {
+        let path = temp_path("sidecar.ase");
+        write_ase_with_metadata(&path, &palette(), &metadata()).unwrap();
+        assert!(metadata_path(&path).exists());
+
+        let (ase, read_metadata) = read_ase_with_metadata(&path).unwrap();
+        assert_eq!(ase, palette());
+        assert_eq!(read_metadata, Some(metadata()));
+    }
+
+    #[test]
+    fn it_reads_without_sidecar() {
+        let path = temp_path("no_sidecar.ase");
+        let ase = palette();
+        fs::write(&path, create_ase(ase.groups, ase.colors)).unwrap();
+
+        let (ase, metadata) = read_ase_with_metadata(&path).unwrap();
+        assert_eq!(ase, palette());
+        assert_eq!(metadata, None);
+    }
+
+    #[test]
+    fn it_embeds_metadata_group() {
+        let mut ase = palette();
+        ase.set_metadata(&PaletteMetadata::default());
+        // replaces the previous metadata
+        ase.set_metadata(&metadata());
+        assert_eq!(ase.groups.len(), 2);
+
+        let (groups, colors) = read_ase(&*create_ase(ase.groups, ase.colors)).unwrap();
+        let mut ase = Ase::new(groups, colors);
+        assert!(ase.groups[0].name.starts_with(METADATA_GROUP_PREFIX));
+
+        assert_eq!(ase.take_metadata().unwrap(), Some(metadata()));
+        assert_eq!(ase, palette());
+    }
+
+    #[test]
+    fn it_takes_no_metadata() {
+        let mut ase = palette();
+        assert_eq!(ase.take_metadata().unwrap(), None);
+        assert_eq!(ase, palette());
+    }
+
+    #[test]
+    fn it_keeps_invalid_metadata_group() {
+        let mut ase = palette();
+        ase.groups
+            .push(Group::new(format!("{METADATA_GROUP_PREFIX}{{"), vec![]));
+        let expected = ase.clone();
+        assert!(ase.take_metadata().is_err());
+        assert_eq!(ase, expected);
+    }
+}
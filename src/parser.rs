@@ -0,0 +1,251 @@
+use std::{io, mem};
+
+use crate::{
+    error::{ASEError, ConformationError},
+    events::AseReader,
+    reader::HEADER_SIZE,
+    ReadEvent,
+};
+
+/// Parses an .ase file from chunks of data, as they become available.
+///
+/// Unlike [`read_ase`](crate::read_ase), running out of data is not an error; incomplete
+/// blocks are kept until the remaining bytes are fed to the parser. Files are read the
+/// same way as [`read_ase`](crate::read_ase) reads them.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, AseParser, ColorBlock, ColorType, ColorValue, ReadEvent};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let source = create_ase(vec![], vec![color.clone()]);
+///
+/// let mut parser = AseParser::new();
+/// let mut events = Vec::new();
+/// for chunk in source.chunks(5) {
+///     events.extend(parser.feed(chunk).unwrap());
+/// }
+/// events.extend(parser.finish().unwrap());
+/// assert_eq!(events, vec![ReadEvent::Color(color)]);
+/// ```
+#[derive(Debug, Default)]
+pub struct AseParser {
+    /// Bytes fed to the parser, which have not been parsed yet.
+    buf: Vec<u8>,
+    /// State of the reader, once the header has been read.
+    reader: Option<AseReader<()>>,
+    /// Whether all blocks declared in the header have been parsed.
+    finished: bool,
+}
+
+impl AseParser {
+    /// Creates a parser expecting the start of an .ase file.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the given bytes, returning the events of all blocks completed by them.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the ASE file is invalid.
+    /// The parser should not be used after an error.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<ReadEvent>, ASEError> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        self.buf.extend_from_slice(bytes);
+
+        let mut reader = match self.reader.take() {
+            Some(reader) => reader,
+            None if self.buf.len() < HEADER_SIZE => return Ok(Vec::new()),
+            None => {
+                let reader = AseReader::new(self.buf.get(..HEADER_SIZE).unwrap_or_default())?
+                    .with_reader(());
+                self.buf.drain(..HEADER_SIZE);
+                reader
+            }
+        };
+
+        let mut events = Vec::new();
+        let mut consumed = 0;
+        let result = loop {
+            let data = self.buf.get(consumed..).unwrap_or_default();
+            // incomplete blocks are parsed again, once more data has been fed, so they are
+            // only parsed once their declared length is available
+            if data.len() < block_size(data) {
+                break Ok(());
+            }
+            let mut attempt = reader.clone().with_reader(data);
+            match attempt.read_block() {
+                Ok(more) => {
+                    events.extend(attempt.take_pending());
+                    consumed = self.buf.len().saturating_sub(attempt.get_ref().len());
+                    reader = attempt.with_reader(());
+                    if !more {
+                        self.finished = true;
+                        break Ok(());
+                    }
+                }
+                Err(ASEError::Io(err)) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    break Ok(())
+                }
+                Err(ASEError::Invalid(ConformationError::Truncated { .. })) => break Ok(()),
+                Err(err) => break Err(attempt.locate(err)),
+            }
+        };
+        self.buf.drain(..consumed);
+        self.reader = Some(reader);
+        result.map(|()| events)
+    }
+
+    /// Returns whether all blocks declared in the header have been parsed.
+    ///
+    /// Bytes fed to a finished parser are ignored.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Finishes parsing, returning the events of the remaining blocks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is incomplete or invalid.
+    pub fn finish(mut self) -> Result<Vec<ReadEvent>, ASEError> {
+        let events = self.feed(&[])?;
+        let buf = mem::take(&mut self.buf);
+        match self.reader {
+            // report the incomplete block, like `read_ase` does
+            Some(reader) => reader
+                .with_reader(&*buf)
+                .collect::<Result<Vec<_>, _>>()
+                .map(|rest| events.into_iter().chain(rest).collect()),
+            None => AseReader::new(&*buf).map(|_| events),
+        }
+    }
+}
+
+/// Returns the number of bytes, which are at least needed to read the block at the start
+/// of `data`.
+///
+/// Only the length of colors and groups is read, other blocks are parsed right away, so
+/// errors are reported as soon as possible.
+fn block_size(data: &[u8]) -> usize {
+    match data {
+        [0x00, 0x01, a, b, c, d, ..] | [0xc0, 0x01, a, b, c, d, ..] => {
+            usize::try_from(u32::from_be_bytes([*a, *b, *c, *d]))
+                .unwrap_or(usize::MAX)
+                .saturating_add(6)
+        }
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        create_ase, events::collect_events as collect, read_ase, read_ase_with_options, ColorBlock,
+        ColorType, ColorValue, Group, ParseOptions,
+    };
+
+    /// Parses the input split at every position, comparing the result to `read_ase`.
+    fn assert_split_parse(input: &[u8]) {
+        let expected =
+            read_ase_with_options(input, &ParseOptions::DEFAULT).map_err(|err| err.to_string());
+        for split in 0..=input.len() {
+            let mut parser = AseParser::new();
+            let result = parser.feed(&input[..split]).and_then(|mut events| {
+                events.extend(parser.feed(&input[split..])?);
+                events.extend(parser.finish()?);
+                Ok(events)
+            });
+            assert_eq!(
+                result.map(collect).map_err(|err| err.to_string()),
+                expected,
+                "split at {split}"
+            );
+        }
+    }
+
+    fn fixtures(kind: &str) -> Vec<Vec<u8>> {
+        std::fs::read_dir(format!(
+            "{}/tests/fixtures/{kind}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .unwrap()
+        .map(|entry| std::fs::read(entry.unwrap().path()).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn it_parses_split_input() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![ColorBlock::new(
+                "light grey".to_owned(),
+                ColorValue::Gray(0.5),
+                ColorType::Normal,
+            )],
+        );
+        let color = ColorBlock::new(
+            "dark red".to_owned(),
+            ColorValue::Rgb(0.5, 0.3, 0.1),
+            ColorType::Spot,
+        );
+        assert_split_parse(&create_ase(vec![group], vec![color]));
+        assert_split_parse(&create_ase(vec![], vec![]));
+
+        for input in fixtures("lenient").into_iter().chain(fixtures("invalid")) {
+            assert_split_parse(&input);
+        }
+    }
+
+    #[test]
+    fn it_parses_single_bytes() {
+        for input in fixtures("lenient") {
+            let mut parser = AseParser::new();
+            let mut events = Vec::new();
+            for byte in &input {
+                events.extend(parser.feed(std::slice::from_ref(byte)).unwrap());
+            }
+            events.extend(parser.finish().unwrap());
+            assert_eq!(collect(events), read_ase(&*input).unwrap());
+        }
+    }
+
+    #[test]
+    fn it_parses_large_blocks_from_single_bytes() {
+        // incomplete blocks must not be parsed for every fed byte
+        let colors = (0..2000)
+            .map(|i| ColorBlock::new(i.to_string(), ColorValue::Gray(0.5), ColorType::Normal))
+            .collect();
+        let input = create_ase(vec![Group::new("group".to_owned(), colors)], vec![]);
+
+        let mut parser = AseParser::new();
+        let mut events = Vec::new();
+        for byte in &input {
+            events.extend(parser.feed(std::slice::from_ref(byte)).unwrap());
+        }
+        events.extend(parser.finish().unwrap());
+        assert_eq!(collect(events), read_ase(&*input).unwrap());
+    }
+
+    #[test]
+    fn it_returns_errors_before_finishing() {
+        let mut parser = AseParser::new();
+        assert!(parser.feed(b"ASE").unwrap().is_empty());
+        assert!(matches!(
+            parser.feed(b"X\0\x01\0\0\0\0\0\0"),
+            Err(ASEError::Invalid(crate::ConformationError::FileSignature))
+        ));
+
+        let mut parser = AseParser::new();
+        assert!(parser.feed(b"ASEF\0\x01\0\0\0\0\0\x01").unwrap().is_empty());
+        assert!(matches!(
+            parser.feed(&[0xff, 0xff]),
+            Err(ASEError::Block { offset: 12, index: 0, ref error }) if matches!(**error, ASEError::BlockTypeError)
+        ));
+
+        assert!(AseParser::new().finish().is_err());
+    }
+}
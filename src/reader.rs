@@ -0,0 +1,1256 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::{
+    error::{ASEError, ConformationError, Limit},
+    types::{self, AseHeader, BlockType, GrayPolarity, NameMatch, RgbRange},
+    wire,
+};
+
+/// Size of the file signature, version and block count.
+pub(crate) const HEADER_SIZE: usize = 12;
+
+/// The number of zero bytes after the last block, which are ignored as padding.
+pub(crate) const MAX_TRAILING_ZEROS: u64 = 4;
+
+/// The number of zero bytes between blocks, which are skipped as padding, see
+/// [`ParseOptions::skip_block_padding`].
+pub(crate) const MAX_BLOCK_PADDING: u64 = 64;
+
+/// Options for reading .ase files, see [`read_ase_with_options`](crate::read_ase_with_options).
+///
+/// Each option tolerates a way, in which files written by some applications do not follow
+/// the format. [`ParseOptions::STRICT`] rejects all of them, [`ParseOptions::LENIENT`]
+/// recovers as much of a file as possible. The default is the behavior of
+/// [`read_ase`](crate::read_ase).
+///
+/// Groups, whose colors follow as separate blocks, are the layout written by Adobe
+/// applications and always read, see [`GroupLayout::Flat`](crate::GroupLayout::Flat).
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::ParseOptions;
+/// let options = ParseOptions {
+///     close_unterminated_groups: false,
+///     ..Default::default()
+/// };
+/// # assert!(options.skip_group_end_length);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether zero bytes following `GroupEnd` blocks are skipped.
+    ///
+    /// These are left by applications, which write the length of `GroupEnd` blocks,
+    /// like for any other block, see [`WriteOptions::group_end_length`](crate::WriteOptions::group_end_length).
+    pub skip_group_end_length: bool,
+    /// Whether runs of zero bytes between blocks are skipped, instead of failing.
+    ///
+    /// Some applications pad blocks to 4- or 8-byte boundaries. Up to 64 zero bytes
+    /// are skipped wherever a block is expected, they are reported as
+    /// [`ParseWarning::BlockPadding`](crate::ParseWarning::BlockPadding).
+    pub skip_block_padding: bool,
+    /// Whether a group, whose colors follow it as separate blocks, is closed at the end
+    /// of the file, if it is missing its `GroupEnd` block.
+    ///
+    /// The group is only closed after the blocks declared in the header have been read.
+    /// A file ending in place of a declared block is truncated, groups are closed there
+    /// only when recovering the nesting of groups, see [`ParseOptions::recover_group_nesting`].
+    pub close_unterminated_groups: bool,
+    /// Whether misplaced group blocks are ignored, instead of failing.
+    ///
+    /// Groups are closed by the start of another group, any block following a group
+    /// containing its colors and the end of the file. `GroupEnd` blocks outside of
+    /// groups, like a second `GroupEnd` block written for a group, are skipped and
+    /// reported as [`ParseWarning::UnmatchedGroupEnd`](crate::ParseWarning::UnmatchedGroupEnd).
+    pub recover_group_nesting: bool,
+    /// Whether groups starting within a group, whose colors follow it as separate blocks,
+    /// are read as separate groups, instead of failing.
+    ///
+    /// Each nested group is returned after the group containing it, which keeps the
+    /// colors on either side of the nested group.
+    pub flatten_nested_groups: bool,
+    /// Whether group names missing their null terminator are read, instead of failing.
+    ///
+    /// The name ends where a color entry or the end of the block takes the place of the
+    /// terminator, otherwise it ends at its length.
+    ///
+    /// Set by [`ParseOptions::DEFAULT`], like these names are read by
+    /// [`Group::parse`](crate::Group::parse).
+    pub recover_name_terminator: bool,
+    /// Whether names, whose length does not count their null terminator, are read,
+    /// instead of failing.
+    ///
+    /// Some applications write the number of characters as the length of names. This is
+    /// only assumed, if the block cannot be read using the length otherwise.
+    ///
+    /// Set by [`ParseOptions::DEFAULT`], like these names are read by
+    /// [`ColorBlock::parse`](crate::ColorBlock::parse).
+    pub recover_name_length: bool,
+    /// Whether codes of color models are matched ignoring case and a null character in
+    /// place of the trailing space, instead of failing.
+    ///
+    /// Some applications write codes like `rgb ` or `LAB\0`. Recovered codes are reported
+    /// as [`ParseWarning::ColorModelCode`](crate::ParseWarning::ColorModelCode).
+    pub recover_color_model: bool,
+    /// Whether color entries, whose declared length is smaller than any color entry, are
+    /// read up to the end of their color type, instead of failing.
+    ///
+    /// Some applications write zero as the length of color entries. The length is derived
+    /// from the name and color model, which is reported as
+    /// [`ParseWarning::ColorEntrySize`](crate::ParseWarning::ColorEntrySize).
+    pub derive_color_entry_length: bool,
+    /// Whether blocks with an unknown type are skipped, instead of failing.
+    ///
+    /// These blocks are stepped over using their length, like blocks added by plugins.
+    pub skip_unknown_blocks: bool,
+    /// Whether blocks with an unknown type are returned as [`Block::Raw`](crate::Block::Raw)
+    /// by [`read_ase_blocks`](crate::read_ase_blocks), instead of failing.
+    ///
+    /// Other functions skip these blocks. Unknown blocks within groups are returned
+    /// before their group.
+    pub keep_unknown_blocks: bool,
+    /// Whether blocks are read until the end of the file, instead of reading the number
+    /// of blocks declared in the header.
+    ///
+    /// Some applications write a wrong count, like zero for any file.
+    pub ignore_block_count: bool,
+    /// Whether data after the last block fails, instead of being ignored.
+    ///
+    /// Up to four zero bytes, like padding to a 4-byte boundary, are always ignored. Any other data is reported as [`ParseWarning::TrailingData`](crate::ParseWarning::TrailingData)
+    /// when reading until the end of the file, otherwise it is not read.
+    pub reject_trailing_data: bool,
+    /// Whether data following the color type of a color entry fails, instead of being skipped.
+    ///
+    /// Some applications pad color entries or append their own data, which is covered by
+    /// the length of the block. Skipped data is reported as [`ParseWarning::ColorEntryLength`](crate::ParseWarning::ColorEntryLength).
+    pub reject_color_entry_data: bool,
+    /// Whether data in a group block following its colors fails, instead of being skipped.
+    ///
+    /// The colors of a group block end before its data at a block, which is not a color
+    /// entry, or a color entry, which cannot be read, unless it is skipped. Skipped data is
+    /// reported as [`ParseWarning::GroupData`](crate::ParseWarning::GroupData).
+    pub reject_group_data: bool,
+    /// Whether color entries within a group, which cannot be read, are skipped, instead
+    /// of ending the colors of the group.
+    ///
+    /// The entries are stepped over using their length, keeping the following colors.
+    pub skip_malformed_colors: bool,
+    /// Whether color entries within a group, which cannot be read, fail, instead of
+    /// ending the colors of the group, unless they are skipped.
+    pub reject_malformed_colors: bool,
+    /// Whether files with a major version other than `1` are read, instead of failing.
+    ///
+    /// Any minor version of `1` is always read, as these are structurally identical.
+    pub accept_any_version: bool,
+    /// How gray values are stored in the file, they are converted to the polarity of
+    /// [`ColorValue::Gray`](crate::ColorValue::Gray) while reading.
+    ///
+    /// Values are read as they are by default.
+    pub gray_polarity: GrayPolarity,
+    /// How RGB colors, whose components are stored in the range from `0` to `255`, are
+    /// detected, they are divided by `255` while reading.
+    ///
+    /// Values are read as they are by default. Divided colors are reported as
+    /// [`ParseWarning::RgbRange`](crate::ParseWarning::RgbRange).
+    pub rgb_range: RgbRange,
+    /// The byte order, in which the UTF-16 code units of names are read.
+    ///
+    /// Some applications write names as little-endian UTF-16, while the rest of the
+    /// file is big-endian. Names read as little-endian are reported as
+    /// [`ParseWarning::NameByteOrder`](crate::ParseWarning::NameByteOrder).
+    pub name_byte_order: NameByteOrder,
+    /// Whether invisible characters and trailing whitespace are removed from the names of
+    /// groups and colors, see [`Ase::sanitize_names`](crate::Ase::sanitize_names) for the
+    /// removed characters.
+    ///
+    /// Names are read as they are by default, so that files can be written again unchanged.
+    pub sanitize_names: bool,
+    /// How the names of groups are compared to merge groups with the same name, see
+    /// [`Ase::merge_duplicate_groups_by`](crate::Ase::merge_duplicate_groups_by).
+    ///
+    /// Groups are kept as they are by default. Merged groups are reported as
+    /// [`ParseWarning::MergedGroups`](crate::ParseWarning::MergedGroups).
+    pub merge_duplicate_groups: Option<NameMatch>,
+    /// Whether reading continues after a block, which cannot be read, at the next
+    /// plausible block boundary, instead of failing.
+    ///
+    /// The boundary is the identifier of a group or color block followed by a length,
+    /// which fits into the rest of the file, or a `GroupEnd` block. The skipped data is
+    /// reported as [`ParseWarning::CorruptBlock`](crate::ParseWarning::CorruptBlock), and
+    /// the blocks are read until the end of the file afterwards. As this can misread data
+    /// resembling a block, it is not set by any preset, but is meant for salvaging
+    /// damaged files.
+    pub resync_corrupt_blocks: bool,
+    /// The limits on the resources used for reading the file.
+    ///
+    /// The default limits are far above any palette, but keep crafted files from using
+    /// unbounded memory.
+    pub limits: Limits,
+}
+
+impl ParseOptions {
+    /// Rejects all files, which do not follow the format.
+    pub const STRICT: Self = Self {
+        skip_group_end_length: false,
+        skip_block_padding: false,
+        close_unterminated_groups: false,
+        recover_group_nesting: false,
+        flatten_nested_groups: false,
+        recover_name_terminator: false,
+        recover_name_length: false,
+        recover_color_model: false,
+        derive_color_entry_length: false,
+        skip_unknown_blocks: false,
+        keep_unknown_blocks: false,
+        ignore_block_count: false,
+        reject_trailing_data: true,
+        reject_color_entry_data: true,
+        reject_group_data: true,
+        skip_malformed_colors: false,
+        reject_malformed_colors: true,
+        accept_any_version: false,
+        gray_polarity: GrayPolarity::ZeroIsBlack,
+        rgb_range: RgbRange::AsStored,
+        name_byte_order: NameByteOrder::BigEndian,
+        sanitize_names: false,
+        merge_duplicate_groups: None,
+        resync_corrupt_blocks: false,
+        limits: Limits::DEFAULT,
+    };
+
+    /// Reads files like [`read_ase`](crate::read_ase).
+    pub const DEFAULT: Self = Self {
+        skip_group_end_length: true,
+        skip_block_padding: false,
+        close_unterminated_groups: true,
+        recover_group_nesting: false,
+        flatten_nested_groups: false,
+        recover_name_terminator: true,
+        recover_name_length: true,
+        recover_color_model: false,
+        derive_color_entry_length: false,
+        skip_unknown_blocks: false,
+        keep_unknown_blocks: false,
+        ignore_block_count: false,
+        reject_trailing_data: false,
+        reject_color_entry_data: false,
+        reject_group_data: false,
+        skip_malformed_colors: false,
+        reject_malformed_colors: false,
+        accept_any_version: false,
+        gray_polarity: GrayPolarity::ZeroIsBlack,
+        rgb_range: RgbRange::AsStored,
+        name_byte_order: NameByteOrder::BigEndian,
+        sanitize_names: false,
+        merge_duplicate_groups: None,
+        resync_corrupt_blocks: false,
+        limits: Limits::DEFAULT,
+    };
+
+    /// Recovers as much of a file as possible.
+    pub const LENIENT: Self = Self {
+        skip_group_end_length: true,
+        skip_block_padding: true,
+        close_unterminated_groups: true,
+        recover_group_nesting: true,
+        flatten_nested_groups: true,
+        recover_name_terminator: true,
+        recover_name_length: true,
+        recover_color_model: true,
+        derive_color_entry_length: true,
+        skip_unknown_blocks: true,
+        keep_unknown_blocks: false,
+        ignore_block_count: true,
+        reject_trailing_data: false,
+        reject_color_entry_data: false,
+        reject_group_data: false,
+        skip_malformed_colors: true,
+        reject_malformed_colors: false,
+        accept_any_version: true,
+        gray_polarity: GrayPolarity::ZeroIsBlack,
+        rgb_range: RgbRange::AsStored,
+        name_byte_order: NameByteOrder::Detect,
+        sanitize_names: false,
+        merge_duplicate_groups: None,
+        resync_corrupt_blocks: false,
+        limits: Limits::DEFAULT,
+    };
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The byte order of the UTF-16 code units of names, see [`ParseOptions::name_byte_order`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameByteOrder {
+    /// Names are big-endian, like the format requires.
+    #[default]
+    BigEndian,
+    /// Names are little-endian.
+    LittleEndian,
+    /// Names are read as little-endian, if they look like it.
+    ///
+    /// This is assumed for names, whose code units all have a zero low byte, but not a
+    /// zero high byte, and which are mostly ASCII, once their bytes are swapped. Names of
+    /// a single character are always read as big-endian.
+    Detect,
+}
+
+impl NameByteOrder {
+    /// Returns whether the big-endian code units of a name are read as little-endian.
+    pub(crate) fn is_little_endian(self, units: &[u8]) -> bool {
+        match self {
+            NameByteOrder::BigEndian => false,
+            NameByteOrder::LittleEndian => true,
+            NameByteOrder::Detect => wire::looks_like_utf16_le(units),
+        }
+    }
+
+    /// Decodes the code units of a name in this byte order, returning whether they were
+    /// read as little-endian.
+    pub(crate) fn decode_into(self, units: &[u8], name: &mut String) -> Result<bool, ASEError> {
+        if self.is_little_endian(units) {
+            wire::decode_utf16_le_units_into(units, name)?;
+            Ok(true)
+        } else {
+            wire::decode_utf16_units_into(units, name)?;
+            Ok(false)
+        }
+    }
+}
+
+/// Limits on the resources used for reading a file, see [`ParseOptions::limits`].
+///
+/// Files exceeding a limit fail with [`ASEError::LimitExceeded`], naming the exceeded
+/// limit.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, read_ase_with_options, ASEError, ColorBlock, ColorType, ColorValue, Limit, Limits, ParseOptions};
+/// let options = ParseOptions {
+///     limits: Limits {
+///         max_name_length: 8,
+///         ..Limits::DEFAULT
+///     },
+///     ..ParseOptions::DEFAULT
+/// };
+/// let color = ColorBlock::new("very long name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let err = read_ase_with_options(&*create_ase(vec![], vec![color]), &options).unwrap_err();
+/// assert!(matches!(
+///     err.root_cause(),
+///     ASEError::LimitExceeded { limit: Limit::NameLength, maximum: 8 }
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of blocks, both declared in the header and read.
+    ///
+    /// Colors contained in group blocks are not counted as blocks.
+    pub max_blocks: u32,
+    /// The maximum length of the names of groups and colors, in UTF-16 code units.
+    pub max_name_length: usize,
+    /// The maximum number of colors in a group.
+    pub max_group_colors: usize,
+    /// The maximum number of bytes of the file, which are decoded, counted from the start
+    /// of the file up to the end of the last block.
+    pub max_decoded_bytes: u64,
+}
+
+impl Limits {
+    /// Limits far above the size of any palette.
+    pub const DEFAULT: Self = Self {
+        max_blocks: 1_000_000,
+        max_name_length: 4096,
+        max_group_colors: 100_000,
+        max_decoded_bytes: 256 * 1024 * 1024,
+    };
+
+    /// Does not limit files, apart from the limits of the format.
+    pub const NONE: Self = Self {
+        max_blocks: u32::MAX,
+        max_name_length: usize::MAX,
+        max_group_colors: usize::MAX,
+        max_decoded_bytes: u64::MAX,
+    };
+
+    /// Fails with [`ASEError::LimitExceeded`], if `value` exceeds the `maximum` of `limit`.
+    pub(crate) fn check(limit: Limit, value: u64, maximum: u64) -> Result<(), ASEError> {
+        if value > maximum {
+            return Err(ASEError::LimitExceeded { limit, maximum });
+        }
+        Ok(())
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A single block, exactly as it is framed in the file.
+#[derive(Debug)]
+pub(crate) struct Frame {
+    /// Type of the block.
+    pub(crate) block_type: BlockType,
+    /// Offset of the block identifier from the start of the file.
+    pub(crate) offset: u64,
+    /// Number of zero bytes skipped directly before the block identifier.
+    ///
+    /// These are left over by `GroupEnd` blocks, which are written with a length field,
+    /// unless they are `block_padding`.
+    pub(crate) padding: u64,
+    /// Whether the skipped zero bytes are padding between blocks, see
+    /// [`ParseOptions::skip_block_padding`].
+    pub(crate) block_padding: bool,
+    /// The length declared by the block, zero for `GroupEnd` blocks.
+    pub(crate) length: u32,
+    /// The block data, without identifier and length.
+    pub(crate) data: Vec<u8>,
+}
+
+/// Reads the blocks of an ASE file one at a time.
+///
+/// This only handles the framing of blocks, interpreting their content is up to the caller.
+#[derive(Debug, Clone)]
+pub(crate) struct BlockReader<T> {
+    reader: T,
+    /// Current position in the file.
+    offset: u64,
+    /// The version stored in the header.
+    pub(crate) version: u32,
+    /// The number of blocks declared in the header.
+    pub(crate) block_count: u32,
+    blocks_to_read: u32,
+    /// Whether blocks with unknown identifiers are returned, instead of failing.
+    allow_raw: bool,
+    /// Whether zero bytes after group-end blocks are skipped.
+    pub(crate) skip_padding: bool,
+    /// Whether runs of zero bytes between blocks are skipped.
+    pub(crate) skip_block_padding: bool,
+    /// Whether color entries, whose declared length is too small, are read by their structure.
+    pub(crate) derive_color_entry_length: bool,
+    /// Whether codes of color models are matched loosely, while deriving lengths.
+    recover_color_model: bool,
+    /// Whether blocks are read until the end of the file, regardless of the declared count.
+    pub(crate) read_to_end: bool,
+    /// Number of blocks read in addition to the declared blocks.
+    excess_blocks: u32,
+    // allow skipping of empty blocks when a group-end block has a size field
+    skipped: u8,
+    safe_to_skip: bool,
+    /// Offset of the block, which is currently read.
+    block_offset: u64,
+    /// Number of blocks, which were started to be read.
+    blocks_started: u32,
+    /// Number of blocks expected in addition to the declared blocks.
+    additional_blocks: u32,
+    /// Offset of the last frame, which was returned.
+    last_frame: Option<u64>,
+    /// The limits on the number of blocks and decoded bytes.
+    limits: Limits,
+    /// Data read ahead while resynchronizing, which is read before the reader, see
+    /// [`Self::resync`].
+    pending: io::Cursor<Vec<u8>>,
+    /// Whether blocks were skipped by [`Self::resync`], after which the declared number of
+    /// blocks is not checked.
+    resynced: bool,
+}
+
+impl<T> BlockReader<T> {
+    /// Continues reading the blocks from another reader, keeping the current state.
+    pub(crate) fn with_reader<U>(self, reader: U) -> BlockReader<U> {
+        BlockReader {
+            reader,
+            offset: self.offset,
+            version: self.version,
+            block_count: self.block_count,
+            blocks_to_read: self.blocks_to_read,
+            allow_raw: self.allow_raw,
+            skip_padding: self.skip_padding,
+            skip_block_padding: self.skip_block_padding,
+            derive_color_entry_length: self.derive_color_entry_length,
+            recover_color_model: self.recover_color_model,
+            read_to_end: self.read_to_end,
+            excess_blocks: self.excess_blocks,
+            skipped: self.skipped,
+            safe_to_skip: self.safe_to_skip,
+            block_offset: self.block_offset,
+            blocks_started: self.blocks_started,
+            additional_blocks: self.additional_blocks,
+            last_frame: self.last_frame,
+            limits: self.limits,
+            pending: self.pending,
+            resynced: self.resynced,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub(crate) fn get_ref(&self) -> &T {
+        &self.reader
+    }
+
+    /// Adds the offset and index of the block, which is currently read, to the error.
+    ///
+    /// Errors, which occurred while reading the header, are returned unchanged.
+    pub(crate) fn locate(&self, error: ASEError) -> ASEError {
+        match self.blocks_started.checked_sub(1) {
+            Some(index) => ASEError::Block {
+                offset: self.block_offset,
+                index,
+                error: Box::new(error),
+            },
+            None => error,
+        }
+    }
+}
+
+impl<T: Read> BlockReader<T> {
+    /// Reads and validates the file header.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either a read to the given data fails,
+    /// or the file signature or version are invalid.
+    pub(crate) fn new(reader: T, allow_raw: bool) -> Result<Self, ASEError> {
+        Self::open(reader, allow_raw, false)
+    }
+
+    /// Reads and validates the file header, reading the blocks as set by the options.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either a read to the given data fails,
+    /// or the file signature or version are invalid.
+    pub(crate) fn with_options(reader: T, options: &ParseOptions) -> Result<Self, ASEError> {
+        let mut block_reader = Self::open(
+            reader,
+            options.skip_unknown_blocks || options.keep_unknown_blocks,
+            options.accept_any_version,
+        )?;
+        block_reader.configure(options);
+        if !block_reader.read_to_end {
+            Limits::check(
+                Limit::Blocks,
+                block_reader.block_count.into(),
+                options.limits.max_blocks.into(),
+            )?;
+        }
+        Ok(block_reader)
+    }
+
+    /// Reads the blocks starting at `offset` in the file, as set by the options, without
+    /// reading a header.
+    ///
+    /// As no number of blocks is declared, the blocks are read until the end of the reader.
+    pub(crate) fn at(reader: T, offset: u64, options: &ParseOptions) -> Self {
+        let mut block_reader = Self::start(
+            reader,
+            offset,
+            types::FILE_VERSION,
+            0,
+            options.skip_unknown_blocks || options.keep_unknown_blocks,
+        );
+        block_reader.configure(options);
+        block_reader.read_to_end = true;
+        block_reader
+    }
+
+    /// Reads the header using [`read_header`], returning a reader for the blocks.
+    fn open(mut reader: T, allow_raw: bool, any_version: bool) -> Result<Self, ASEError> {
+        let header = read_header(&mut reader, any_version)?;
+        Ok(Self::start(
+            reader,
+            HEADER_SIZE as u64,
+            u32::from(header.version_major) << 16 | u32::from(header.version_minor),
+            header.block_count,
+            allow_raw,
+        ))
+    }
+
+    /// Returns a reader for the blocks starting at `offset`, reading `block_count` blocks.
+    fn start(reader: T, offset: u64, version: u32, block_count: u32, allow_raw: bool) -> Self {
+        Self {
+            reader,
+            offset,
+            version,
+            block_count,
+            blocks_to_read: block_count,
+            allow_raw,
+            skip_padding: true,
+            skip_block_padding: false,
+            derive_color_entry_length: false,
+            recover_color_model: false,
+            read_to_end: false,
+            excess_blocks: 0,
+            skipped: 0,
+            safe_to_skip: false,
+            block_offset: 0,
+            blocks_started: 0,
+            additional_blocks: 0,
+            last_frame: None,
+            limits: Limits::NONE,
+            pending: io::Cursor::new(Vec::new()),
+            resynced: false,
+        }
+    }
+
+    /// Reads the blocks as set by the options.
+    fn configure(&mut self, options: &ParseOptions) {
+        self.skip_padding = options.skip_group_end_length;
+        self.skip_block_padding = options.skip_block_padding;
+        self.derive_color_entry_length = options.derive_color_entry_length;
+        self.recover_color_model = options.recover_color_model;
+        self.read_to_end = options.ignore_block_count;
+        self.limits = options.limits;
+    }
+
+    /// Reads the next block, returns `None` once all expected blocks have been read.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails or
+    /// the block has an unknown type, while those are not allowed.
+    pub(crate) fn next_block(&mut self) -> Result<Option<Frame>, ASEError> {
+        let Some(mut frame) = self.next_frame()? else {
+            return Ok(None);
+        };
+        self.read_data(&mut frame)?;
+        Ok(Some(frame))
+    }
+
+    /// Reads the identifier of the next block, returns `None` once all expected blocks
+    /// have been read.
+    ///
+    /// The data of the returned frame is empty, until it is read using [`Self::read_data`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails or
+    /// the block has an unknown type, while those are not allowed.
+    pub(crate) fn next_frame(&mut self) -> Result<Option<Frame>, ASEError> {
+        let mut buf_u16 = [0; 2];
+        let mut padding: u64 = 0;
+        let mut block_padding = false;
+
+        if self.blocks_to_read > 0 || self.read_to_end {
+            self.block_offset = self.offset;
+            self.blocks_started = self.blocks_started.saturating_add(1);
+        }
+        loop {
+            if self.read_to_end {
+                // padding at the end of the file is trailing data
+                let skipped = if block_padding { padding } else { 0 };
+                match self.read_identifier(&mut buf_u16)? {
+                    0 if block_padding => {
+                        self.read_trailing_data(self.offset.saturating_sub(skipped), true)?;
+                        return Ok(None);
+                    }
+                    0 => {
+                        self.blocks_started = self.blocks_started.saturating_sub(1);
+                        return Ok(None);
+                    }
+                    2 => {}
+                    _ if self.blocks_to_read == 0 => {
+                        let only_zeros = buf_u16[0] == 0;
+                        let start = self.offset.saturating_sub(1).saturating_sub(skipped);
+                        self.read_trailing_data(start, only_zeros)?;
+                        return Ok(None);
+                    }
+                    _ => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                }
+            } else if self.blocks_to_read == 0 {
+                return Ok(None);
+            } else {
+                self.read_exact(&mut buf_u16)?;
+            }
+
+            if buf_u16 == [0, 0] {
+                // only skip the length of a group end, if we haven't skipped two already
+                if self.skipped < 2 && self.safe_to_skip && self.skip_padding {
+                    self.skipped = self.skipped.saturating_add(1);
+                    padding = padding.saturating_add(2);
+                    continue;
+                }
+                if self.skip_block_padding && padding < MAX_BLOCK_PADDING {
+                    block_padding = true;
+                    padding = padding.saturating_add(2);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        self.block_offset = self.offset.saturating_sub(2);
+        let block_type = BlockType::from(u16::from_be_bytes(buf_u16));
+        // after the declared blocks, anything but a block is trailing data
+        if self.read_to_end && self.blocks_to_read == 0 {
+            if buf_u16 == [0, 0] {
+                let skipped = if block_padding { padding } else { 0 };
+                self.read_trailing_data(self.block_offset.saturating_sub(skipped), true)?;
+                return Ok(None);
+            }
+            if !self.allow_raw && matches!(block_type, BlockType::Raw(_)) {
+                self.read_trailing_data(self.block_offset, false)?;
+                return Ok(None);
+            }
+        }
+        if !self.allow_raw && matches!(block_type, BlockType::Raw(_)) {
+            return Err(ASEError::BlockTypeError);
+        }
+
+        // every block consumes at least its identifier, so callers looping over the
+        // frames always make progress
+        let offset = self.offset.saturating_sub(2);
+        if self.last_frame.map_or(false, |last| offset <= last) {
+            return Err(ASEError::Invalid(ConformationError::NoProgress));
+        }
+        self.last_frame = Some(offset);
+        Limits::check(
+            Limit::Blocks,
+            self.blocks_started.into(),
+            self.limits.max_blocks.into(),
+        )?;
+
+        Ok(Some(Frame {
+            block_type,
+            offset,
+            padding,
+            block_padding,
+            length: 0,
+            data: Vec::new(),
+        }))
+    }
+
+    /// Reads the length and data of the block, whose identifier was read by [`Self::next_frame`].
+    ///
+    /// The data is read incrementally, so a declared length is only allocated, if the
+    /// data is actually present.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails, or the data
+    /// ends before the declared length.
+    pub(crate) fn read_data(&mut self, frame: &mut Frame) -> Result<(), ASEError> {
+        let result = self.read_length(frame).and_then(|length| {
+            // reuse the allocation of the frame, as callers may pass in a scratch buffer
+            frame.data.clear();
+            if self.derive_color_entry_length
+                && frame.block_type == BlockType::ColorEntry
+                && length < types::MIN_ENTRY_LENGTH
+            {
+                let length = self.read_color_entry(&mut frame.data)?;
+                return self.complete_block(length, frame.data.len());
+            }
+            let read = self
+                .source()
+                .take(length.into())
+                .read_to_end(&mut frame.data)?;
+            self.complete_block(length, read)
+        });
+        self.check_trailing_block(frame, result)
+    }
+
+    /// Reads the data of a color entry up to the end of its color type, returning the
+    /// length of the entry, which is derived from its name and color model.
+    ///
+    /// The data ends before the returned length, if the reader ends.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails, or the color
+    /// model is unknown.
+    fn read_color_entry(&mut self, data: &mut Vec<u8>) -> Result<u32, ASEError> {
+        // the name is stored as its length, counting code units, followed by the code units
+        let mut length = 2;
+        if self.fill(data, length)? {
+            let name_length = wire::get_u16_be(data)?;
+            length = usize::from(name_length).saturating_mul(2).saturating_add(2);
+            let model = length;
+            length = length.saturating_add(4);
+            if self.fill(data, length)? {
+                let code = data.get(model..).unwrap_or_default();
+                let values = types::values_length(code, self.recover_color_model)
+                    .ok_or(ASEError::ColorFormat)?;
+                // the values are followed by the color type
+                length = length.saturating_add(values).saturating_add(2);
+                self.fill(data, length)?;
+            }
+        }
+        Ok(u32::try_from(length).unwrap_or(u32::MAX))
+    }
+
+    /// Reads into `data`, until it holds `length` bytes, returning whether it does.
+    fn fill(&mut self, data: &mut Vec<u8>, length: usize) -> Result<bool, ASEError> {
+        let missing = length.saturating_sub(data.len());
+        self.source().take(missing as u64).read_to_end(data)?;
+        Ok(data.len() >= length)
+    }
+
+    /// Reads the length of the block, zero for `GroupEnd` blocks.
+    fn read_length(&mut self, frame: &mut Frame) -> Result<u32, ASEError> {
+        // block length for GroupEnd blocks should always be zero, the `skipped`
+        // variable above is intended to help us avoid the issue where the size
+        // is specified.
+        let block_length = if frame.block_type == BlockType::GroupEnd {
+            self.safe_to_skip = true;
+            self.skipped = 0;
+            0
+        } else {
+            let mut buf_u32 = [0; 4];
+            self.read_exact(&mut buf_u32)?;
+            self.safe_to_skip = false;
+            u32::from_be_bytes(buf_u32)
+        };
+        frame.length = block_length;
+        Limits::check(
+            Limit::DecodedBytes,
+            self.offset.saturating_add(block_length.into()),
+            self.limits.max_decoded_bytes,
+        )?;
+        Ok(block_length)
+    }
+
+    /// Counts the block, after `read` bytes of its data have been read.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the data ends before the declared `length`.
+    fn complete_block(&mut self, length: u32, read: usize) -> Result<(), ASEError> {
+        let read = read as u64;
+        self.offset = self.offset.saturating_add(read);
+        if read < u64::from(length) {
+            return Err(ASEError::Invalid(ConformationError::Truncated {
+                missing: u64::from(length).saturating_sub(read),
+            }));
+        }
+
+        if self.blocks_to_read == 0 {
+            self.excess_blocks = self.excess_blocks.saturating_add(1);
+        }
+        self.blocks_to_read = self.blocks_to_read.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Treats an incomplete block after the declared blocks as trailing data, when reading
+    /// until the end of the file.
+    fn check_trailing_block<R>(
+        &mut self,
+        frame: &Frame,
+        result: Result<R, ASEError>,
+    ) -> Result<R, ASEError> {
+        let trailing = self.read_to_end && self.blocks_to_read == 0;
+        let incomplete = match &result {
+            Err(ASEError::Io(err)) => err.kind() == io::ErrorKind::UnexpectedEof,
+            Err(ASEError::Invalid(ConformationError::Truncated { .. })) => true,
+            _ => false,
+        };
+        if !(trailing && incomplete) {
+            return result;
+        }
+        // the block is not zero padding, so this always fails
+        self.read_trailing_data(frame.offset, false)?;
+        result
+    }
+
+    /// Returns the number of blocks read, if it differs from the declared number of blocks,
+    /// unless blocks were skipped by [`Self::resync`].
+    pub(crate) fn count_mismatch(&self) -> Option<u32> {
+        (!self.resynced && (self.blocks_to_read > 0 || self.excess_blocks > 0)).then(|| {
+            self.block_count
+                .saturating_sub(self.blocks_to_read)
+                .saturating_add(self.excess_blocks)
+        })
+    }
+
+    /// Returns whether the major version of the file is supported.
+    pub(crate) fn is_supported_version(&self) -> bool {
+        is_supported_version(self.version)
+    }
+
+    /// Returns the number of bytes read so far.
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the offset of the block, which is currently read.
+    pub(crate) fn block_offset(&self) -> u64 {
+        self.block_offset
+    }
+
+    /// Reads the remaining data after the blocks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails, or the data
+    /// is not zero padding.
+    pub(crate) fn finish(&mut self) -> Result<(), ASEError> {
+        self.block_offset = self.offset;
+        self.blocks_started = self.blocks_started.saturating_add(1);
+        self.read_trailing_data(self.offset, true)
+    }
+
+    /// Reads the remaining data starting at `start`, which is only zero padding, if
+    /// `only_zeros` is set and the remaining bytes are zero.
+    ///
+    /// The trailing data is treated like a block, which is not counted.
+    fn read_trailing_data(&mut self, start: u64, mut only_zeros: bool) -> Result<(), ASEError> {
+        self.block_offset = start;
+        let mut buf = [0; 256];
+        loop {
+            let read = self.read_some(&mut buf)?;
+            let data = buf.get(..read).unwrap_or_default();
+            only_zeros &= data.iter().all(|byte| *byte == 0);
+            if read < buf.len() {
+                break;
+            }
+        }
+
+        let length = self.offset.saturating_sub(start);
+        if only_zeros && length <= MAX_TRAILING_ZEROS {
+            self.blocks_started = self.blocks_started.saturating_sub(1);
+            return Ok(());
+        }
+        Err(ASEError::Invalid(ConformationError::TrailingData {
+            length,
+        }))
+    }
+
+    /// Expect one more block than declared in the header.
+    ///
+    /// Groups, which already contain their colors, are not counted with their `GroupEnd` block.
+    pub(crate) fn expect_additional_block(&mut self) {
+        // each group is followed by a single `GroupEnd` block, so the number of blocks
+        // cannot grow beyond the number of blocks read
+        if self.additional_blocks < self.blocks_started {
+            self.additional_blocks = self.additional_blocks.saturating_add(1);
+            self.blocks_to_read = self.blocks_to_read.saturating_add(1);
+        }
+    }
+
+    /// Returns the number of blocks, which are still expected.
+    pub(crate) fn remaining_blocks(&self) -> u32 {
+        self.blocks_to_read
+    }
+
+    /// Returns whether the error is the end of the file in place of the last expected block,
+    /// such as the `GroupEnd` block of a group.
+    pub(crate) fn is_missing_last_block(&self, error: &ASEError) -> bool {
+        matches!(error, ASEError::Io(err) if err.kind() == std::io::ErrorKind::UnexpectedEof)
+            && self.remaining_blocks() == 1
+            && self.offset() == self.block_offset()
+    }
+
+    /// Reverts [`Self::expect_additional_block`], for groups missing their `GroupEnd` block.
+    pub(crate) fn forget_additional_block(&mut self) {
+        self.additional_blocks = self.additional_blocks.saturating_sub(1);
+        self.blocks_to_read = self.blocks_to_read.saturating_sub(1);
+    }
+
+    /// Skips forward from a corrupt block to the next plausible block boundary, returning
+    /// the offset, at which reading continues, see [`ParseOptions::resync_corrupt_blocks`].
+    ///
+    /// `consumed` is the data read last, which is searched together with the rest of the
+    /// reader. If no boundary is found, reading continues at the end of the data.
+    /// Afterwards the blocks are read until the end of the file, as the number of declared
+    /// blocks no longer applies.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a read to the given data fails.
+    pub(crate) fn resync(&mut self, consumed: &[u8]) -> Result<u64, ASEError> {
+        let start = self.offset.saturating_sub(consumed.len() as u64);
+        let mut data = consumed.to_vec();
+        let remaining = self.limits.max_decoded_bytes.saturating_sub(self.offset);
+        self.source().take(remaining).read_to_end(&mut data)?;
+
+        let boundary = (0..data.len())
+            .find(|position| data.get(*position..).map_or(false, is_block_boundary))
+            .unwrap_or(data.len());
+        data.drain(..boundary);
+        self.pending = io::Cursor::new(data);
+        self.offset = start.saturating_add(boundary as u64);
+        self.block_offset = self.offset;
+        self.read_to_end = true;
+        self.skipped = 0;
+        self.safe_to_skip = false;
+        self.resynced = true;
+        Ok(self.offset)
+    }
+
+    /// Returns the data read ahead by [`Self::resync`], followed by the reader.
+    fn source(&mut self) -> io::Chain<&mut io::Cursor<Vec<u8>>, &mut T> {
+        (&mut self.pending).chain(&mut self.reader)
+    }
+
+    /// Reads the identifier of the next block, returns the number of bytes read, which is
+    /// less than two at the end of the reader.
+    fn read_identifier(&mut self, buf: &mut [u8; 2]) -> Result<usize, ASEError> {
+        self.read_some(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ASEError> {
+        if self.read_some(buf)? < buf.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "failed to fill whole buffer",
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Fills the buffer, unless the end of the reader is reached, returning the number
+    /// of bytes read.
+    ///
+    /// Unlike [`Read::read_exact`], the bytes read before the end are counted.
+    fn read_some(&mut self, buf: &mut [u8]) -> Result<usize, ASEError> {
+        let mut filled = 0;
+        while let Some(rest) = buf.get_mut(filled..).filter(|rest| !rest.is_empty()) {
+            match self.source().read(rest) {
+                Ok(0) => break,
+                Ok(read) => {
+                    filled = filled.saturating_add(read);
+                    self.offset = self.offset.saturating_add(read as u64);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(filled)
+    }
+}
+
+impl<'a> BlockReader<&'a [u8]> {
+    /// Reads the length of the block, whose identifier was read by [`Self::next_frame`],
+    /// returning its data borrowed from the slice.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the data ends before the declared length.
+    pub(crate) fn read_data_borrowed(&mut self, frame: &mut Frame) -> Result<&'a [u8], ASEError> {
+        let result = self.read_length(frame).and_then(|length| {
+            let bytes: &'a [u8] = self.reader;
+            let (data, rest) = bytes.split_at(bytes.len().min(length as usize));
+            self.reader = rest;
+            self.complete_block(length, data.len()).map(|()| data)
+        });
+        self.check_trailing_block(frame, result)
+    }
+}
+
+/// Returns whether a block plausibly starts at the start of `bytes`, which extend to the
+/// end of the file.
+///
+/// Group and color blocks have to declare a length, which fits into the file and holds
+/// their name, group ends have to be followed by another block or the end of the file.
+fn is_block_boundary(bytes: &[u8]) -> bool {
+    match wire::get_u16_be(bytes).map(BlockType::from) {
+        Ok(BlockType::GroupEnd) => {
+            let rest = bytes.get(2..).unwrap_or_default();
+            rest.is_empty()
+                || wire::get_u16_be(rest).map_or(false, |identifier| {
+                    identifier == BlockType::GroupEnd.into() || is_framed_block(rest)
+                })
+        }
+        Ok(_) => is_framed_block(bytes),
+        Err(_) => false,
+    }
+}
+
+/// Returns whether a group or color block with a plausible length starts at the start
+/// of `bytes`, see [`is_block_boundary`].
+fn is_framed_block(bytes: &[u8]) -> bool {
+    let minimum = match wire::get_u16_be(bytes).map(BlockType::from) {
+        Ok(BlockType::ColorEntry) => types::MIN_ENTRY_LENGTH,
+        // the length and terminator of the name
+        Ok(BlockType::GroupStart) => 4,
+        _ => return false,
+    };
+    let data = bytes.get(6..).unwrap_or_default();
+    let (Ok(length), Ok(name_length)) = (
+        wire::get_u32_be(bytes.get(2..).unwrap_or_default()),
+        wire::get_u16_be(data),
+    ) else {
+        return false;
+    };
+    let name = u64::from(name_length).saturating_mul(2).saturating_add(2);
+    name_length > 0
+        && length >= minimum
+        && u64::from(length) <= data.len() as u64
+        && name <= u64::from(length)
+}
+
+/// Reads and validates the file header.
+///
+/// Files with an unsupported major version are only read, if `any_version` is set.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the file signature or version are invalid.
+pub(crate) fn read_header<T: Read>(
+    reader: &mut T,
+    any_version: bool,
+) -> Result<AseHeader, ASEError> {
+    let mut buf_u32 = [0; 4];
+
+    // read magic bytes
+    reader.read_exact(&mut buf_u32)?;
+    if &buf_u32 != types::FILE_SIGNATURE {
+        return Err(ASEError::Invalid(ConformationError::FileSignature));
+    }
+
+    // read version, any minor version of 1 is read
+    reader.read_exact(&mut buf_u32)?;
+    let version = u32::from_be_bytes(buf_u32);
+    if !any_version && !is_supported_version(version) {
+        return Err(ASEError::Invalid(ConformationError::FileVersion));
+    }
+
+    reader.read_exact(&mut buf_u32)?;
+    Ok(AseHeader {
+        version_major: (version >> 16) as u16,
+        version_minor: version as u16,
+        block_count: u32::from_be_bytes(buf_u32),
+    })
+}
+
+/// Returns whether the major version is supported.
+fn is_supported_version(version: u32) -> bool {
+    version >> 16 == types::FILE_VERSION >> 16
+}
+
+/// Checks whether a block declares more data than the stream contains, without reading
+/// the block data.
+///
+/// Other errors are left to be reported by parsing, the stream is rewound to its initial
+/// position afterwards.
+///
+/// # Errors
+///
+/// This function will return an error if either seeking or a read to the given data fails,
+/// or a block extends past the end of the stream.
+pub(crate) fn check_truncation<T: Read + Seek>(reader: &mut T) -> Result<(), ASEError> {
+    let start = reader.stream_position()?;
+    let end = reader.seek(SeekFrom::End(0))?;
+    let result = scan_block_lengths(reader, start, end);
+    reader.seek(SeekFrom::Start(start))?;
+    result
+}
+
+/// Seeks over the blocks of the file starting at `start`, comparing their declared lengths
+/// to the `end` of the stream.
+fn scan_block_lengths<T: Read + Seek>(
+    reader: &mut T,
+    start: u64,
+    end: u64,
+) -> Result<(), ASEError> {
+    let read_u16 = |reader: &mut T| -> Result<u16, ASEError> {
+        let mut buf = [0; 2];
+        reader.read_exact(&mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    };
+    let read_u32 = |reader: &mut T| -> Result<u32, ASEError> {
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    };
+
+    // the signature and version are validated while parsing
+    let mut position = start.saturating_add(12);
+    if position > end {
+        return Ok(());
+    }
+    reader.seek(SeekFrom::Start(start.saturating_add(8)))?;
+    let mut blocks_to_read = read_u32(reader)?;
+
+    // mirrors the skipping of zero bytes after `GroupEnd` blocks in `BlockReader`
+    let mut skipped: u8 = 2;
+    while blocks_to_read > 0 {
+        // incomplete identifiers and lengths are reported by parsing
+        if end.saturating_sub(position) < 2 {
+            return Ok(());
+        }
+        let block_type = read_u16(reader)?;
+        position = position.saturating_add(2);
+
+        if block_type == 0 && skipped < 2 {
+            skipped = skipped.saturating_add(1);
+            continue;
+        }
+        match BlockType::from(block_type) {
+            BlockType::GroupEnd => {
+                skipped = 0;
+                blocks_to_read = blocks_to_read.saturating_sub(1);
+                continue;
+            }
+            BlockType::GroupStart | BlockType::ColorEntry => skipped = 2,
+            BlockType::Raw(_) => return Ok(()),
+        }
+
+        if end.saturating_sub(position) < 4 {
+            return Ok(());
+        }
+        let length = read_u32(reader)?;
+        position = position.saturating_add(4);
+        let block_end = position.saturating_add(length.into());
+        if block_end > end {
+            return Err(ASEError::Invalid(ConformationError::Truncated {
+                missing: block_end.saturating_sub(end),
+            }));
+        }
+
+        // groups containing their colors are followed by an uncounted `GroupEnd` block
+        if BlockType::from(block_type) == BlockType::GroupStart && length >= 2 {
+            let name_length = u32::from(read_u16(reader)?);
+            if length > name_length.saturating_mul(2).saturating_add(2) {
+                blocks_to_read = blocks_to_read.saturating_add(1);
+            }
+        }
+
+        reader.seek(SeekFrom::Start(block_end))?;
+        position = block_end;
+        blocks_to_read = blocks_to_read.saturating_sub(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_does_not_allocate_declared_length() {
+        let mut input = b"ASEF\0\x01\0\0\0\0\0\x01".to_vec();
+        input.extend([0, 1, 0xff, 0xff, 0xff, 0xff, 0, 1, 0, 0]);
+
+        let mut blocks = BlockReader::new(&*input, false).unwrap();
+        let mut frame = blocks.next_frame().unwrap().unwrap();
+        assert!(matches!(
+            blocks.read_data(&mut frame),
+            Err(ASEError::Invalid(ConformationError::Truncated { missing })) if missing == u64::from(u32::MAX) - 4
+        ));
+        assert_eq!(frame.length, u32::MAX);
+        assert!(frame.data.capacity() < 1024);
+        assert_eq!(blocks.offset(), input.len() as u64);
+    }
+
+    #[test]
+    fn it_expects_group_ends_only_for_read_blocks() {
+        let mut input = b"ASEF\0\x01\0\0\0\0\0\x01".to_vec();
+        input.extend([0xc0, 1, 0, 0, 0, 0]);
+
+        let mut blocks = BlockReader::new(&*input, false).unwrap();
+        blocks.expect_additional_block();
+        assert_eq!(blocks.remaining_blocks(), 1);
+
+        blocks.next_frame().unwrap().unwrap();
+        blocks.expect_additional_block();
+        blocks.expect_additional_block();
+        // the started block is still counted, until its data has been read
+        assert_eq!(blocks.remaining_blocks(), 2);
+    }
+}
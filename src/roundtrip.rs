@@ -0,0 +1,194 @@
+use std::fmt::Display;
+
+use crate::{create_ase, read_ase, ASEError, ColorBlock, ColorValue, Group};
+
+/// Indicates that a document did not survive being written and read again.
+#[derive(Debug)]
+pub enum RoundtripError {
+    /// The written document could not be read.
+    Read(ASEError),
+    /// A block was read differently than it was written.
+    Mismatch {
+        /// Describes the first mismatching block, e.g. `group 0 ("Accents"), color 1 ("Blue")`.
+        block: String,
+        /// The written value.
+        expected: String,
+        /// The read value.
+        found: String,
+    },
+}
+
+impl Display for RoundtripError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoundtripError::Read(err) => write!(f, "Failed to read written document: {err}"),
+            RoundtripError::Mismatch {
+                block,
+                expected,
+                found,
+            } => write!(f, "Mismatch in {block}: expected {expected}, found {found}"),
+        }
+    }
+}
+
+impl std::error::Error for RoundtripError {}
+
+/// Writes the document using [`create_ase`], reads it again using [`read_ase`] and
+/// compares the result to the original document.
+///
+/// Names and color types have to be equal, color components have to be bitwise equal.
+/// The only exception is the L* component of [`ColorValue::Lab`], which is scaled
+/// to `[0, 1]` when writing and back to `[0, 100]` when reading.
+///
+/// # Errors
+///
+/// Returns a [`RoundtripError`] describing the first mismatching block.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{assert_roundtrip, ColorBlock, ColorType, ColorValue};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// assert!(assert_roundtrip(&[], &[color]).is_ok());
+/// ```
+pub fn assert_roundtrip(groups: &[Group], colors: &[ColorBlock]) -> Result<(), RoundtripError> {
+    let (read_groups, read_colors) =
+        read_ase(&*create_ase(groups.to_vec(), colors.to_vec())).map_err(RoundtripError::Read)?;
+
+    compare_len("groups", groups.len(), read_groups.len())?;
+    for (index, (group, read_group)) in groups.iter().zip(&read_groups).enumerate() {
+        let block = format!("group {index} ({:?})", group.name);
+        compare(&block, "name", &group.name, &read_group.name)?;
+        compare_len(&block, group.blocks.len(), read_group.blocks.len())?;
+        for (index, (color, read_color)) in group.blocks.iter().zip(&read_group.blocks).enumerate()
+        {
+            compare_color(
+                &format!("{block}, color {index} ({:?})", color.name),
+                color,
+                read_color,
+            )?;
+        }
+    }
+
+    compare_len("colors", colors.len(), read_colors.len())?;
+    for (index, (color, read_color)) in colors.iter().zip(&read_colors).enumerate() {
+        compare_color(
+            &format!("color {index} ({:?})", color.name),
+            color,
+            read_color,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn compare_color(block: &str, color: &ColorBlock, read: &ColorBlock) -> Result<(), RoundtripError> {
+    compare(block, "name", &color.name, &read.name)?;
+    compare(block, "color type", &color.color_type, &read.color_type)?;
+
+    let expected = match color.color {
+        // L* is stored in [0, 1]
+        ColorValue::Lab(l, a, b) => ColorValue::Lab(l / 100.0 * 100.0, a, b),
+        ref color => color.clone(),
+    };
+    let bits = |color: &ColorValue| -> Vec<u32> {
+        match *color {
+            ColorValue::Cmyk(c, m, y, k) => {
+                vec![c.to_bits(), m.to_bits(), y.to_bits(), k.to_bits()]
+            }
+            ColorValue::Rgb(r, g, b) => vec![r.to_bits(), g.to_bits(), b.to_bits()],
+            ColorValue::Lab(l, a, b) => vec![l.to_bits(), a.to_bits(), b.to_bits()],
+            ColorValue::Gray(value) => vec![value.to_bits()],
+        }
+    };
+    if expected.get_type() != read.color.get_type() || bits(&expected) != bits(&read.color) {
+        return Err(mismatch(block, "color", &expected, &read.color));
+    }
+    Ok(())
+}
+
+fn compare<T: PartialEq + std::fmt::Debug>(
+    block: &str,
+    field: &str,
+    expected: &T,
+    found: &T,
+) -> Result<(), RoundtripError> {
+    if expected != found {
+        return Err(mismatch(block, field, expected, found));
+    }
+    Ok(())
+}
+
+fn compare_len(block: &str, expected: usize, found: usize) -> Result<(), RoundtripError> {
+    compare(block, "number of blocks", &expected, &found)
+}
+
+fn mismatch<T: std::fmt::Debug>(
+    block: &str,
+    field: &str,
+    expected: &T,
+    found: &T,
+) -> RoundtripError {
+    RoundtripError::Mismatch {
+        block: block.to_owned(),
+        expected: format!("{field} {expected:?}"),
+        found: format!("{field} {found:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ColorType;
+
+    use super::*;
+
+    #[test]
+    fn it_accepts_lab_scaling() {
+        // regression test, L* was not scaled when writing
+        let color = ColorBlock::new(
+            "lab".to_owned(),
+            ColorValue::Lab(47.83, 56.29, -36.03),
+            ColorType::Spot,
+        );
+        assert!(assert_roundtrip(&[], &[color]).is_ok());
+    }
+
+    #[test]
+    fn it_accepts_non_ascii_names() {
+        // regression test, name lengths were counted in UTF-8 bytes
+        let group = Group::new(
+            "Grün".to_owned(),
+            vec![ColorBlock::new(
+                "赤".to_owned(),
+                ColorValue::Rgb(1.0, 0.0, 0.0),
+                ColorType::Global,
+            )],
+        );
+        let color = ColorBlock::new("🎨".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        assert!(assert_roundtrip(&[group], &[color]).is_ok());
+    }
+
+    #[test]
+    fn it_compares_bitwise() {
+        // NaN is never equal to itself, but its bits are preserved
+        let color = ColorBlock::new(
+            "nan".to_owned(),
+            ColorValue::Rgb(0.0, f32::NAN, 0.0),
+            ColorType::Normal,
+        );
+        assert!(assert_roundtrip(&[], &[color]).is_ok());
+    }
+
+    #[test]
+    fn it_names_mismatching_block() {
+        // `-0.0` is equal to `0.0`, but is not bitwise equal
+        let res = compare_color(
+            "color 0 (\"zero\")",
+            &ColorBlock::new("zero".to_owned(), ColorValue::Gray(-0.0), ColorType::Normal),
+            &ColorBlock::new("zero".to_owned(), ColorValue::Gray(0.0), ColorType::Normal),
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Mismatch in color 0 (\"zero\"): expected color Gray(-0.0), found color Gray(0.0)"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Helpers for writing tests against palettes.
+//!
+//! This module is only available with the `test-util` feature.
+
+use crate::{Ase, ColorBlock, ColorType, ColorValue, Group};
+
+/// Creates a [`ColorValue::Rgb`] from 8-bit channels.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::ColorValue;
+/// # use adobe_swatch_exchange::test_util::rgb8;
+/// assert_eq!(rgb8(255, 0, 51), ColorValue::Rgb(1.0, 0.0, 0.2));
+/// ```
+pub fn rgb8(r: u8, g: u8, b: u8) -> ColorValue {
+    ColorValue::Rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+}
+
+/// Creates a [`ColorBlock`] of type [`ColorType::Normal`] with the given name.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue};
+/// # use adobe_swatch_exchange::test_util::named;
+/// assert_eq!(
+///     named("name", ColorValue::Gray(0.5)),
+///     ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)
+/// );
+/// ```
+pub fn named(name: &str, value: ColorValue) -> ColorBlock {
+    ColorBlock::new(name.to_owned(), value, ColorType::Normal)
+}
+
+/// Returns `true` if both colors use the same color model, and all of their components
+/// differ by at most `tolerance`.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::ColorValue;
+/// # use adobe_swatch_exchange::test_util::approx_eq;
+/// assert!(approx_eq(&ColorValue::Gray(0.5), &ColorValue::Gray(0.501), 0.01));
+/// assert!(!approx_eq(&ColorValue::Gray(0.5), &ColorValue::Rgb(0.5, 0.5, 0.5), 0.01));
+/// ```
+pub fn approx_eq(a: &ColorValue, b: &ColorValue, tolerance: f32) -> bool {
+    let close = |a: f32, b: f32| (a - b).abs() <= tolerance;
+    match (a, b) {
+        (ColorValue::Cmyk(c1, m1, y1, k1), ColorValue::Cmyk(c2, m2, y2, k2)) => {
+            close(*c1, *c2) && close(*m1, *m2) && close(*y1, *y2) && close(*k1, *k2)
+        }
+        (ColorValue::Rgb(r1, g1, b1), ColorValue::Rgb(r2, g2, b2)) => {
+            close(*r1, *r2) && close(*g1, *g2) && close(*b1, *b2)
+        }
+        (ColorValue::Lab(l1, a1, b1), ColorValue::Lab(l2, a2, b2)) => {
+            close(*l1, *l2) && close(*a1, *a2) && close(*b1, *b2)
+        }
+        (ColorValue::Gray(v1), ColorValue::Gray(v2)) => close(*v1, *v2),
+        _ => false,
+    }
+}
+
+/// Asserts that both palettes are structurally equal, comparing colors using [`approx_eq`].
+///
+/// # Panics
+///
+/// Panics with a report listing every difference between the palettes.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{Ase, ColorValue};
+/// # use adobe_swatch_exchange::test_util::{assert_palette_eq, named};
+/// let a = Ase::new(vec![], vec![named("name", ColorValue::Gray(0.5))]);
+/// let b = Ase::new(vec![], vec![named("name", ColorValue::Gray(0.5001))]);
+/// assert_palette_eq(&a, &b, 0.001);
+/// ```
+#[track_caller]
+pub fn assert_palette_eq(a: &Ase, b: &Ase, tolerance: f32) {
+    let differences = diff(a, b, tolerance);
+    if !differences.is_empty() {
+        panic!(
+            "palettes differ (tolerance {tolerance}):\n  {}",
+            differences.join("\n  ")
+        );
+    }
+}
+
+/// Lists the differences between both palettes.
+fn diff(a: &Ase, b: &Ase, tolerance: f32) -> Vec<String> {
+    let mut differences = Vec::new();
+
+    if a.groups.len() != b.groups.len() {
+        differences.push(format!("groups: {} != {}", a.groups.len(), b.groups.len()));
+    }
+    for (index, (a, b)) in a.groups.iter().zip(&b.groups).enumerate() {
+        diff_group(&mut differences, index, a, b, tolerance);
+    }
+
+    if a.colors.len() != b.colors.len() {
+        differences.push(format!("colors: {} != {}", a.colors.len(), b.colors.len()));
+    }
+    for (index, (a, b)) in a.colors.iter().zip(&b.colors).enumerate() {
+        diff_color(&mut differences, &format!("color {index}"), a, b, tolerance);
+    }
+
+    differences
+}
+
+fn diff_group(differences: &mut Vec<String>, index: usize, a: &Group, b: &Group, tolerance: f32) {
+    let path = format!("group {index} ({:?})", a.name);
+    if a.name != b.name {
+        differences.push(format!("{path}: name {:?} != {:?}", a.name, b.name));
+    }
+    if a.blocks.len() != b.blocks.len() {
+        differences.push(format!(
+            "{path}: colors {} != {}",
+            a.blocks.len(),
+            b.blocks.len()
+        ));
+    }
+    for (index, (a, b)) in a.blocks.iter().zip(&b.blocks).enumerate() {
+        diff_color(
+            differences,
+            &format!("{path}, color {index}"),
+            a,
+            b,
+            tolerance,
+        );
+    }
+}
+
+fn diff_color(
+    differences: &mut Vec<String>,
+    path: &str,
+    a: &ColorBlock,
+    b: &ColorBlock,
+    tolerance: f32,
+) {
+    let path = format!("{path} ({:?})", a.name);
+    if a.name != b.name {
+        differences.push(format!("{path}: name {:?} != {:?}", a.name, b.name));
+    }
+    if a.color_type != b.color_type {
+        differences.push(format!(
+            "{path}: color type {:?} != {:?}",
+            a.color_type, b.color_type
+        ));
+    }
+    if !approx_eq(&a.color, &b.color, tolerance) {
+        differences.push(format!("{path}: color {:?} != {:?}", a.color, b.color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_differences() {
+        let a = Ase::new(
+            vec![Group::new(
+                "group".to_owned(),
+                vec![named("red", rgb8(255, 0, 0))],
+            )],
+            vec![named("grey", ColorValue::Gray(0.5))],
+        );
+        let mut b = a.clone();
+        b.groups[0].blocks[0].color = rgb8(254, 0, 0);
+        b.colors[0].name = "gray".to_owned();
+        b.colors.push(named("black", ColorValue::Gray(0.0)));
+
+        assert_eq!(
+            diff(&a, &b, 0.001),
+            vec![
+                "group 0 (\"group\"), color 0 (\"red\"): color Rgb(1.0, 0.0, 0.0) != Rgb(0.99607843, 0.0, 0.0)",
+                "colors: 1 != 2",
+                "color 0 (\"grey\"): name \"grey\" != \"gray\"",
+            ]
+        );
+        assert!(diff(&a, &a, 0.0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "palettes differ")]
+    fn it_panics_on_different_palettes() {
+        assert_palette_eq(
+            &Ase::new(vec![], vec![named("name", ColorValue::Gray(0.5))]),
+            &Ase::new(vec![], vec![named("name", ColorValue::Gray(0.6))]),
+            0.01,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Instrumentation of the parser using `tracing`, enabled by the `tracing` feature.
+//!
+//! Without the feature, the macros expand to nothing, so neither their arguments are
+//! evaluated, nor is `tracing` required.
+
+/// Emits a debug event, like [`tracing::debug!`].
+///
+/// The macro has to be used as a statement.
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+/// Enters a debug span, like [`tracing::debug_span!`], which is bound to `$span` until
+/// the end of the enclosing block.
+///
+/// The macro has to be used as a statement.
+macro_rules! trace_span {
+    ($span:ident, $($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let $span = ::tracing::debug_span!($($arg)*).entered();
+    };
+}
+
+/// Records the value of a field of a span entered by [`trace_span!`].
+macro_rules! trace_record {
+    ($span:ident, $field:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        $span.record($field, $value);
+    };
+}
+
+pub(crate) use {trace_event, trace_record, trace_span};
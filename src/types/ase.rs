@@ -0,0 +1,523 @@
+// the counters are bounded by the number of colors in memory
+#![allow(clippy::arithmetic_side_effects)]
+
+use std::{collections::HashMap, mem};
+
+use crate::{
+    metadata,
+    writer::{self, WriteOptions},
+};
+
+use super::{ColorBlock, ColorType, ColorValue, Group, RgbRange};
+
+/// Invisible characters removed from names by [`Ase::sanitize_names`].
+const INVISIBLE_CHARACTERS: [char; 5] = [
+    // byte order mark, also known as zero width no-break space
+    '\u{FEFF}', // zero width space
+    '\u{200B}', // zero width non-joiner
+    '\u{200C}', // zero width joiner
+    '\u{200D}', // word joiner
+    '\u{2060}',
+];
+
+/// How the names of groups are compared by [`Ase::merge_duplicate_groups_by`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameMatch {
+    /// Names match if they are identical.
+    #[default]
+    Exact,
+    /// Names match if they are identical after removing invisible characters and trailing
+    /// whitespace, like [`Ase::sanitize_names`]. The name of the first group is kept.
+    Normalized,
+}
+
+/// An ASE document, consisting of groups and single colors.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Ase {
+    /// The named groups of colors
+    pub groups: Vec<Group>,
+    /// The colors, which are not part of any group
+    pub colors: Vec<ColorBlock>,
+}
+
+impl Ase {
+    /// Creates a new document from the given groups and single colors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::Ase;
+    /// # use adobe_swatch_exchange::ColorBlock;
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// # use adobe_swatch_exchange::ColorType;
+    /// let ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// # assert_eq!(ase.colors.len(), 1);
+    /// ```
+    pub fn new(groups: Vec<Group>, colors: Vec<ColorBlock>) -> Self {
+        Self { groups, colors }
+    }
+
+    /// Returns the number of bytes written by [`create_ase`](crate::create_ase) for this document.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{create_ase, Ase, ColorBlock, ColorType, ColorValue};
+    /// let ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// assert_eq!(ase.encoded_size(), create_ase(ase.groups, ase.colors).len());
+    /// ```
+    pub fn encoded_size(&self) -> usize {
+        writer::encoded_size(&self.groups, &self.colors, &WriteOptions::default())
+    }
+
+    /// Converts colors, which are shades of gray, into [`ColorValue::Gray`].
+    ///
+    /// RGB colors are converted if their channels differ by at most `tolerance`, using
+    /// their average as value. CMYK colors are converted if cyan, magenta and yellow are
+    /// at most `tolerance`, using `1.0 - black` as value. Gray values are interpreted the
+    /// same way as RGB channels, `0.0` being black and `1.0` white.
+    ///
+    /// Returns the number of converted colors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let mut ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Rgb(0.5, 0.5, 0.5), ColorType::Normal)],
+    /// );
+    /// let size = ase.encoded_size();
+    /// assert_eq!(ase.compact_grays(0.0), 1);
+    /// assert_eq!(ase.colors[0].color, ColorValue::Gray(0.5));
+    /// assert_eq!(ase.encoded_size(), size - 8);
+    /// ```
+    pub fn compact_grays(&mut self, tolerance: f32) -> usize {
+        let mut converted = 0;
+        for block in self.colors_mut() {
+            let gray = match block.color {
+                ColorValue::Rgb(r, g, b) if r.max(g).max(b) - r.min(g).min(b) <= tolerance => {
+                    (r + g + b) / 3.0
+                }
+                ColorValue::Cmyk(c, m, y, k)
+                    if c.abs() <= tolerance && m.abs() <= tolerance && y.abs() <= tolerance =>
+                {
+                    1.0 - k
+                }
+                _ => continue,
+            };
+            block.color = ColorValue::Gray(gray);
+            converted += 1;
+        }
+        converted
+    }
+
+    /// Divides the components of all RGB colors by `255`, if any of them is greater than
+    /// `1.0`, like files storing them in the range from `0` to `255`.
+    ///
+    /// Returns the number of divided colors, see [`RgbRange::PerFile`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let mut ase = Ase::new(
+    ///     vec![],
+    ///     vec![
+    ///         ColorBlock::new("rust".to_owned(), ColorValue::Rgb(255.0, 51.0, 0.0), ColorType::Normal),
+    ///         ColorBlock::new("ink".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal),
+    ///     ],
+    /// );
+    /// assert_eq!(ase.normalize_component_ranges(), 2);
+    /// assert_eq!(ase.colors[0].color, ColorValue::Rgb(1.0, 0.2, 0.0));
+    /// assert_eq!(ase.normalize_component_ranges(), 0);
+    /// ```
+    pub fn normalize_component_ranges(&mut self) -> usize {
+        RgbRange::PerFile.normalize(self.colors_mut().map(|block| &mut block.color))
+    }
+
+    /// Removes invisible characters and trailing whitespace from the names of all groups
+    /// and colors, so that names looking identical are equal.
+    ///
+    /// The removed characters are the byte order mark `U+FEFF`, the zero width space
+    /// `U+200B`, the zero width non-joiner `U+200C`, the zero width joiner `U+200D` and the
+    /// word joiner `U+2060`, wherever they appear, followed by any whitespace at the end
+    /// of the name. The group storing the metadata of the palette, see
+    /// [`Ase::set_metadata`], is kept as it is.
+    ///
+    /// Returns the number of changed names.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let mut ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("\u{FEFF}Sea\u{200B}weed ".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// assert_eq!(ase.sanitize_names(), 1);
+    /// assert_eq!(ase.colors[0].name, "Seaweed");
+    /// ```
+    pub fn sanitize_names(&mut self) -> usize {
+        sanitize_names(&mut self.groups, &mut self.colors)
+    }
+
+    /// Merges groups with the same name into the first one of them, appending the colors
+    /// in document order.
+    ///
+    /// Returns the number of groups merged into another one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue, Group};
+    /// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// let mut ase = Ase::new(
+    ///     vec![
+    ///         Group::new("Brand".to_owned(), vec![color.clone()]),
+    ///         Group::new("Brand".to_owned(), vec![color.clone()]),
+    ///     ],
+    ///     vec![],
+    /// );
+    /// assert_eq!(ase.merge_duplicate_groups(), 1);
+    /// assert_eq!(ase.groups, vec![Group::new("Brand".to_owned(), vec![color.clone(), color])]);
+    /// ```
+    pub fn merge_duplicate_groups(&mut self) -> usize {
+        self.merge_duplicate_groups_by(NameMatch::Exact)
+    }
+
+    /// Merges groups with matching names into the first one of them, like
+    /// [`Ase::merge_duplicate_groups`], comparing the names as given.
+    ///
+    /// Returns the number of groups merged into another one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue, Group, NameMatch};
+    /// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// let mut ase = Ase::new(
+    ///     vec![
+    ///         Group::new("Brand".to_owned(), vec![color.clone()]),
+    ///         Group::new("Brand\u{200B} ".to_owned(), vec![color.clone()]),
+    ///     ],
+    ///     vec![],
+    /// );
+    /// assert_eq!(ase.merge_duplicate_groups_by(NameMatch::Exact), 0);
+    /// assert_eq!(ase.merge_duplicate_groups_by(NameMatch::Normalized), 1);
+    /// assert_eq!(ase.groups, vec![Group::new("Brand".to_owned(), vec![color.clone(), color])]);
+    /// ```
+    pub fn merge_duplicate_groups_by(&mut self, names: NameMatch) -> usize {
+        let groups = self.groups.len();
+        merge_groups(&mut self.groups, names);
+        groups - self.groups.len()
+    }
+
+    /// Returns all colors, first those in groups, followed by the single colors.
+    pub(crate) fn colors(&self) -> impl Iterator<Item = &ColorBlock> {
+        self.groups
+            .iter()
+            .flat_map(|group| &group.blocks)
+            .chain(&self.colors)
+    }
+
+    /// Returns all colors mutably, first those in groups, followed by the single colors.
+    pub(crate) fn colors_mut(&mut self) -> impl Iterator<Item = &mut ColorBlock> {
+        self.groups
+            .iter_mut()
+            .flat_map(|group| group.blocks.iter_mut())
+            .chain(self.colors.iter_mut())
+    }
+
+    /// Sets the type of all colors, including those in groups.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let mut ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// ase.set_color_type_all(ColorType::Global);
+    /// # assert_eq!(ase.colors[0].color_type, ColorType::Global);
+    /// ```
+    pub fn set_color_type_all(&mut self, color_type: ColorType) {
+        self.colors_mut()
+            .for_each(|block| block.color_type = color_type.clone());
+    }
+
+    /// Changes the type of all colors of type `from` to `to`, including those in groups.
+    ///
+    /// Returns the number of changed colors.
+    pub fn set_color_type_where(&mut self, from: ColorType, to: ColorType) -> usize {
+        let mut changed = 0;
+        for block in self.colors_mut().filter(|block| block.color_type == from) {
+            block.color_type = to.clone();
+            changed += 1;
+        }
+        changed
+    }
+}
+
+/// Sanitizes the names of the groups and colors, see [`Ase::sanitize_names`].
+///
+/// Returns the number of changed names.
+pub(crate) fn sanitize_names(groups: &mut [Group], colors: &mut [ColorBlock]) -> usize {
+    let mut changed = 0;
+    for group in groups {
+        if metadata::is_metadata_group(group) {
+            continue;
+        }
+        changed += usize::from(sanitize_name(&mut group.name));
+        for block in &mut group.blocks {
+            changed += usize::from(sanitize_name(&mut block.name));
+        }
+    }
+    for block in colors {
+        changed += usize::from(sanitize_name(&mut block.name));
+    }
+    changed
+}
+
+/// Merges groups with matching names into the first one of them, see
+/// [`Ase::merge_duplicate_groups_by`].
+///
+/// Returns for each of the original groups the index of the group it is part of
+/// afterwards, and the number of colors preceding its colors in that group.
+pub(crate) fn merge_groups(groups: &mut Vec<Group>, names: NameMatch) -> Vec<(usize, usize)> {
+    let mut indices: HashMap<String, usize> = HashMap::with_capacity(groups.len());
+    let mut merged: Vec<Group> = Vec::with_capacity(groups.len());
+    let mut positions = Vec::with_capacity(groups.len());
+    for group in mem::take(groups) {
+        let mut name = group.name.clone();
+        if names == NameMatch::Normalized {
+            sanitize_name(&mut name);
+        }
+        let target = indices
+            .get(&name)
+            .and_then(|&index| Some((index, merged.get_mut(index)?)));
+        match target {
+            Some((index, target)) => {
+                positions.push((index, target.blocks.len()));
+                target.blocks.extend(group.blocks);
+            }
+            None => {
+                indices.insert(name, merged.len());
+                positions.push((merged.len(), 0));
+                merged.push(group);
+            }
+        }
+    }
+    *groups = merged;
+    positions
+}
+
+/// Removes invisible characters and trailing whitespace from the name, returns whether
+/// it changed.
+pub(crate) fn sanitize_name(name: &mut String) -> bool {
+    let length = name.len();
+    name.retain(|c| !INVISIBLE_CHARACTERS.contains(&c));
+    name.truncate(name.trim_end().len());
+    name.len() != length
+}
+
+impl From<(Vec<Group>, Vec<ColorBlock>)> for Ase {
+    fn from((groups, colors): (Vec<Group>, Vec<ColorBlock>)) -> Self {
+        Self::new(groups, colors)
+    }
+}
+
+impl From<Ase> for (Vec<Group>, Vec<ColorBlock>) {
+    fn from(value: Ase) -> Self {
+        (value.groups, value.colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_ase, read_ase, test_util::named, ColorValue};
+
+    use super::*;
+
+    fn palette() -> Ase {
+        let mut spot = named("spot", ColorValue::Gray(0.2));
+        spot.color_type = ColorType::Spot;
+        Ase::new(
+            vec![Group::new(
+                "group".to_owned(),
+                vec![named("light grey", ColorValue::Gray(0.8)), spot],
+            )],
+            vec![named("name", ColorValue::Gray(0.5))],
+        )
+    }
+
+    fn color_types(ase: &Ase) -> Vec<ColorType> {
+        ase.groups
+            .iter()
+            .flat_map(|group| &group.blocks)
+            .chain(&ase.colors)
+            .map(|block| block.color_type.clone())
+            .collect()
+    }
+
+    #[test]
+    fn it_sets_color_type_all() {
+        let mut ase = palette();
+        ase.set_color_type_all(ColorType::Global);
+        assert_eq!(color_types(&ase), vec![ColorType::Global; 3]);
+
+        // the type is stored in the last byte of each color block
+        let bytes = create_ase(ase.groups, ase.colors);
+        assert_eq!(bytes.last(), Some(&(ColorType::Global as u8)));
+        let (groups, colors) = read_ase(&*bytes).unwrap();
+        assert_eq!(
+            color_types(&Ase::new(groups, colors)),
+            vec![ColorType::Global; 3]
+        );
+    }
+
+    #[test]
+    fn it_sets_color_type_where() {
+        let mut ase = palette();
+        assert_eq!(
+            ase.set_color_type_where(ColorType::Normal, ColorType::Global),
+            2
+        );
+        assert_eq!(
+            color_types(&ase),
+            vec![ColorType::Global, ColorType::Spot, ColorType::Global]
+        );
+        assert_eq!(
+            ase.set_color_type_where(ColorType::Spot, ColorType::Normal),
+            1
+        );
+        assert_eq!(
+            ase.set_color_type_where(ColorType::Spot, ColorType::Normal),
+            0
+        );
+
+        let (groups, colors) = read_ase(&*create_ase(ase.groups, ase.colors)).unwrap();
+        assert_eq!(
+            color_types(&Ase::new(groups, colors)),
+            vec![ColorType::Global, ColorType::Normal, ColorType::Global]
+        );
+    }
+
+    #[test]
+    fn it_calculates_encoded_size() {
+        let mut ase = palette();
+        ase.groups.push(Group::new("empty".to_owned(), vec![]));
+        assert_eq!(
+            ase.encoded_size(),
+            create_ase(ase.groups.clone(), ase.colors.clone()).len()
+        );
+    }
+
+    #[test]
+    fn it_compacts_grays() {
+        let mut ase = Ase::new(
+            vec![Group::new(
+                "group".to_owned(),
+                vec![
+                    named("exact", ColorValue::Rgb(0.25, 0.25, 0.25)),
+                    named("near", ColorValue::Rgb(0.5, 0.501, 0.499)),
+                    named("outside", ColorValue::Rgb(0.5, 0.52, 0.5)),
+                ],
+            )],
+            vec![
+                named("black", ColorValue::Cmyk(0.0, 0.0, 0.0, 1.0)),
+                named("k only", ColorValue::Cmyk(0.0, 0.0, 0.0, 0.25)),
+                named("cyan", ColorValue::Cmyk(0.1, 0.0, 0.0, 0.25)),
+                named("lab", ColorValue::Lab(50.0, 0.0, 0.0)),
+                named("gray", ColorValue::Gray(0.5)),
+            ],
+        );
+        let size = ase.encoded_size();
+
+        assert_eq!(ase.compact_grays(0.01), 4);
+        assert_eq!(
+            ase,
+            Ase::new(
+                vec![Group::new(
+                    "group".to_owned(),
+                    vec![
+                        named("exact", ColorValue::Gray(0.25)),
+                        named("near", ColorValue::Gray(0.5)),
+                        named("outside", ColorValue::Rgb(0.5, 0.52, 0.5)),
+                    ],
+                )],
+                vec![
+                    named("black", ColorValue::Gray(0.0)),
+                    named("k only", ColorValue::Gray(0.75)),
+                    named("cyan", ColorValue::Cmyk(0.1, 0.0, 0.0, 0.25)),
+                    named("lab", ColorValue::Lab(50.0, 0.0, 0.0)),
+                    named("gray", ColorValue::Gray(0.5)),
+                ],
+            )
+        );
+        // 8 bytes for every RGB color, 12 for every CMYK color
+        assert_eq!(ase.encoded_size(), size - 2 * 8 - 2 * 12);
+        assert_eq!(ase.compact_grays(0.01), 0);
+    }
+
+    #[test]
+    fn it_merges_duplicate_groups() {
+        let names = |group: &Group| -> Vec<String> {
+            group
+                .blocks
+                .iter()
+                .map(|block| block.name.clone())
+                .collect()
+        };
+        // three groups named "Brand", one named "Brand\u{200B} " and one named "Other"
+        let bytes = include_bytes!("../../tests/fixtures/valid/duplicate_groups.ase");
+        let read = Ase::from(read_ase(&bytes[..]).unwrap());
+
+        let mut ase = read.clone();
+        assert_eq!(ase.merge_duplicate_groups(), 2);
+        assert_eq!(ase.groups.len(), 3);
+        assert_eq!(ase.groups[0].name, "Brand");
+        assert_eq!(names(&ase.groups[0]), ["a", "b", "c", "d"]);
+        assert_eq!(names(&ase.groups[1]), ["x"]);
+        assert_eq!(ase.groups[2].name, "Brand\u{200B} ");
+        assert_eq!(ase.colors, read.colors);
+        assert_eq!(ase.merge_duplicate_groups(), 0);
+
+        let mut ase = read;
+        assert_eq!(ase.merge_duplicate_groups_by(NameMatch::Normalized), 3);
+        assert_eq!(ase.groups.len(), 2);
+        assert_eq!(names(&ase.groups[0]), ["a", "b", "c", "d", "e"]);
+        assert_eq!(ase.groups[1].name, "Other");
+    }
+
+    #[test]
+    fn it_sanitizes_names() {
+        let mut ase = Ase::new(
+            vec![Group::new(
+                "\u{FEFF}Brand \u{200D}".to_owned(),
+                vec![named("Sea\u{200B}weed", ColorValue::Gray(0.5))],
+            )],
+            vec![
+                named("\u{FEFF}Sand", ColorValue::Gray(0.5)),
+                named("Stone", ColorValue::Gray(0.5)),
+                named("Rock\u{2060}\t", ColorValue::Gray(0.5)),
+            ],
+        );
+        ase.set_metadata(&crate::PaletteMetadata {
+            title: Some("Coast".to_owned()),
+            ..Default::default()
+        });
+
+        assert_eq!(ase.sanitize_names(), 4);
+        // the metadata is stored in the first group
+        assert_eq!(ase.groups[1].name, "Brand");
+        assert_eq!(ase.groups[1].blocks[0].name, "Seaweed");
+        let names = ase.colors.iter().map(|block| block.name.as_str());
+        assert_eq!(names.collect::<Vec<_>>(), ["Sand", "Stone", "Rock"]);
+        // the zero width space of the metadata group is kept
+        assert_eq!(ase.sanitize_names(), 0);
+        assert_eq!(
+            ase.take_metadata().unwrap().unwrap().title.unwrap(),
+            "Coast"
+        );
+    }
+}
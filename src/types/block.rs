@@ -0,0 +1,87 @@
+use crate::{
+    error::{ASEError, ConformationError},
+    wire,
+    writer::Writer,
+};
+
+use super::{BlockType, ColorBlock, Group};
+
+/// A group, a single color or an unknown block, in the order they are stored in a file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// A group of colors.
+    Group(Group),
+    /// A single color, which is not part of a group.
+    Color(ColorBlock),
+    /// A block with a type unknown to this crate, which is written back unchanged.
+    Raw(RawBlock),
+}
+
+/// A block with a type unknown to this crate, like blocks added by plugins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawBlock {
+    /// The type of the block.
+    pub type_id: u16,
+    /// The data of the block, without its type and length.
+    pub data: Vec<u8>,
+}
+
+impl RawBlock {
+    /// Write the block using the given [`Writer`], exactly as it was read.
+    pub(crate) fn write(&self, writer: &mut Writer) -> Result<(), ASEError> {
+        writer.buf.write_u16(self.type_id);
+        writer.write_with_length(|writer| {
+            writer.buf.write_slice(&self.data);
+            Ok(())
+        })
+    }
+}
+
+/// Splits the block of the given type at the start of `bytes` into its data, without its
+/// type and length, and the bytes following the block.
+///
+/// # Errors
+/// This function will return an [`ASEError::BlockTypeError`] if the block has another type,
+/// and an [`ASEError::Invalid`] if `bytes` ends before the length of the block.
+pub(crate) fn split_block(bytes: &[u8], block_type: BlockType) -> Result<(&[u8], &[u8]), ASEError> {
+    if wire::get_u16_be(bytes)? != u16::from(block_type) {
+        return Err(ASEError::BlockTypeError);
+    }
+    let length = wire::get_u32_be(bytes.get(2..).unwrap_or_default())?;
+    let rest = bytes.get(6..).unwrap_or_default();
+    match (rest.get(..length as usize), rest.get(length as usize..)) {
+        (Some(data), Some(rest)) => Ok((data, rest)),
+        _ => Err(ASEError::Invalid(ConformationError::Truncated {
+            missing: u64::from(length).saturating_sub(rest.len() as u64),
+        })),
+    }
+}
+
+/// Returns an error, if `rest` is not empty.
+pub(crate) fn reject_trailing(rest: &[u8]) -> Result<(), ASEError> {
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err(ASEError::Invalid(ConformationError::TrailingData {
+            length: rest.len() as u64,
+        }))
+    }
+}
+
+impl From<Group> for Block {
+    fn from(value: Group) -> Self {
+        Block::Group(value)
+    }
+}
+
+impl From<ColorBlock> for Block {
+    fn from(value: ColorBlock) -> Self {
+        Block::Color(value)
+    }
+}
+
+impl From<RawBlock> for Block {
+    fn from(value: RawBlock) -> Self {
+        Block::Raw(value)
+    }
+}
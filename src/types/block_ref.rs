@@ -0,0 +1,128 @@
+use crate::{error::ASEError, wire};
+
+use super::{
+    group::{ColorEntries, GroupName},
+    ColorBlock, ColorType, ColorValue, Group,
+};
+
+/// A single color borrowing its name from the data of an .ase file, see
+/// [`read_ase_borrowed`](crate::read_ase_borrowed).
+///
+/// The name is only decoded when it is requested, use [`ColorBlock::from`] to get an
+/// owned color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorBlockRef<'a> {
+    /// The UTF-16 code units of the name, up to its null terminator.
+    name: &'a [u8],
+    /// The specific color value of the block.
+    pub color: ColorValue,
+    /// The type of color
+    pub color_type: ColorType,
+}
+
+impl<'a> ColorBlockRef<'a> {
+    /// Parses a [`ColorBlockRef`] from bytes, like [`ColorBlock::parse`].
+    ///
+    /// # Errors
+    /// This function will return an error if parsing fails.
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, ASEError> {
+        let parts = ColorBlock::parse_parts(bytes, false)?;
+        wire::validate_utf16_units(parts.name)?;
+        Ok(Self {
+            name: parts.name,
+            color: parts.color,
+            color_type: parts.color_type,
+        })
+    }
+
+    /// Returns the name associated with the color.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{create_ase, read_ase_borrowed, ColorBlock, ColorType, ColorValue};
+    /// let color = ColorBlock::new("Grün".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// let source = create_ase(vec![], vec![color]);
+    /// let (_, colors) = read_ase_borrowed(&source).unwrap();
+    /// assert_eq!(colors[0].name(), "Grün");
+    /// assert_eq!(colors[0].raw_name(), &[0, 71, 0, 114, 0, 252, 0, 110]);
+    /// ```
+    pub fn name(&self) -> String {
+        self.name_chars().collect()
+    }
+
+    /// Returns the characters of the name, decoding them one at a time.
+    pub fn name_chars(&self) -> impl Iterator<Item = char> + 'a {
+        decode_name(self.name)
+    }
+
+    /// Returns the big-endian UTF-16 code units of the name, without its null terminator.
+    pub fn raw_name(&self) -> &'a [u8] {
+        self.name
+    }
+}
+
+impl From<ColorBlockRef<'_>> for ColorBlock {
+    fn from(value: ColorBlockRef<'_>) -> Self {
+        ColorBlock::new(value.name(), value.color, value.color_type)
+    }
+}
+
+/// A named group of colors borrowing its name from the data of an .ase file, see
+/// [`read_ase_borrowed`](crate::read_ase_borrowed).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupRef<'a> {
+    /// The UTF-16 code units of the name, up to its null terminator.
+    name: &'a [u8],
+    /// The color blocks of the group.
+    pub blocks: Vec<ColorBlockRef<'a>>,
+}
+
+impl<'a> GroupRef<'a> {
+    /// Parses a [`GroupRef`] from bytes, like [`Group::parse`].
+    ///
+    /// # Errors
+    /// This function will return an error if the name cannot be constructed.
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, ASEError> {
+        let GroupName {
+            units: name, end, ..
+        } = GroupName::parse(bytes)?;
+        wire::validate_utf16_units(name)?;
+        let mut blocks = Vec::new();
+        for entry in ColorEntries::new(bytes, end) {
+            let Ok(block) = ColorBlockRef::parse(entry?.data) else {
+                break;
+            };
+            blocks.push(block);
+        }
+        Ok(Self { name, blocks })
+    }
+
+    /// Returns the name of the group.
+    pub fn name(&self) -> String {
+        self.name_chars().collect()
+    }
+
+    /// Returns the characters of the name, decoding them one at a time.
+    pub fn name_chars(&self) -> impl Iterator<Item = char> + 'a {
+        decode_name(self.name)
+    }
+
+    /// Returns the big-endian UTF-16 code units of the name, without its null terminator.
+    pub fn raw_name(&self) -> &'a [u8] {
+        self.name
+    }
+}
+
+impl From<GroupRef<'_>> for Group {
+    fn from(value: GroupRef<'_>) -> Self {
+        Group::new(
+            value.name(),
+            value.blocks.into_iter().map(ColorBlock::from).collect(),
+        )
+    }
+}
+
+/// Decodes a name validated by [`wire::validate_utf16_units`].
+fn decode_name(name: &[u8]) -> impl Iterator<Item = char> + '_ {
+    wire::decode_utf16_units(name).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+}
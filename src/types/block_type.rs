@@ -1,25 +1,80 @@
 use crate::error::ASEError;
 
 /// Block identifier
-#[derive(Debug, Clone, PartialEq)]
-pub(crate) enum BlockType {
-    /// Indicates the start of a [`Group`]
-    GroupStart = 0xc001,
-    /// Indicates the end of a [`Group`]
-    GroupEnd = 0xc002,
-    /// Indicates the start of a [`ColorBlock`]
-    ColorEntry = 0x0001,
+///
+/// Every block in an ASE file starts with a two byte identifier, followed by the
+/// length of the block. Identifiers not defined by the format are preserved as
+/// [`BlockType::Raw`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockType {
+    /// Indicates the start of a [`Group`](crate::Group)
+    GroupStart,
+    /// Indicates the end of a [`Group`](crate::Group)
+    GroupEnd,
+    /// Indicates the start of a [`ColorBlock`](crate::ColorBlock)
+    ColorEntry,
+    /// An identifier unknown to this crate.
+    Raw(u16),
 }
 
-impl TryFrom<u16> for BlockType {
-    type Error = ASEError;
+impl BlockType {
+    /// Converts the identifier into a [`BlockType`], rejecting unknown identifiers.
+    ///
+    /// # Errors
+    /// Returns [`ASEError::BlockTypeError`] if the identifier would be a [`BlockType::Raw`].
+    pub(crate) fn parse(value: u16) -> Result<Self, ASEError> {
+        match Self::from(value) {
+            BlockType::Raw(_) => Err(ASEError::BlockTypeError),
+            block_type => Ok(block_type),
+        }
+    }
+}
+
+impl From<u16> for BlockType {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0001 => Self::ColorEntry,
+            0xc001 => Self::GroupStart,
+            0xc002 => Self::GroupEnd,
+            value => Self::Raw(value),
+        }
+    }
+}
 
-    fn try_from(value: u16) -> Result<Self, Self::Error> {
+impl From<BlockType> for u16 {
+    fn from(value: BlockType) -> Self {
         match value {
-            0x0001 => Ok(Self::ColorEntry),
-            0xc001 => Ok(Self::GroupStart),
-            0xc002 => Ok(Self::GroupEnd),
-            _ => Err(ASEError::BlockTypeError),
+            BlockType::ColorEntry => 0x0001,
+            BlockType::GroupStart => 0xc001,
+            BlockType::GroupEnd => 0xc002,
+            BlockType::Raw(value) => value,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_known_identifiers() {
+        for block_type in [
+            BlockType::ColorEntry,
+            BlockType::GroupStart,
+            BlockType::GroupEnd,
+        ] {
+            assert_eq!(BlockType::from(u16::from(block_type)), block_type);
+            assert_eq!(BlockType::parse(u16::from(block_type)).unwrap(), block_type);
+        }
+    }
+
+    #[test]
+    fn it_preserves_unknown_identifiers() {
+        assert_eq!(BlockType::from(0x0042), BlockType::Raw(0x0042));
+        assert_eq!(u16::from(BlockType::Raw(0x0042)), 0x0042);
+        assert!(matches!(
+            BlockType::parse(0x0042),
+            Err(ASEError::BlockTypeError)
+        ));
+    }
+}
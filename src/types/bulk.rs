@@ -0,0 +1,318 @@
+#![allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::{color_value::contrast_ratio, Ase, ColorBlock, ColorModel, ColorValue};
+
+/// Two colors, whose contrast is below the minimum, see [`Ase::contrast_audit`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContrastPair {
+    /// Index of the first color among all colors, first those in groups, followed by the
+    /// single colors.
+    pub first: usize,
+    /// Index of the second color, which is greater than that of the first color.
+    pub second: usize,
+    /// The WCAG contrast ratio between the colors, see [`ColorValue::contrast_ratio`].
+    pub ratio: f32,
+}
+
+impl Ase {
+    /// Converts all colors, including those in groups, to the given color model, see
+    /// [`ColorValue::converted`].
+    ///
+    /// Returns the number of converted colors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorModel, ColorType, ColorValue};
+    /// let mut ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// assert_eq!(ase.convert_all(ColorModel::Rgb), 1);
+    /// assert_eq!(ase.colors[0].color, ColorValue::Rgb(0.5, 0.5, 0.5));
+    /// ```
+    pub fn convert_all(&mut self, model: ColorModel) -> usize {
+        self.colors_mut()
+            .map(|block| usize::from(convert(block, model)))
+            .sum()
+    }
+
+    /// Converts all colors like [`Ase::convert_all`], spreading the colors across threads.
+    ///
+    /// The `rayon` feature requires at least Rust 1.80.
+    #[cfg(feature = "rayon")]
+    pub fn convert_all_par(&mut self, model: ColorModel) -> usize {
+        let colors: Vec<&mut ColorBlock> = self.colors_mut().collect();
+        colors
+            .into_par_iter()
+            .map(|block| usize::from(convert(block, model)))
+            .sum()
+    }
+
+    /// Removes colors, whose color difference (CIE76 Delta E) to any preceding color is at
+    /// most `tolerance`, so of a run of similar colors only the first one is kept.
+    ///
+    /// Colors are compared across groups in document order, first those in groups, followed
+    /// by the single colors. Groups are kept, even if all of their colors are removed.
+    ///
+    /// Returns the number of removed colors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let gray = |name: &str, value| ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal);
+    /// let mut ase = Ase::new(vec![], vec![gray("a", 0.5), gray("b", 0.501), gray("c", 0.9)]);
+    /// assert_eq!(ase.dedup_perceptual(1.0), 1);
+    /// assert_eq!(ase.colors, vec![gray("a", 0.5), gray("c", 0.9)]);
+    /// ```
+    pub fn dedup_perceptual(&mut self, tolerance: f32) -> usize {
+        let colors: Vec<ColorValue> = self.colors().map(lab).collect();
+        let duplicates: Vec<bool> = (0..colors.len())
+            .map(|index| is_duplicate(&colors, index, tolerance))
+            .collect();
+        self.remove_colors(&duplicates)
+    }
+
+    /// Removes similar colors like [`Ase::dedup_perceptual`], spreading the comparisons
+    /// across threads.
+    #[cfg(feature = "rayon")]
+    pub fn dedup_perceptual_par(&mut self, tolerance: f32) -> usize {
+        let blocks: Vec<&ColorBlock> = self.colors().collect();
+        let colors: Vec<ColorValue> = blocks.into_par_iter().map(lab).collect();
+        let duplicates: Vec<bool> = (0..colors.len())
+            .into_par_iter()
+            .map(|index| is_duplicate(&colors, index, tolerance))
+            .collect();
+        self.remove_colors(&duplicates)
+    }
+
+    /// Returns all pairs of colors, whose WCAG contrast ratio is below `minimum`, like
+    /// `4.5` for text of the AA level.
+    ///
+    /// Colors are indexed in document order, first those in groups, followed by the single
+    /// colors. The pairs are ordered by the first and then the second color.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue, ContrastPair};
+    /// let gray = |value| ColorBlock::new(String::new(), ColorValue::Gray(value), ColorType::Normal);
+    /// let ase = Ase::new(vec![], vec![gray(0.0), gray(1.0), gray(0.1)]);
+    /// let pairs = ase.contrast_audit(4.5);
+    /// assert_eq!(pairs.len(), 1);
+    /// assert_eq!((pairs[0].first, pairs[0].second), (0, 2));
+    /// ```
+    pub fn contrast_audit(&self, minimum: f32) -> Vec<ContrastPair> {
+        let luminances: Vec<f32> = self.colors().map(|block| block.color.luminance()).collect();
+        (0..luminances.len())
+            .flat_map(|first| low_contrast(&luminances, first, minimum))
+            .collect()
+    }
+
+    /// Returns the pairs of colors with low contrast like [`Ase::contrast_audit`],
+    /// spreading the comparisons across threads.
+    #[cfg(feature = "rayon")]
+    pub fn contrast_audit_par(&self, minimum: f32) -> Vec<ContrastPair> {
+        let blocks: Vec<&ColorBlock> = self.colors().collect();
+        let luminances: Vec<f32> = blocks
+            .into_par_iter()
+            .map(|block| block.color.luminance())
+            .collect();
+        let pairs: Vec<Vec<ContrastPair>> = (0..luminances.len())
+            .into_par_iter()
+            .map(|first| low_contrast(&luminances, first, minimum).collect())
+            .collect();
+        pairs.concat()
+    }
+
+    /// Removes the colors, for which `remove` is set, in document order.
+    fn remove_colors(&mut self, remove: &[bool]) -> usize {
+        let mut remove = remove.iter();
+        let mut removed = 0;
+        let mut keep = |_: &ColorBlock| {
+            let duplicate = remove.next().copied().unwrap_or(false);
+            removed += usize::from(duplicate);
+            !duplicate
+        };
+        for group in &mut self.groups {
+            group.blocks.retain(&mut keep);
+        }
+        self.colors.retain(&mut keep);
+        removed
+    }
+}
+
+/// Converts the color of the block, returns whether it changed its model.
+fn convert(block: &mut ColorBlock, model: ColorModel) -> bool {
+    if block.color.model() == model {
+        return false;
+    }
+    block.color = block.color.converted(model);
+    true
+}
+
+/// Returns the color of the block in CIE Lab.
+fn lab(block: &ColorBlock) -> ColorValue {
+    block.color.converted(ColorModel::Lab)
+}
+
+/// Returns whether the Lab color at `index` is within `tolerance` of a preceding one.
+fn is_duplicate(colors: &[ColorValue], index: usize, tolerance: f32) -> bool {
+    let color = &colors[index];
+    colors[..index]
+        .iter()
+        .any(|preceding| preceding.delta_e(color) <= tolerance)
+}
+
+/// Returns the pairs of the color at `first` and the following colors, whose contrast is
+/// below `minimum`.
+fn low_contrast(
+    luminances: &[f32],
+    first: usize,
+    minimum: f32,
+) -> impl Iterator<Item = ContrastPair> + '_ {
+    let luminance = luminances[first];
+    luminances
+        .iter()
+        .enumerate()
+        .skip(first + 1)
+        .map(move |(second, &other)| ContrastPair {
+            first,
+            second,
+            ratio: contrast_ratio(luminance, other),
+        })
+        .filter(move |pair| pair.ratio < minimum)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::named, Group};
+
+    use super::*;
+
+    fn palette() -> Ase {
+        Ase::new(
+            vec![Group::new(
+                "group".to_owned(),
+                vec![
+                    named("red", ColorValue::Rgb(1.0, 0.0, 0.0)),
+                    named("black", ColorValue::Cmyk(0.0, 0.0, 0.0, 1.0)),
+                ],
+            )],
+            vec![
+                named("almost red", ColorValue::Rgb(0.995, 0.0, 0.0)),
+                named("white", ColorValue::Lab(100.0, 0.0, 0.0)),
+                named("black", ColorValue::Gray(0.0)),
+            ],
+        )
+    }
+
+    /// Returns a palette of random colors in all models, with some repeated colors.
+    #[cfg(feature = "rayon")]
+    fn random_palette(colors: usize) -> Ase {
+        let mut state: u32 = 0x9e37_79b9;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        let mut blocks: Vec<ColorBlock> = Vec::with_capacity(colors);
+        for index in 0..colors {
+            let color = match index % 5 {
+                0 => ColorValue::Rgb(next(), next(), next()),
+                1 => ColorValue::Cmyk(next(), next(), next(), next()),
+                2 => ColorValue::Lab(next() * 100.0, next() * 255.0 - 128.0, next() * 50.0),
+                3 => ColorValue::Gray(next()),
+                _ => blocks[index / 2].color.clone(),
+            };
+            blocks.push(ColorBlock::new(
+                index.to_string(),
+                color,
+                crate::ColorType::Normal,
+            ));
+        }
+        let colors = blocks.split_off(colors / 2);
+        let groups = blocks
+            .chunks(50)
+            .map(|chunk| Group::new("group".to_owned(), chunk.to_vec()))
+            .collect();
+        Ase::new(groups, colors)
+    }
+
+    #[test]
+    fn it_converts_all_colors() {
+        let mut ase = palette();
+        assert_eq!(ase.convert_all(ColorModel::Rgb), 3);
+        assert!(ase
+            .colors()
+            .all(|block| block.color.model() == ColorModel::Rgb));
+        assert_eq!(
+            ase.groups[0].blocks[1].color,
+            ColorValue::Rgb(0.0, 0.0, 0.0)
+        );
+        assert_eq!(ase.colors[2].color, ColorValue::Rgb(0.0, 0.0, 0.0));
+        assert_eq!(ase.convert_all(ColorModel::Rgb), 0);
+
+        assert_eq!(ase.convert_all(ColorModel::Gray), 5);
+        assert!(
+            matches!(ase.colors[1].color, ColorValue::Gray(white) if (white - 1.0).abs() < 1e-6)
+        );
+    }
+
+    #[test]
+    fn it_removes_similar_colors() {
+        let mut ase = palette();
+        assert_eq!(ase.dedup_perceptual(0.0), 1);
+        let names: Vec<_> = ase.colors().map(|block| block.name.as_str()).collect();
+        assert_eq!(names, ["red", "black", "almost red", "white"]);
+
+        assert_eq!(ase.dedup_perceptual(2.0), 1);
+        assert_eq!(ase.groups[0].blocks.len(), 2);
+        assert_eq!(ase.colors.len(), 1);
+    }
+
+    #[test]
+    fn it_audits_contrast() {
+        let pairs = palette().contrast_audit(1.5);
+        let indices: Vec<_> = pairs.iter().map(|pair| (pair.first, pair.second)).collect();
+        assert_eq!(indices, [(0, 2), (1, 4)]);
+        assert_eq!(pairs[1].ratio, 1.0);
+        assert!(palette().contrast_audit(1.0).is_empty());
+        assert_eq!(palette().contrast_audit(22.0).len(), 10);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn it_matches_sequential_results_in_parallel() {
+        let ase = random_palette(2_000);
+        for model in [
+            ColorModel::Rgb,
+            ColorModel::Cmyk,
+            ColorModel::Lab,
+            ColorModel::Gray,
+        ] {
+            let (mut sequential, mut parallel) = (ase.clone(), ase.clone());
+            assert_eq!(
+                sequential.convert_all(model),
+                parallel.convert_all_par(model)
+            );
+            assert_eq!(sequential, parallel);
+        }
+
+        for tolerance in [0.0, 2.5, 10.0] {
+            let (mut sequential, mut parallel) = (ase.clone(), ase.clone());
+            let removed = sequential.dedup_perceptual(tolerance);
+            assert!(removed > 0);
+            assert_eq!(removed, parallel.dedup_perceptual_par(tolerance));
+            assert_eq!(sequential, parallel);
+        }
+
+        for minimum in [1.05, 1.5] {
+            let sequential = ase.contrast_audit(minimum);
+            assert!(!sequential.is_empty());
+            assert_eq!(sequential, ase.contrast_audit_par(minimum));
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use crate::{buffer::Buffer, error::ASEError};
+use crate::{
+    error::ASEError,
+    trace::trace_event,
+    wire::{self, Terminator},
+    writer::{name_units, truncate_long_name, Writer, DEFAULT_OPTIONS},
+};
 
-use super::{block_type::BlockType, ColorType, ColorValue};
+use super::{block, block_type::BlockType, ColorType, ColorValue};
+
+/// The length of the smallest color entry, a gray color with an empty name.
+pub(crate) const MIN_ENTRY_LENGTH: u32 = 14;
 
 /// A single color with an associated name.
 #[derive(Debug, Clone, PartialEq)]
@@ -35,18 +43,55 @@ impl ColorBlock {
         }
     }
 
-    /// Write the block to the given [`Buffer`]
-    pub(crate) fn write(self, buf: &mut Buffer) {
-        buf.write_u16(BlockType::ColorEntry as u16);
-        buf.write_u32(self.calculate_length());
-        // name length, +1 for null terminator
-        buf.write_u16(self.name.len() as u16 + 1);
-        buf.write_null_terminated_utf_16_str(&self.name);
+    /// Encodes the color as it is written to .ase files.
+    ///
+    /// The bytes start with the color entry marker and the length of the block, followed
+    /// by the name, color model, color values and color type.
+    ///
+    /// Names with more than `65534` UTF-16 code units are truncated to fit into their length
+    /// field, like by [`create_ase`](crate::create_ase).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue};
+    /// let block = ColorBlock::new("".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// assert_eq!(
+    ///     block.to_bytes(),
+    ///     vec![0, 1, 0, 0, 0, 14, 0, 1, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2]
+    /// );
+    /// ```
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new(self.calculate_length() as usize + 2 + 4, &DEFAULT_OPTIONS);
+        if self.write(&mut writer).is_err() {
+            // names are written as they are by default, once they fit
+            let mut block = self.clone();
+            truncate_long_name(&mut block.name);
+            return block.to_bytes();
+        }
+        writer.into_vec()
+    }
+
+    /// Write the block using the given [`Writer`]
+    ///
+    /// # Errors
+    /// This function will return an error if the name cannot be written.
+    pub(crate) fn write(&self, writer: &mut Writer) -> Result<(), ASEError> {
+        writer.buf.write_u16(BlockType::ColorEntry.into());
+        writer.write_with_length(|writer| {
+            let name = writer.color_name(&self.name, &self.color);
+            writer.write_name(&name)?;
 
-        // write color
-        buf.write_slice(self.color.get_type());
-        self.color.write_values(buf);
-        buf.write_u16(self.color_type as u16);
+            // write color
+            writer.buf.write_slice(self.color.get_type());
+            writer
+                .color_values(&self.color)
+                .write_values(&mut writer.buf, writer.options.lab_scaling);
+            writer
+                .buf
+                .write_u16(writer.options.color_types.map(&self.color_type) as u16);
+            Ok(())
+        })
     }
 
     /// Calculate the length of an color block.
@@ -57,50 +102,241 @@ impl ColorBlock {
     ///  - color type (4)
     ///  - color value (1/3/4)
     ///  - type (2)
+    #[allow(clippy::arithmetic_side_effects)]
     pub(crate) fn calculate_length(&self) -> u32 {
-        2 + self.name.len() as u32 * 2 + 2 + 4 + self.color.calculate_length() + 2
+        2 + name_units(&self.name) as u32 * 2 + 2 + 4 + self.color.calculate_length() + 2
+    }
+
+    /// Parses a [`ColorBlock`] from the data of a color entry block.
+    ///
+    /// The data follows the type and length of the block: the length of the name,
+    /// the null terminated UTF-16 name, the four character color model, the color
+    /// values and the color type. Bytes after the color type are ignored. Use
+    /// [`ColorBlock::from_bytes`] to parse a block including its type and length.
+    ///
+    /// # Errors
+    /// This function will return an [`ASEError::InputDataParseError`] if the data is
+    /// truncated, an [`ASEError::UTF16Error`] if the name is not valid UTF-16, an
+    /// [`ASEError::ColorFormat`] if the color model is unknown and an
+    /// [`ASEError::ColorTypeError`] if the color type is unknown. Errors after a non-empty
+    /// name are wrapped in an [`ASEError::Swatch`] carrying the name.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue};
+    /// // name "a", gray color model with a value of 0.5 and a normal color type
+    /// let data = [0, 2, 0, 97, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2];
+    /// assert_eq!(
+    ///     ColorBlock::parse(&data).unwrap(),
+    ///     ColorBlock::new("a".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)
+    /// );
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<Self, ASEError> {
+        Self::parse_entry(bytes).map(|(block, _)| block)
+    }
+
+    /// Parses a [`ColorBlock`] like [`ColorBlock::parse`], returning the number of bytes
+    /// read up to the end of its color type.
+    ///
+    /// This is the size of the data, which the length of the block should declare.
+    ///
+    /// # Errors
+    /// This function will return an error like [`ColorBlock::parse`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorBlock;
+    /// let data = [0, 2, 0, 97, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0xff, 0xff];
+    /// let (_, length) = ColorBlock::parse_with_length(&data).unwrap();
+    /// assert_eq!(length, 16);
+    /// ```
+    pub fn parse_with_length(bytes: &[u8]) -> Result<(Self, usize), ASEError> {
+        Self::parse_entry(bytes).map(|(block, layout)| (block, layout.length))
+    }
+
+    /// Parses a [`ColorBlock`] from a color entry block, as returned by
+    /// [`ColorBlock::to_bytes`].
+    ///
+    /// The block starts with its type and length, followed by the data read by
+    /// [`ColorBlock::parse`].
+    ///
+    /// # Errors
+    /// This function will return an [`ASEError::BlockTypeError`] if the block is not a
+    /// color entry, an [`ASEError::Invalid`] if `bytes` ends before the length of the
+    /// block or continues after it, and the errors of [`ColorBlock::parse`] otherwise.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue};
+    /// let block = ColorBlock::new("a".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// assert_eq!(ColorBlock::from_bytes(&block.to_bytes()).unwrap(), block);
+    /// assert!(ColorBlock::from_bytes(&block.to_bytes()[..10]).is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ASEError> {
+        let (data, rest) = block::split_block(bytes, BlockType::ColorEntry)?;
+        block::reject_trailing(rest)?;
+        Self::parse(data)
     }
 
-    /// Parses a [`ColorBlock`] from bytes.
+    /// Parses a [`ColorBlock`] from bytes, returning how the color entry is laid out.
     ///
     /// # Errors
     /// This function will return an error if parsing fails.
-    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, ASEError> {
-        let name_length = u16::from_be_bytes(
-            bytes
-                .get(0..2)
-                .ok_or(ASEError::InputDataParseError)?
-                .try_into()?,
-        );
-        // read name bytes, but stop before null byte
-        let name_bytes: Vec<u16> = bytes
-            .get(2..(name_length as usize * 2))
-            .ok_or(ASEError::InputDataParseError)?
-            .chunks_exact(2)
-            .map(|bytes| u16::from_be_bytes(bytes.try_into().unwrap()))
-            .collect();
-        let name = String::from_utf16(&name_bytes)?;
-
-        let color_value_start = name_length as usize * 2 + 2;
-        let color_value = ColorValue::try_from(
+    pub(crate) fn parse_entry(bytes: &[u8]) -> Result<(Self, EntryLayout), ASEError> {
+        let parts = Self::parse_parts(bytes, false)?;
+        let mut name = String::new();
+        wire::decode_utf16_units_into(parts.name, &mut name)?;
+        Ok((Self::new(name, parts.color, parts.color_type), parts.layout))
+    }
+
+    /// Parses the parts of a [`ColorBlock`] from bytes, without decoding its name.
+    ///
+    /// Some applications write the number of characters as the length of the name,
+    /// followed by its null terminator. If the color cannot be read after the name, it
+    /// is read after such a name.
+    ///
+    /// Codes of color models, which only differ from the format in case or a null
+    /// character in place of the trailing space, are read, if `recover_model` is set.
+    ///
+    /// # Errors
+    /// This function will return an error if parsing fails.
+    pub(crate) fn parse_parts(
+        bytes: &[u8],
+        recover_model: bool,
+    ) -> Result<ColorParts<'_>, ASEError> {
+        let (name, color_value_start) = wire::get_utf16_nul_units(bytes)?;
+        let (name, (color, color_type, length, recovered_model), terminator) =
+            match Self::parse_color(bytes, color_value_start, recover_model) {
+                Ok(color) => (name, color, Terminator::Counted),
+                // names, which are not valid, fail before their color
+                Err(_) if wire::decode_utf16_units(name).any(|c| c.is_err()) => {
+                    return Err(ASEError::UTF16Error)
+                }
+                // the name is valid, so it is added to the error
+                Err(err) => {
+                    let Some((name, color_value_start)) =
+                        wire::get_utf16_uncounted_nul_units(bytes)
+                    else {
+                        return Err(err.in_swatch(wire::decode_utf16_units_lossy(name)));
+                    };
+                    let color = Self::parse_color(bytes, color_value_start, recover_model)
+                        .map_err(|_| err.in_swatch(wire::decode_utf16_units_lossy(name)))?;
+                    trace_event!("read a name, whose terminator is not counted by its length");
+                    (name, color, Terminator::Uncounted)
+                }
+            };
+        if recovered_model {
+            trace_event!("recovered the code of the color model");
+        }
+        Ok(ColorParts {
+            name,
+            color,
+            color_type,
+            layout: EntryLayout {
+                length,
+                name_length: name.len() / 2,
+                terminator,
+                little_endian_name: false,
+                recovered_model,
+            },
+        })
+    }
+
+    /// Parses a [`ColorBlock`] from bytes, decoding its name into `name`.
+    ///
+    /// # Errors
+    /// This function will return an error if parsing fails.
+    pub(crate) fn parse_into(
+        bytes: &[u8],
+        name: &mut String,
+    ) -> Result<(ColorValue, ColorType), ASEError> {
+        let parts = Self::parse_parts(bytes, false)?;
+        wire::decode_utf16_units_into(parts.name, name)?;
+        Ok((parts.color, parts.color_type))
+    }
+
+    /// Parses the color value and type of a [`ColorBlock`], which follow its name at
+    /// `color_value_start`, and returns the end of the color type and whether the code of
+    /// the color model was recovered, see [`Self::parse_parts`].
+    ///
+    /// # Errors
+    /// This function will return an error if parsing fails.
+    pub(crate) fn parse_color(
+        bytes: &[u8],
+        color_value_start: usize,
+        recover_model: bool,
+    ) -> Result<(ColorValue, ColorType, usize, bool), ASEError> {
+        let (color_value, recovered_model) = ColorValue::parse(
             bytes
                 .get(color_value_start..)
                 .ok_or(ASEError::InputDataParseError)?,
+            recover_model,
         )?;
 
-        let color_type_start = color_value_start + color_value.calculate_length() as usize + 4;
+        // skip the color model and its values
+        let color_type_start = color_value_start
+            .saturating_add(4)
+            .saturating_add(color_value.calculate_length() as usize);
         let color_type = ColorType::try_from(
             bytes
-                .get(color_type_start + 1)
+                .get(color_type_start.saturating_add(1))
                 .ok_or(ASEError::InputDataParseError)?,
         )?;
 
-        Ok(Self::new(name, color_value, color_type))
+        Ok((
+            color_value,
+            color_type,
+            color_type_start.saturating_add(2),
+            recovered_model,
+        ))
+    }
+}
+
+/// The parts of a color entry, see [`ColorBlock::parse_parts`].
+#[derive(Debug, Clone)]
+pub(crate) struct ColorParts<'a> {
+    /// The UTF-16 code units of the name, up to its null terminator.
+    pub(crate) name: &'a [u8],
+    /// The color value of the entry.
+    pub(crate) color: ColorValue,
+    /// The color type of the entry.
+    pub(crate) color_type: ColorType,
+    /// How the entry is laid out.
+    pub(crate) layout: EntryLayout,
+}
+
+/// How a color entry is laid out, see [`ColorBlock::parse_entry`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EntryLayout {
+    /// The number of bytes up to the end of the color type.
+    ///
+    /// Any bytes after the color type are not part of the color.
+    pub(crate) length: usize,
+    /// The number of UTF-16 code units of the name.
+    pub(crate) name_length: usize,
+    /// Where the null terminator of the name is found.
+    pub(crate) terminator: Terminator,
+    /// Whether the name was read as little-endian UTF-16.
+    pub(crate) little_endian_name: bool,
+    /// Whether the code of the color model only matches the format loosely.
+    pub(crate) recovered_model: bool,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ColorBlock {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(
+            super::arbitrary_name(u)?,
+            u.arbitrary()?,
+            u.arbitrary()?,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{ConformationError, LabScaling, WriteOptions};
+
     use super::*;
 
     #[test]
@@ -112,15 +348,76 @@ mod tests {
     #[test]
     fn it_writes_bytes_correctly() {
         let block = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
-        let mut buf = Buffer::with_capacity(22);
-        block.write(&mut buf);
+        let options = WriteOptions::default();
+        let mut writer = Writer::new(22, &options);
+        block.write(&mut writer).unwrap();
+        let bytes = vec![
+            0, 1, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 71, 114, 97, 121, 63, 0,
+            0, 0, 0, 2,
+        ];
+        assert_eq!(writer.into_vec(), bytes);
+        assert_eq!(block.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn it_scales_lab_lightness() {
+        let block = ColorBlock::new(
+            "".to_owned(),
+            ColorValue::Lab(47.83, 56.29, -36.03),
+            ColorType::Normal,
+        );
+        let write = |lab_scaling| {
+            let options = WriteOptions {
+                lab_scaling,
+                ..Default::default()
+            };
+            let mut writer = Writer::new(0, &options);
+            block.write(&mut writer).unwrap();
+            writer.into_vec()
+        };
+        let header = [0, 1, 0, 0, 0, 22, 0, 1, 0, 0, 76, 65, 66, 32];
+        let ab = [66, 97, 40, 246, 194, 16, 30, 184];
+
+        let bytes = write(LabScaling::Normalized);
+        assert_eq!(bytes[..14], header);
+        assert_eq!(bytes[14..18], [62, 244, 227, 189]);
+        assert_eq!(bytes[18..26], ab);
+        assert_eq!(bytes, block.to_bytes());
+
+        let bytes = write(LabScaling::Raw);
+        assert_eq!(bytes[..14], header);
+        assert_eq!(bytes[14..18], [66, 63, 81, 236]);
+        assert_eq!(bytes[18..26], ab);
+    }
+
+    #[test]
+    fn it_clamps_components() {
+        let block = ColorBlock::new(
+            "".to_owned(),
+            ColorValue::Rgb(1.0000001, -0.0003, 0.5),
+            ColorType::Normal,
+        );
+        let header = [0, 1, 0, 0, 0, 22, 0, 1, 0, 0, 82, 71, 66, 32];
+        let write = |options: &WriteOptions| {
+            let mut writer = Writer::new(0, options);
+            block.write(&mut writer).unwrap();
+            writer.into_vec()
+        };
+
+        let bytes = write(&WriteOptions::default());
+        assert_eq!(bytes[..14], header);
         assert_eq!(
-            buf.into_vec(),
-            vec![
-                0, 1, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0, 109, 0, 101, 0, 0, 71, 114, 97, 121, 63,
-                0, 0, 0, 0, 2
-            ]
+            bytes[14..26],
+            [63, 128, 0, 1, 185, 157, 73, 82, 63, 0, 0, 0]
         );
+
+        let options = WriteOptions {
+            clamp_components: true,
+            ..Default::default()
+        };
+        let bytes = write(&options);
+        assert_eq!(bytes[..14], header);
+        assert_eq!(bytes[14..26], [63, 128, 0, 0, 0, 0, 0, 0, 63, 0, 0, 0]);
     }
 
     #[test]
@@ -144,6 +441,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_reads_name_length_without_terminator() {
+        let block = ColorBlock::new("ab".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+        let (read, layout) = ColorBlock::parse_entry(&[
+            0, 2, 0, 97, 0, 98, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2,
+        ])
+        .unwrap();
+        assert_eq!(read, block);
+        assert_eq!(layout.length, 18);
+        assert_eq!(layout.terminator, Terminator::Uncounted);
+    }
+
+    #[test]
+    fn it_reads_whole_blocks() {
+        let block = ColorBlock::new(
+            "dark red".to_owned(),
+            ColorValue::Rgb(0.5, 0.3, 0.1),
+            ColorType::Spot,
+        );
+        let bytes = block.to_bytes();
+        assert_eq!(ColorBlock::from_bytes(&bytes).unwrap(), block);
+
+        assert!(matches!(
+            ColorBlock::from_bytes(&bytes[..bytes.len() - 2]),
+            Err(ASEError::Invalid(ConformationError::Truncated {
+                missing: 2
+            }))
+        ));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            ColorBlock::from_bytes(&trailing),
+            Err(ASEError::Invalid(ConformationError::TrailingData {
+                length: 1
+            }))
+        ));
+        let mut group_start = bytes;
+        group_start[0] = 0xc0;
+        assert!(matches!(
+            ColorBlock::from_bytes(&group_start),
+            Err(ASEError::BlockTypeError)
+        ));
+    }
+
     #[test]
     fn it_returns_error_on_empty_input() {
         let parser_result = ColorBlock::parse(&[]);
@@ -4,7 +4,7 @@ use crate::error::ASEError;
 /// Specifies how the color behaves in a document.
 ///
 /// Information from <https://pypi.org/project/swatch/>
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum ColorType {
     /// Represents Global colors in ASE files.
     ///
@@ -41,3 +41,13 @@ impl TryFrom<&u8> for ColorType {
         }
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ColorType {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(
+            u.choose(&[ColorType::Global, ColorType::Spot, ColorType::Normal])?
+                .clone(),
+        )
+    }
+}
@@ -1,6 +1,10 @@
 use std::ops::Range;
 
-use crate::{buffer::Buffer, error::ASEError};
+use crate::{
+    buffer::Buffer,
+    error::{ASEError, HexError},
+    wire, LabScaling,
+};
 
 /// Color data
 #[derive(Debug, Clone, PartialEq)]
@@ -11,9 +15,322 @@ pub enum ColorValue {
     Gray(f32),
 }
 
+/// The color model of a [`ColorValue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorModel {
+    Cmyk,
+    Rgb,
+    Lab,
+    Gray,
+}
+
+/// Which end of the range of gray values is black.
+///
+/// [`ColorValue::Gray`] always uses `0.0` for black and `1.0` for white, applications
+/// disagree on how the values are stored in files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GrayPolarity {
+    /// `0.0` is black, so values are stored as they are.
+    #[default]
+    ZeroIsBlack,
+    /// `0.0` is white, so values are inverted, like `1.0 - v`.
+    ZeroIsWhite,
+}
+
+/// How RGB colors, whose components are stored in the range from `0` to `255` instead of
+/// `0.0` to `1.0`, are detected.
+///
+/// Detected colors are divided by `255`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RgbRange {
+    /// Components are read as they are.
+    #[default]
+    AsStored,
+    /// Each RGB color with a component greater than `1.0` is divided.
+    PerColor,
+    /// All RGB colors are divided, if any of them has a component greater than `1.0`.
+    PerFile,
+}
+
+impl RgbRange {
+    /// Divides the components of the detected RGB colors by `255`, returning the number of
+    /// divided colors.
+    pub(crate) fn normalize<'a>(self, colors: impl Iterator<Item = &'a mut ColorValue>) -> usize {
+        let is_byte_range = |color: &ColorValue| matches!(*color, ColorValue::Rgb(r, g, b) if r.max(g).max(b) > 1.0);
+        let mut colors: Vec<_> = colors
+            .filter(|color| matches!(color, ColorValue::Rgb(..)))
+            .collect();
+        match self {
+            RgbRange::AsStored => return 0,
+            RgbRange::PerColor => colors.retain(|color| is_byte_range(color)),
+            RgbRange::PerFile if !colors.iter().any(|color| is_byte_range(color)) => return 0,
+            RgbRange::PerFile => {}
+        }
+        for color in &mut colors {
+            if let ColorValue::Rgb(r, g, b) = **color {
+                **color = ColorValue::Rgb(r / 255.0, g / 255.0, b / 255.0);
+            }
+        }
+        colors.len()
+    }
+}
+
 impl ColorValue {
+    /// Parses an sRGB hex code, like `#bf616a`, into an [`Rgb`](ColorValue::Rgb) color.
+    ///
+    /// The code has the form `#RGB`, `#RRGGBB` or `#RRGGBBAA`, with an optional leading `#`
+    /// and digits in either case. The alpha of `#RRGGBBAA` is ignored, as colors in ASE
+    /// files are opaque.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an [`ASEError::HexColor`] if the code contains a character,
+    /// which is not a hex digit, or does not have 3, 6 or 8 digits.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert_eq!(
+    ///     ColorValue::from_hex("#ff8000").unwrap(),
+    ///     ColorValue::Rgb(1.0, 128.0 / 255.0, 0.0)
+    /// );
+    /// assert_eq!(
+    ///     ColorValue::from_hex("F80").unwrap(),
+    ///     ColorValue::from_hex("#FF8800").unwrap()
+    /// );
+    /// assert!(ColorValue::from_hex("#ff80").is_err());
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<ColorValue, ASEError> {
+        let error = |reason| ASEError::HexColor {
+            hex: hex.to_owned(),
+            reason,
+        };
+        let digits = hex
+            .strip_prefix('#')
+            .unwrap_or(hex)
+            .chars()
+            .map(|digit| {
+                // hex digits are less than 16, so they fit into a byte
+                digit
+                    .to_digit(16)
+                    .map(|value| value as u8)
+                    .ok_or_else(|| error(HexError::Digit(digit)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let [r, g, b] = match *digits.as_slice() {
+            // each digit is repeated, like `#f80` for `#ff8800`
+            [r, g, b] => [r, g, b].map(|digit| digit.wrapping_mul(0x11)),
+            [r1, r2, g1, g2, b1, b2] | [r1, r2, g1, g2, b1, b2, _, _] => {
+                [(r1, r2), (g1, g2), (b1, b2)].map(|(high, low)| high << 4 | low)
+            }
+            _ => return Err(error(HexError::Length(digits.len()))),
+        };
+        Ok(ColorValue::Rgb(
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        ))
+    }
+
+    /// Returns the color as sRGB hex code, like `#bf616a`, or `None` if a component is NaN
+    /// or infinite.
+    ///
+    /// Other color models are converted to sRGB first, CMYK colors without a color profile
+    /// and Lab colors assuming a D50 white point. Components outside of the gamut are
+    /// clamped, then each is scaled to `0` to `255` and rounded half up, so `0.5` becomes
+    /// `80`. The digits are lowercase.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert_eq!(
+    ///     ColorValue::Rgb(0.749_019_6, 0.380_392_16, 0.415_686_28).to_hex(),
+    ///     Some("#bf616a".to_owned())
+    /// );
+    /// assert_eq!(ColorValue::Gray(0.5).to_hex(), Some("#808080".to_owned()));
+    /// assert_eq!(ColorValue::Gray(f32::NAN).to_hex(), None);
+    /// ```
+    pub fn to_hex(&self) -> Option<String> {
+        self.is_finite().then(|| self.to_srgb_hex())
+    }
+
+    /// Returns the color model of the value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorModel, ColorValue};
+    /// assert_eq!(ColorValue::Gray(0.5).model(), ColorModel::Gray);
+    /// ```
+    pub fn model(&self) -> ColorModel {
+        match self {
+            ColorValue::Cmyk(_, _, _, _) => ColorModel::Cmyk,
+            ColorValue::Rgb(_, _, _) => ColorModel::Rgb,
+            ColorValue::Lab(_, _, _) => ColorModel::Lab,
+            ColorValue::Gray(_) => ColorModel::Gray,
+        }
+    }
+
+    /// Returns `true` if all components are within the range of the color model.
+    ///
+    /// CMYK, RGB and Gray components range from `0.0` to `1.0`, Lab lightness from
+    /// `0.0` to `100.0` and its a* and b* components from `-128.0` to `127.0`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert!(ColorValue::Lab(50.0, -100.0, 100.0).is_in_range());
+    /// assert!(!ColorValue::Rgb(1.5, 0.0, 0.0).is_in_range());
+    /// assert!(!ColorValue::Gray(f32::NAN).is_in_range());
+    /// ```
+    pub fn is_in_range(&self) -> bool {
+        let unit = |v: f32| (0.0..=1.0).contains(&v);
+        match *self {
+            ColorValue::Cmyk(c, m, y, k) => [c, m, y, k].into_iter().all(unit),
+            ColorValue::Rgb(r, g, b) => [r, g, b].into_iter().all(unit),
+            ColorValue::Lab(l, a, b) => {
+                (0.0..=100.0).contains(&l) && [a, b].iter().all(|v| (-128.0..=127.0).contains(v))
+            }
+            ColorValue::Gray(value) => unit(value),
+        }
+    }
+
+    /// Returns the color with all components clamped to the range of the color model,
+    /// see [`ColorValue::is_in_range`].
+    ///
+    /// NaN components are left unchanged.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert_eq!(
+    ///     ColorValue::Rgb(1.0000001, -0.0003, 0.5).clamped(),
+    ///     ColorValue::Rgb(1.0, 0.0, 0.5)
+    /// );
+    /// assert_eq!(
+    ///     ColorValue::Lab(100.5, -130.0, 127.5).clamped(),
+    ///     ColorValue::Lab(100.0, -128.0, 127.0)
+    /// );
+    /// ```
+    pub fn clamped(&self) -> ColorValue {
+        let unit = |v: f32| v.clamp(0.0, 1.0);
+        let ab = |v: f32| v.clamp(-128.0, 127.0);
+        match *self {
+            ColorValue::Cmyk(c, m, y, k) => ColorValue::Cmyk(unit(c), unit(m), unit(y), unit(k)),
+            ColorValue::Rgb(r, g, b) => ColorValue::Rgb(unit(r), unit(g), unit(b)),
+            ColorValue::Lab(l, a, b) => ColorValue::Lab(l.clamp(0.0, 100.0), ab(a), ab(b)),
+            ColorValue::Gray(value) => ColorValue::Gray(unit(value)),
+        }
+    }
+
+    /// Converts a gray value between [`GrayPolarity::ZeroIsBlack`] and `polarity`, other
+    /// colors are returned as they are.
+    ///
+    /// Converting a value twice returns the original value.
+    pub(crate) fn with_gray_polarity(&self, polarity: GrayPolarity) -> ColorValue {
+        match (self, polarity) {
+            (ColorValue::Gray(value), GrayPolarity::ZeroIsWhite) => ColorValue::Gray(1.0 - value),
+            (color, _) => color.clone(),
+        }
+    }
+
+    /// Returns the color with negative zero components replaced by positive zero.
+    pub(crate) fn without_negative_zero(&self) -> ColorValue {
+        let zero = |v: f32| if v == 0.0 { 0.0 } else { v };
+        match *self {
+            ColorValue::Cmyk(c, m, y, k) => ColorValue::Cmyk(zero(c), zero(m), zero(y), zero(k)),
+            ColorValue::Rgb(r, g, b) => ColorValue::Rgb(zero(r), zero(g), zero(b)),
+            ColorValue::Lab(l, a, b) => ColorValue::Lab(zero(l), zero(a), zero(b)),
+            ColorValue::Gray(value) => ColorValue::Gray(zero(value)),
+        }
+    }
+
+    /// Returns `true` if no component is NaN or infinite.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert!(ColorValue::Rgb(2.0, 0.0, 0.0).is_finite());
+    /// assert!(!ColorValue::Rgb(f32::NAN, 0.0, 0.0).is_finite());
+    /// assert!(!ColorValue::Lab(50.0, f32::NEG_INFINITY, 0.0).is_finite());
+    /// ```
+    pub fn is_finite(&self) -> bool {
+        match *self {
+            ColorValue::Cmyk(c, m, y, k) => [c, m, y, k].into_iter().all(f32::is_finite),
+            ColorValue::Rgb(r, g, b) | ColorValue::Lab(r, g, b) => {
+                [r, g, b].into_iter().all(f32::is_finite)
+            }
+            ColorValue::Gray(value) => value.is_finite(),
+        }
+    }
+
+    /// Returns the color converted to the given color model, colors of that model are
+    /// returned as they are.
+    ///
+    /// Colors are converted through sRGB without clamping, CMYK colors without a color
+    /// profile and Lab colors assuming a D50 white point. CMYK components are computed from
+    /// the clamped sRGB color. Gray values have the relative luminance of the color.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorModel, ColorValue};
+    /// assert_eq!(
+    ///     ColorValue::Rgb(0.5, 0.25, 0.0).converted(ColorModel::Cmyk),
+    ///     ColorValue::Cmyk(0.0, 0.5, 1.0, 0.5)
+    /// );
+    /// assert_eq!(
+    ///     ColorValue::Gray(0.5).converted(ColorModel::Rgb),
+    ///     ColorValue::Rgb(0.5, 0.5, 0.5)
+    /// );
+    /// ```
+    pub fn converted(&self, model: ColorModel) -> ColorValue {
+        if self.model() == model {
+            return self.clone();
+        }
+        let (r, g, b) = self.to_srgb();
+        match model {
+            ColorModel::Rgb => ColorValue::Rgb(r, g, b),
+            ColorModel::Lab => {
+                let (l, a, b) = self.to_lab();
+                ColorValue::Lab(l, a, b)
+            }
+            ColorModel::Cmyk => {
+                let [r, g, b] = [r, g, b].map(|v| v.clamp(0.0, 1.0));
+                let k = 1.0 - r.max(g).max(b);
+                if k >= 1.0 {
+                    return ColorValue::Cmyk(0.0, 0.0, 0.0, 1.0);
+                }
+                let [c, m, y] = [r, g, b].map(|v| (1.0 - v - k) / (1.0 - k));
+                ColorValue::Cmyk(c, m, y, k)
+            }
+            ColorModel::Gray => ColorValue::Gray(linear_to_srgb(relative_luminance(r, g, b))),
+        }
+    }
+
+    /// Returns the WCAG contrast ratio between the colors, from `1.0` for colors of the
+    /// same luminance to `21.0` for black and white.
+    ///
+    /// Colors are converted to sRGB and clamped, like for [`ColorValue::converted`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// let black = ColorValue::Gray(0.0);
+    /// let white = ColorValue::Rgb(1.0, 1.0, 1.0);
+    /// assert!((black.contrast_ratio(&white) - 21.0).abs() < 1e-4);
+    /// assert_eq!(black.contrast_ratio(&black), 1.0);
+    /// ```
+    pub fn contrast_ratio(&self, other: &ColorValue) -> f32 {
+        contrast_ratio(self.luminance(), other.luminance())
+    }
+
+    /// Returns the relative luminance of the color in sRGB, as defined by WCAG.
+    pub(crate) fn luminance(&self) -> f32 {
+        let (r, g, b) = self.to_srgb();
+        relative_luminance(r, g, b)
+    }
+
     /// Returns the color type identifier
-    pub(super) fn get_type(&self) -> &[u8] {
+    pub(crate) fn get_type(&self) -> &[u8] {
         match self {
             ColorValue::Cmyk(_, _, _, _) => b"CMYK",
             ColorValue::Rgb(_, _, _) => b"RGB ",
@@ -22,9 +339,9 @@ impl ColorValue {
         }
     }
 
-    /// Write the color values to the given [`Buffer`]
-    pub(super) fn write_values(self, buf: &mut Buffer) {
-        match self {
+    /// Write the color values to the given [`Buffer`], scaling L* of Lab colors as given.
+    pub(crate) fn write_values(&self, buf: &mut Buffer, lab_scaling: LabScaling) {
+        match *self {
             ColorValue::Cmyk(c, m, y, k) => {
                 buf.write_f32(c);
                 buf.write_f32(m);
@@ -37,8 +354,11 @@ impl ColorValue {
                 buf.write_f32(b);
             }
             ColorValue::Lab(l, a, b) => {
-                // ASE stores L* scaled to [0, 1]
-                buf.write_f32(l / 100.0);
+                match lab_scaling {
+                    // ASE stores L* scaled to [0, 1]
+                    LabScaling::Normalized => buf.write_f32(l / 100.0),
+                    LabScaling::Raw => buf.write_f32(l),
+                }
                 buf.write_f32(a);
                 buf.write_f32(b);
             }
@@ -46,6 +366,52 @@ impl ColorValue {
         }
     }
 
+    /// Converts the color to sRGB, without clamping colors outside of the gamut.
+    ///
+    /// CMYK colors are converted without a color profile, Lab colors are assumed to use
+    /// a D50 white point.
+    pub(crate) fn to_srgb(&self) -> (f32, f32, f32) {
+        match *self {
+            ColorValue::Cmyk(c, m, y, k) => (
+                (1.0 - c) * (1.0 - k),
+                (1.0 - m) * (1.0 - k),
+                (1.0 - y) * (1.0 - k),
+            ),
+            ColorValue::Rgb(r, g, b) => (r, g, b),
+            ColorValue::Lab(l, a, b) => lab_to_srgb(l, a, b),
+            ColorValue::Gray(value) => (value, value, value),
+        }
+    }
+
+    /// Converts the color to CIE Lab (D50), converting other models through sRGB,
+    /// see [`ColorValue::to_srgb`].
+    pub(crate) fn to_lab(&self) -> (f32, f32, f32) {
+        match *self {
+            ColorValue::Lab(l, a, b) => (l, a, b),
+            _ => {
+                let (r, g, b) = self.to_srgb();
+                srgb_to_lab(r, g, b)
+            }
+        }
+    }
+
+    /// Returns the CIE76 color difference (Delta E) between the colors.
+    pub(crate) fn delta_e(&self, other: &ColorValue) -> f32 {
+        let (l1, a1, b1) = self.to_lab();
+        let (l2, a2, b2) = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// Returns the color as hex code in sRGB, like `#bf616a`, see [`ColorValue::to_srgb`].
+    ///
+    /// NaN components become `00`, see [`ColorValue::to_hex`] for the rounding.
+    pub(crate) fn to_srgb_hex(&self) -> String {
+        let (r, g, b) = self.to_srgb();
+        // the clamped values are not negative, so rounding half away from zero rounds half up
+        let [r, g, b] = [r, g, b].map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
     /// Calculate the length of the color
     ///
     /// The length is based on the number of f32, times 4,
@@ -59,46 +425,225 @@ impl ColorValue {
     }
 }
 
+/// Removes the sRGB transfer function from a component, mirroring negative values.
+fn srgb_to_linear(v: f32) -> f32 {
+    let linear = if v.abs() <= 0.040_45 {
+        v.abs() / 12.92
+    } else {
+        ((v.abs() + 0.055) / 1.055).powf(2.4)
+    };
+    linear.copysign(v)
+}
+
+/// Applies the sRGB transfer function to a linear component, mirroring negative values.
+fn linear_to_srgb(v: f32) -> f32 {
+    let encoded = if v.abs() <= 0.003_130_8 {
+        12.92 * v.abs()
+    } else {
+        1.055 * v.abs().powf(1.0 / 2.4) - 0.055
+    };
+    encoded.copysign(v)
+}
+
+/// Returns the relative luminance of the sRGB color, as defined by WCAG.
+fn relative_luminance(r: f32, g: f32, b: f32) -> f32 {
+    let [r, g, b] = [r, g, b].map(|v| srgb_to_linear(v.clamp(0.0, 1.0)));
+    0.212_6 * r + 0.715_2 * g + 0.072_2 * b
+}
+
+/// Returns the WCAG contrast ratio between colors of the given relative luminances.
+pub(crate) fn contrast_ratio(a: f32, b: f32) -> f32 {
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Converts CIE Lab (D50) to sRGB.
+fn lab_to_srgb(l: f32, a: f32, b: f32) -> (f32, f32, f32) {
+    const EPSILON: f32 = 6.0 / 29.0;
+    let f_inv = |t: f32| {
+        if t > EPSILON {
+            t * t * t
+        } else {
+            3.0 * EPSILON * EPSILON * (t - 4.0 / 29.0)
+        }
+    };
+
+    let fy = (l + 16.0) / 116.0;
+    let x = 0.964_22 * f_inv(fy + a / 500.0);
+    let y = f_inv(fy);
+    let z = 0.825_21 * f_inv(fy - b / 200.0);
+
+    // Bradford adaptation from D50 to D65
+    let (x, y, z) = (
+        0.955_576_6 * x - 0.023_039_3 * y + 0.063_163_6 * z,
+        -0.028_289_5 * x + 1.009_941_6 * y + 0.021_007_7 * z,
+        0.012_298_2 * x - 0.020_483 * y + 1.329_909_8 * z,
+    );
+
+    let gamma = linear_to_srgb;
+    (
+        gamma(3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z),
+        gamma(-0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z),
+        gamma(0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z),
+    )
+}
+
+/// Converts sRGB to CIE Lab (D50).
+fn srgb_to_lab(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+    let (x, y, z) = (
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+        0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+    );
+
+    // Bradford adaptation from D65 to D50
+    let (x, y, z) = (
+        1.047_811_2 * x + 0.022_886_6 * y - 0.050_127 * z,
+        0.029_542_4 * x + 0.990_484_4 * y - 0.017_049_1 * z,
+        -0.009_234_5 * x + 0.015_043_6 * y + 0.752_131_6 * z,
+    );
+
+    const EPSILON: f32 = 6.0 / 29.0;
+    let f = |t: f32| {
+        if t > EPSILON * EPSILON * EPSILON {
+            t.cbrt()
+        } else {
+            t / (3.0 * EPSILON * EPSILON) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x / 0.964_22), f(y), f(z / 0.825_21));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
 impl TryFrom<&[u8]> for ColorValue {
     type Error = ASEError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::parse(value, false).map(|(color, _)| color)
+    }
+}
+
+impl ColorValue {
+    /// Parses a color value like [`TryFrom`], matching the code of its color model like
+    /// [`recover_model_code`], if `recover_model` is set.
+    ///
+    /// Returns the color value and whether its code was recovered.
+    pub(crate) fn parse(value: &[u8], recover_model: bool) -> Result<(Self, bool), ASEError> {
         let f32_from_bytes = |index: Range<usize>| {
             value
                 .get(index)
                 .ok_or(ASEError::InputDataParseError)
-                .and_then(|data| {
-                    <&[u8] as TryInto<[u8; 4]>>::try_into(data)
-                        .map_err(|_| ASEError::InputDataParseError)
-                })
-                .map(f32::from_be_bytes)
+                .and_then(wire::get_f32_be)
         };
 
-        match &value.get(..4) {
+        let code = value.get(..4);
+        let recovered = code.filter(|_| recover_model).and_then(recover_model_code);
+        let color = match recovered.or(code) {
             Some(b"CMYK") => {
                 let cyan = f32_from_bytes(4..8)?;
                 let magenta = f32_from_bytes(8..12)?;
                 let yellow = f32_from_bytes(12..16)?;
                 let black = f32_from_bytes(16..20)?;
-                Ok(ColorValue::Cmyk(cyan, magenta, yellow, black))
+                ColorValue::Cmyk(cyan, magenta, yellow, black)
             }
             Some(b"RGB ") => {
                 let red = f32_from_bytes(4..8)?;
                 let green = f32_from_bytes(8..12)?;
                 let blue = f32_from_bytes(12..16)?;
-                Ok(ColorValue::Rgb(red, green, blue))
+                ColorValue::Rgb(red, green, blue)
             }
             Some(b"LAB ") => {
                 // scale L* to be in [0, 100]
                 let l = f32_from_bytes(4..8)? * 100.0;
                 let a = f32_from_bytes(8..12)?;
                 let b = f32_from_bytes(12..16)?;
-                Ok(ColorValue::Lab(l, a, b))
+                ColorValue::Lab(l, a, b)
             }
-            Some(b"Gray") => Ok(ColorValue::Gray(f32_from_bytes(4..8)?)),
-            Some(_) => Err(ASEError::ColorFormat),
-            _ => Err(ASEError::InputDataParseError),
+            Some(b"Gray") => ColorValue::Gray(f32_from_bytes(4..8)?),
+            Some(_) => return Err(ASEError::ColorFormat),
+            _ => return Err(ASEError::InputDataParseError),
+        };
+        Ok((color, recovered.is_some()))
+    }
+}
+
+/// Returns the number of bytes of the values following the code of a color model, which
+/// is matched like by [`ColorValue::parse`].
+pub(crate) fn values_length(code: &[u8], recover_model: bool) -> Option<usize> {
+    let recovered = Some(code)
+        .filter(|_| recover_model)
+        .and_then(recover_model_code);
+    match recovered.unwrap_or(code) {
+        b"CMYK" => Some(16),
+        b"RGB " | b"LAB " => Some(12),
+        b"Gray" => Some(4),
+        _ => None,
+    }
+}
+
+/// Returns the canonical code of a color model, which `code` differs from only in case
+/// or a null character in place of the trailing space.
+///
+/// Exact codes are not recovered.
+fn recover_model_code(code: &[u8]) -> Option<&'static [u8]> {
+    let [first, second, third, last] = code else {
+        return None;
+    };
+    let last = if *last == 0 { b' ' } else { *last };
+    let loose = [*first, *second, *third, last].map(|byte| byte.to_ascii_uppercase());
+    [b"CMYK", b"RGB ", b"LAB ", b"Gray"]
+        .into_iter()
+        .find(|canonical| canonical.map(|byte| byte.to_ascii_uppercase()) == loose)
+        .filter(|canonical| canonical.as_slice() != code)
+        .map(|canonical| canonical.as_slice())
+}
+
+/// Generates a color component in the given range, favoring edge cases.
+#[cfg(feature = "arbitrary")]
+fn arbitrary_component(
+    u: &mut arbitrary::Unstructured<'_>,
+    range: std::ops::RangeInclusive<f32>,
+) -> arbitrary::Result<f32> {
+    let (start, end) = range.into_inner();
+    Ok(match u.int_in_range(0..=7_u8)? {
+        0 => start,
+        1 => end,
+        2 => 0.0,
+        3 => -0.0,
+        // smallest normal and subnormal values
+        4 => f32::MIN_POSITIVE,
+        5 => f32::from_bits(1),
+        _ => {
+            let fraction = u.arbitrary::<u32>()? as f32 / u32::MAX as f32;
+            start + (end - start) * fraction
         }
+    })
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ColorValue {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // allow slightly out of range values, as they are produced by color conversions
+        let unit = -0.5..=1.5;
+        Ok(match u.int_in_range(0..=3_u8)? {
+            0 => ColorValue::Cmyk(
+                arbitrary_component(u, unit.clone())?,
+                arbitrary_component(u, unit.clone())?,
+                arbitrary_component(u, unit.clone())?,
+                arbitrary_component(u, unit)?,
+            ),
+            1 => ColorValue::Rgb(
+                arbitrary_component(u, unit.clone())?,
+                arbitrary_component(u, unit.clone())?,
+                arbitrary_component(u, unit)?,
+            ),
+            2 => ColorValue::Lab(
+                arbitrary_component(u, 0.0..=100.0)?,
+                arbitrary_component(u, -128.0..=127.0)?,
+                arbitrary_component(u, -128.0..=127.0)?,
+            ),
+            _ => ColorValue::Gray(arbitrary_component(u, unit)?),
+        })
     }
 }
 
@@ -111,7 +656,7 @@ mod tests {
         let rgb = ColorValue::Cmyk(0.0, 49.0, 54.0, 25.0);
         let mut buffer = Buffer::with_capacity(20);
         buffer.write_slice(rgb.get_type());
-        rgb.clone().write_values(&mut buffer);
+        rgb.clone().write_values(&mut buffer, LabScaling::default());
         let res = ColorValue::try_from(buffer.into_vec().as_slice());
         assert!(res.is_ok());
         assert_eq!(rgb, res.unwrap());
@@ -122,7 +667,7 @@ mod tests {
         let rgb = ColorValue::Rgb(0.749_019_6, 0.380_392_16, 0.415_686_28);
         let mut buffer = Buffer::with_capacity(20);
         buffer.write_slice(rgb.get_type());
-        rgb.clone().write_values(&mut buffer);
+        rgb.clone().write_values(&mut buffer, LabScaling::default());
         let res = ColorValue::try_from(buffer.into_vec().as_slice());
         assert!(res.is_ok());
         assert_eq!(rgb, res.unwrap());
@@ -133,7 +678,9 @@ mod tests {
         let color = ColorValue::Lab(0.525_823_97, 38.506_775, 12.420_94);
         let mut buffer = Buffer::with_capacity(20);
         buffer.write_slice(color.get_type());
-        color.clone().write_values(&mut buffer);
+        color
+            .clone()
+            .write_values(&mut buffer, LabScaling::default());
         let res = ColorValue::try_from(buffer.into_vec().as_slice());
         assert!(res.is_ok());
         assert_eq!(color, res.unwrap());
@@ -144,12 +691,96 @@ mod tests {
         let gray = ColorValue::Gray(0.749_019_6);
         let mut buffer = Buffer::with_capacity(8);
         buffer.write_slice(gray.get_type());
-        gray.clone().write_values(&mut buffer);
+        gray.clone()
+            .write_values(&mut buffer, LabScaling::default());
         let res = ColorValue::try_from(buffer.into_vec().as_slice());
         assert!(res.is_ok());
         assert_eq!(gray, res.unwrap());
     }
 
+    #[test]
+    fn it_parses_hex_codes() {
+        let aurora = ColorValue::Rgb(0.749_019_6, 0.380_392_16, 0.415_686_28);
+        for hex in ["#BF616A", "#bf616a", "bF616a", "#BF616A80", "BF616AFF"] {
+            assert_eq!(ColorValue::from_hex(hex).unwrap(), aurora, "{hex}");
+        }
+        assert_eq!(
+            ColorValue::from_hex("#fFf").unwrap(),
+            ColorValue::Rgb(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            ColorValue::from_hex("08c").unwrap(),
+            ColorValue::from_hex("#0088CC").unwrap()
+        );
+        assert_eq!(
+            ColorValue::from_hex("#000000").unwrap(),
+            ColorValue::Rgb(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_hex_codes() {
+        for (hex, expected) in [
+            ("", HexError::Length(0)),
+            ("#", HexError::Length(0)),
+            ("#ff80", HexError::Length(4)),
+            ("#ff800", HexError::Length(5)),
+            ("#ff80000", HexError::Length(7)),
+            ("#ff8000000", HexError::Length(9)),
+            ("#ff800g", HexError::Digit('g')),
+            ("##ff8000", HexError::Digit('#')),
+            (" #ff8000", HexError::Digit(' ')),
+            ("#ff 800", HexError::Digit(' ')),
+            ("#ff80٠0", HexError::Digit('٠')),
+        ] {
+            match ColorValue::from_hex(hex) {
+                Err(ASEError::HexColor { hex: input, reason }) => {
+                    assert_eq!((input.as_str(), reason), (hex, expected));
+                }
+                result => panic!("{hex}: {result:?}"),
+            }
+        }
+        assert_eq!(
+            ColorValue::from_hex("#ff80").unwrap_err().to_string(),
+            "Invalid hex color \"#ff80\": Hex code has 4 digits instead of 3, 6 or 8"
+        );
+    }
+
+    #[test]
+    fn it_formats_hex_codes() {
+        let hex = |color: ColorValue| color.to_hex().unwrap();
+        assert_eq!(hex(ColorValue::Rgb(0.5, 0.5, 0.5)), "#808080");
+        assert_eq!(
+            hex(ColorValue::Rgb(127.0 / 255.0, 128.0 / 255.0, 0.499)),
+            "#7f807f"
+        );
+        assert_eq!(
+            hex(ColorValue::Rgb(0.749_019_6, 0.380_392_16, 0.415_686_28)),
+            "#bf616a"
+        );
+        assert_eq!(
+            hex(ColorValue::Rgb(0.921_568_63, 0.796_078_44, 0.545_098_07)),
+            "#ebcb8b"
+        );
+        assert_eq!(hex(ColorValue::Rgb(1.5, -0.2, 1.0)), "#ff00ff");
+        assert_eq!(hex(ColorValue::Cmyk(0.0, 0.0, 0.0, 0.0)), "#ffffff");
+        assert_eq!(hex(ColorValue::Cmyk(0.0, 1.0, 1.0, 0.5)), "#800000");
+        assert_eq!(hex(ColorValue::Gray(0.0)), "#000000");
+        assert_eq!(hex(ColorValue::Gray(0.5)), "#808080");
+        assert_eq!(hex(ColorValue::Lab(0.0, 0.0, 0.0)), "#000000");
+        assert_eq!(hex(ColorValue::Lab(100.0, 0.0, 0.0)), "#ffffff");
+        assert_eq!(hex(ColorValue::Lab(50.0, 0.0, 0.0)), "#777777");
+
+        // every hex code is formatted as it is parsed
+        for value in 0..=255u8 {
+            let code = format!("#{value:02x}{:02x}{value:02x}", 255 - value);
+            assert_eq!(hex(ColorValue::from_hex(&code).unwrap()), code);
+        }
+
+        assert_eq!(ColorValue::Rgb(f32::NAN, 0.0, 0.0).to_hex(), None);
+        assert_eq!(ColorValue::Lab(50.0, f32::INFINITY, 0.0).to_hex(), None);
+    }
+
     #[test]
     fn it_returns_input_data_parse_error() {
         let data = vec![];
@@ -170,6 +801,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_recovers_model_codes() {
+        for (code, canonical) in [
+            (b"rgb ", b"RGB "),
+            (b"Rgb\0", b"RGB "),
+            (b"LAB\0", b"LAB "),
+            (b"cmyk", b"CMYK"),
+            (b"GRAY", b"Gray"),
+        ] {
+            let mut data = code.to_vec();
+            data.extend([0x3f, 0, 0, 0].repeat(4));
+            assert!(matches!(
+                ColorValue::try_from(data.as_slice()),
+                Err(ASEError::ColorFormat)
+            ));
+            let (color, recovered) = ColorValue::parse(&data, true).unwrap();
+            assert!(recovered);
+            assert_eq!(color.get_type(), canonical);
+        }
+        for code in [b"RGB ", b"Gray"] {
+            let mut data = code.to_vec();
+            data.extend([0x3f, 0, 0, 0].repeat(3));
+            assert!(!ColorValue::parse(&data, true).unwrap().1);
+        }
+        for code in [b"CMY\0", b"rgb\t", b"gra\0"] {
+            assert!(matches!(
+                ColorValue::parse(code, true),
+                Err(ASEError::ColorFormat)
+            ));
+        }
+    }
+
     #[test]
     fn it_returns_input_data_parse_error_for_oob() {
         // try check pass in data starting with the correct header, but ending too early
@@ -180,4 +843,19 @@ mod tests {
             "Only ASEError::InputDataParseError should be returned"
         );
     }
+
+    #[test]
+    fn it_converts_between_srgb_and_lab() {
+        let (l, a, b) = ColorValue::Gray(1.0).to_lab();
+        assert!((l - 100.0).abs() < 0.01 && a.abs() < 0.01 && b.abs() < 0.01);
+
+        for color in [
+            ColorValue::Rgb(0.749_019_6, 0.380_392_16, 0.415_686_28),
+            ColorValue::Rgb(0.0, 0.0, 1.0),
+        ] {
+            let (l, a, b) = color.to_lab();
+            let (r, g, b) = ColorValue::Lab(l, a, b).to_srgb();
+            assert!(color.delta_e(&ColorValue::Rgb(r, g, b)) < 0.01, "{color:?}");
+        }
+    }
 }
@@ -0,0 +1,172 @@
+// gradients are only written from groups in memory
+#![allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+
+use std::fmt::Write;
+
+use super::Group;
+
+/// Options for [`Group::to_css_gradient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssGradientOptions {
+    /// The angle or direction of the gradient, like `90deg` or `to bottom`.
+    pub direction: String,
+    /// The relative widths of the colors, colors are spaced evenly if empty.
+    ///
+    /// In a smooth gradient, the weight of a color is the distance to the next color,
+    /// so the weight of the last color is ignored. Missing weights default to `1.0`.
+    pub weights: Vec<f32>,
+    /// Whether each color is shown as a solid band, instead of blending into the next color.
+    pub hard_stops: bool,
+}
+
+impl Default for CssGradientOptions {
+    fn default() -> Self {
+        Self {
+            direction: "90deg".to_owned(),
+            weights: Vec::new(),
+            hard_stops: false,
+        }
+    }
+}
+
+impl Group {
+    /// Returns the colors of the group as a CSS `linear-gradient`.
+    ///
+    /// Colors are converted to sRGB. A group with a single color produces a gradient
+    /// of only that color, a group without colors a transparent one.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue, CssGradientOptions, Group};
+    /// let group = Group::new(
+    ///     "Ramp".to_owned(),
+    ///     vec![
+    ///         ColorBlock::new("Black".to_owned(), ColorValue::Gray(0.0), ColorType::Normal),
+    ///         ColorBlock::new("White".to_owned(), ColorValue::Gray(1.0), ColorType::Normal),
+    ///     ],
+    /// );
+    /// assert_eq!(
+    ///     group.to_css_gradient(&CssGradientOptions::default()),
+    ///     "linear-gradient(90deg, #000000 0%, #ffffff 100%)"
+    /// );
+    /// ```
+    pub fn to_css_gradient(&self, options: &CssGradientOptions) -> String {
+        let colors: Vec<String> = match self.blocks.as_slice() {
+            [] => vec!["transparent".to_owned(); 2],
+            [block] => vec![block.color.to_srgb_hex(); 2],
+            blocks => blocks
+                .iter()
+                .map(|block| block.color.to_srgb_hex())
+                .collect(),
+        };
+        let weight = |index: usize| options.weights.get(index).copied().unwrap_or(1.0);
+
+        // positions of the color boundaries, from 0 to the total weight
+        let mut boundaries = vec![0.0];
+        let bands = if options.hard_stops {
+            colors.len()
+        } else {
+            colors.len() - 1
+        };
+        for index in 0..bands {
+            boundaries.push(boundaries[index] + weight(index));
+        }
+        let total = boundaries[bands];
+        let percent = |boundary: f32| {
+            let percent = if total > 0.0 {
+                boundary / total * 100.0
+            } else {
+                0.0
+            };
+            // at most two decimals, without trailing zeros
+            let percent = format!("{percent:.2}");
+            percent
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+                .to_owned()
+        };
+
+        let mut css = format!("linear-gradient({}", options.direction);
+        for (index, color) in colors.iter().enumerate() {
+            if options.hard_stops {
+                let (start, end) = (boundaries[index], boundaries[index + 1]);
+                write!(css, ", {color} {}% {}%", percent(start), percent(end))
+            } else {
+                write!(css, ", {color} {}%", percent(boundaries[index]))
+            }
+            .expect("writing to a String cannot fail");
+        }
+        css.push(')');
+        css
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::named, ColorValue};
+
+    use super::*;
+
+    fn rgb(r: u8, g: u8, b: u8) -> ColorValue {
+        ColorValue::Rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+    }
+
+    fn aurora() -> Group {
+        Group::new(
+            "Aurora".to_owned(),
+            vec![
+                named("nord11", rgb(0xbf, 0x61, 0x6a)),
+                named("nord12", rgb(0xd0, 0x87, 0x70)),
+                named("nord13", rgb(0xeb, 0xcb, 0x8b)),
+                named("nord14", rgb(0xa3, 0xbe, 0x8c)),
+                named("nord15", rgb(0xb4, 0x8e, 0xad)),
+            ],
+        )
+    }
+
+    #[test]
+    fn it_creates_smooth_gradient() {
+        assert_eq!(
+            aurora().to_css_gradient(&CssGradientOptions::default()),
+            "linear-gradient(90deg, #bf616a 0%, #d08770 25%, #ebcb8b 50%, #a3be8c 75%, #b48ead 100%)"
+        );
+
+        let options = CssGradientOptions {
+            direction: "to bottom".to_owned(),
+            weights: vec![2.0, 1.0, 0.5],
+            ..Default::default()
+        };
+        assert_eq!(
+            aurora().to_css_gradient(&options),
+            "linear-gradient(to bottom, #bf616a 0%, #d08770 44.44%, #ebcb8b 66.67%, #a3be8c 77.78%, #b48ead 100%)"
+        );
+    }
+
+    #[test]
+    fn it_creates_banded_gradient() {
+        let options = CssGradientOptions {
+            hard_stops: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            aurora().to_css_gradient(&options),
+            "linear-gradient(90deg, #bf616a 0% 20%, #d08770 20% 40%, #ebcb8b 40% 60%, #a3be8c 60% 80%, #b48ead 80% 100%)"
+        );
+    }
+
+    #[test]
+    fn it_creates_gradient_for_single_color() {
+        let group = Group::new(
+            "Single".to_owned(),
+            vec![named("gray", ColorValue::Gray(0.5))],
+        );
+        assert_eq!(
+            group.to_css_gradient(&CssGradientOptions::default()),
+            "linear-gradient(90deg, #808080 0%, #808080 100%)"
+        );
+        assert_eq!(
+            Group::default().to_css_gradient(&CssGradientOptions::default()),
+            "linear-gradient(90deg, transparent 0%, transparent 100%)"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+/// Information about an .ase file, as returned by [`read_ase_info`](crate::read_ase_info).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileInfo {
+    /// Major version of the file format.
+    pub version_major: u16,
+    /// Minor version of the file format.
+    pub version_minor: u16,
+    /// The number of blocks declared in the header.
+    pub declared_blocks: u32,
+    /// The number of groups and colors read, including the colors in groups.
+    ///
+    /// This may differ from the declared blocks, as writers disagree on whether
+    /// colors in groups and the ends of groups are counted.
+    pub parsed_blocks: u32,
+    /// The number of bytes read from the file.
+    pub bytes_read: u64,
+}
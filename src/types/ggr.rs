@@ -0,0 +1,151 @@
+#![allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+
+use std::fmt::Write;
+
+use crate::error::{ASEError, BlockError};
+
+use super::Group;
+
+/// Blending function of a segment in a GIMP gradient.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GgrBlend {
+    #[default]
+    Linear,
+    Curved,
+    Sine,
+    SphereIncreasing,
+    SphereDecreasing,
+}
+
+impl From<GgrBlend> for u8 {
+    fn from(value: GgrBlend) -> Self {
+        match value {
+            GgrBlend::Linear => 0,
+            GgrBlend::Curved => 1,
+            GgrBlend::Sine => 2,
+            GgrBlend::SphereIncreasing => 3,
+            GgrBlend::SphereDecreasing => 4,
+        }
+    }
+}
+
+impl Group {
+    /// Returns the colors of the group as a GIMP gradient (`.ggr`).
+    ///
+    /// Each pair of adjacent colors forms one segment, with the colors evenly spaced
+    /// and converted to RGB.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the group has fewer than two colors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue, GgrBlend, Group};
+    /// let group = Group::new(
+    ///     "Ramp".to_owned(),
+    ///     vec![
+    ///         ColorBlock::new("Black".to_owned(), ColorValue::Gray(0.0), ColorType::Normal),
+    ///         ColorBlock::new("White".to_owned(), ColorValue::Gray(1.0), ColorType::Normal),
+    ///     ],
+    /// );
+    /// let ggr = group.to_ggr("Ramp", GgrBlend::Linear).unwrap();
+    /// assert!(ggr.starts_with("GIMP Gradient\nName: Ramp\n1\n"));
+    /// ```
+    pub fn to_ggr(&self, name: &str, blending: GgrBlend) -> Result<String, ASEError> {
+        if self.blocks.len() < 2 {
+            return Err(ASEError::InvalidBlock {
+                name: self.name.clone(),
+                reason: BlockError::TooFewColors,
+            });
+        }
+
+        let segments = self.blocks.len() - 1;
+        let mut ggr = format!(
+            "GIMP Gradient\nName: {}\n{segments}\n",
+            name.replace('\n', " ")
+        );
+        for (i, pair) in self.blocks.windows(2).enumerate() {
+            let left = i as f64 / segments as f64;
+            let right = (i + 1) as f64 / segments as f64;
+            let (r0, g0, b0) = pair[0].color.to_srgb();
+            let (r1, g1, b1) = pair[1].color.to_srgb();
+            // the last two values select RGB as color space and fixed endpoint colors
+            writeln!(
+                ggr,
+                "{left:.6} {:.6} {right:.6} {:.6} {:.6} {:.6} 1.000000 {:.6} {:.6} {:.6} 1.000000 {} 0 0 0",
+                (left + right) / 2.0,
+                r0.clamp(0.0, 1.0),
+                g0.clamp(0.0, 1.0),
+                b0.clamp(0.0, 1.0),
+                r1.clamp(0.0, 1.0),
+                g1.clamp(0.0, 1.0),
+                b1.clamp(0.0, 1.0),
+                u8::from(blending),
+            )
+            .expect("writing to a String cannot fail");
+        }
+        Ok(ggr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::named, ColorValue};
+
+    use super::*;
+
+    /// Returns the name and the segments of the gradient.
+    fn parse_ggr(ggr: &str) -> (&str, Vec<Vec<f64>>) {
+        let mut lines = ggr.lines();
+        assert_eq!(lines.next(), Some("GIMP Gradient"));
+        let name = lines.next().unwrap().strip_prefix("Name: ").unwrap();
+        let count: usize = lines.next().unwrap().parse().unwrap();
+        let segments: Vec<Vec<f64>> = lines
+            .map(|line| line.split(' ').map(|v| v.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(segments.len(), count);
+        (name, segments)
+    }
+
+    #[test]
+    fn it_exports_ramp() {
+        let group = Group::new(
+            "Ramp".to_owned(),
+            vec![
+                named("Red", ColorValue::Rgb(1.0, 0.0, 0.0)),
+                named("Gray", ColorValue::Gray(0.5)),
+                named("Cyan", ColorValue::Cmyk(1.0, 0.0, 0.0, 0.0)),
+            ],
+        );
+        let ggr = group.to_ggr("Brand Ramp", GgrBlend::default()).unwrap();
+        let (name, segments) = parse_ggr(&ggr);
+        assert_eq!(name, "Brand Ramp");
+        assert_eq!(
+            segments,
+            vec![
+                vec![0.0, 0.25, 0.5, 1.0, 0.0, 0.0, 1.0, 0.5, 0.5, 0.5, 1.0, 0.0, 0.0, 0.0, 0.0],
+                vec![0.5, 0.75, 1.0, 0.5, 0.5, 0.5, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+            ]
+        );
+
+        let ggr = group.to_ggr("Brand Ramp", GgrBlend::Sine).unwrap();
+        let (_, segments) = parse_ggr(&ggr);
+        assert!(segments.iter().all(|segment| segment[11] == 2.0));
+    }
+
+    #[test]
+    fn it_rejects_too_few_colors() {
+        let group = Group::new(
+            "Single".to_owned(),
+            vec![named("Red", ColorValue::Gray(0.0))],
+        );
+        assert!(matches!(
+            group.to_ggr("Single", GgrBlend::Linear),
+            Err(ASEError::InvalidBlock {
+                reason: BlockError::TooFewColors,
+                ..
+            })
+        ));
+    }
+}
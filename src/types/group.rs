@@ -1,6 +1,16 @@
-use crate::{buffer::Buffer, error::ASEError};
+use crate::{
+    error::{ASEError, ConformationError},
+    trace::{trace_event, trace_span},
+    wire::{self, Terminator},
+    writer::{name_units, truncate_group_names, GroupLayout, Writer, DEFAULT_OPTIONS},
+};
 
-use super::{block_type::BlockType, ColorBlock};
+use super::{
+    block,
+    block_type::BlockType,
+    color_block::{EntryLayout, MIN_ENTRY_LENGTH},
+    ColorBlock,
+};
 
 /// Represents a named collection of colors
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -15,7 +25,7 @@ pub struct Group {
 ///
 /// This is a workaround for groups with size values that
 /// do not include all color blocks.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum GroupHold {
     /// Colors are being collected into a found parent group.
     HoldingBuilding,
@@ -47,19 +57,64 @@ impl Group {
         Self { name, blocks }
     }
 
-    /// Write the group to the given [`Buffer`]
-    pub(crate) fn write(self, buf: &mut Buffer) {
-        buf.write_u16(BlockType::GroupStart as u16);
-        buf.write_u32(self.calculate_length());
+    /// Encodes the group as it is written to .ase files.
+    ///
+    /// The bytes start with the group start marker and the length of the group, which
+    /// includes its name and colors, and end with the group end marker.
+    ///
+    /// Names with more than `65534` UTF-16 code units are truncated to fit into their length
+    /// field, like by [`create_ase`](crate::create_ase).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::Group;
+    /// let group = Group::new("a".to_owned(), vec![]);
+    /// assert_eq!(group.to_bytes(), vec![0xc0, 0x01, 0, 0, 0, 6, 0, 2, 0, 97, 0, 0, 0xc0, 0x02]);
+    /// ```
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new(
+            self.calculate_length() as usize + 2 + 4 + 2,
+            &DEFAULT_OPTIONS,
+        );
+        if self.write(&mut writer).is_err() {
+            // names are written as they are by default, once they fit
+            let mut group = self.clone();
+            truncate_group_names(&mut group);
+            return group.to_bytes();
+        }
+        writer.into_vec()
+    }
+
+    /// Write the group using the given [`Writer`]
+    ///
+    /// # Errors
+    /// This function will return an error if the name of the group, or one of its
+    /// colors cannot be written.
+    pub(crate) fn write(&self, writer: &mut Writer) -> Result<(), ASEError> {
+        writer.buf.write_u16(BlockType::GroupStart.into());
+        match writer.options.group_layout {
+            GroupLayout::Nested => writer.write_with_length(|writer| {
+                writer.write_name(&self.name)?;
 
-        // name length, +1 for null terminator
-        buf.write_u16(self.name.len() as u16 + 1);
-        buf.write_null_terminated_utf_16_str(&self.name);
+                // write colors
+                for block in &self.blocks {
+                    block.write(writer)?;
+                }
+                Ok(())
+            })?,
+            GroupLayout::Flat => {
+                writer.write_with_length(|writer| writer.write_name(&self.name))?;
 
-        // write colors
-        self.blocks.into_iter().for_each(|block| block.write(buf));
+                // write colors
+                for block in &self.blocks {
+                    block.write(writer)?;
+                }
+            }
+        }
 
-        buf.write_u16(BlockType::GroupEnd as u16);
+        writer.write_group_end();
+        Ok(())
     }
 
     /// Calculate the length of an group.
@@ -69,8 +124,9 @@ impl Group {
     ///  - name * 2 (UTF 16) + null terminator (2)
     ///  - color entry type (2)
     ///  - color entry length
+    #[allow(clippy::arithmetic_side_effects)]
     pub(crate) fn calculate_length(&self) -> u32 {
-        2 + self.name.len() as u32 * 2
+        2 + name_units(&self.name) as u32 * 2
             + 2
             + self
                 .blocks
@@ -80,74 +136,400 @@ impl Group {
                 .sum::<u32>()
     }
 
-    /// Parses a [`Group`] from bytes.
+    /// Parses a [`Group`] from the data of a group start block.
     ///
-    /// This will extract the name and than try to parse the left-over bytes
-    /// as [`ColorBlock`]s. It stops when either the given bytes are 'empty',parsing a [`ColorBlock`]
-    /// fails or the next block is not a [`ColorBlock`].
+    /// The data follows the type and length of the block: the length of the name and
+    /// the null terminated UTF-16 name, optionally followed by color entry blocks, each
+    /// including its type and length. The colors end when either the data ends, a color
+    /// cannot be parsed by [`ColorBlock::parse`] or the next block is not a color entry.
+    ///
+    /// Groups written by Adobe applications store their colors after the group start
+    /// block instead, these are returned without colors. Use [`Group::from_bytes`] to
+    /// parse a group including its colors and `GroupEnd` block.
     ///
     /// # Errors
-    /// This function will return an error if either the name cannot be constructed, or
-    /// if it cannot be correctly parsed. In either case an [`ASEError::Invalid`] is returned.
-    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, ASEError> {
-        let name_length = u16::from_be_bytes(
-            bytes
-                .get(0..2)
-                .ok_or(ASEError::InputDataParseError)?
-                .try_into()?,
-        );
-        // read name bytes, but stop before not byte
-        let name_bytes: Vec<u16> = bytes
-            .get(2..(name_length as usize * 2))
-            .ok_or(ASEError::InputDataParseError)?
-            .chunks_exact(2)
-            .map(|bytes| u16::from_be_bytes(bytes.try_into().unwrap()))
-            .collect();
-        let name = String::from_utf16(&name_bytes)?;
-
-        let mut pointer = name_length as usize * 2 + 2;
-        let mut blocks = Vec::new();
-        loop {
-            if pointer >= bytes.len() - 1 {
-                break;
+    /// This function will return an [`ASEError::InputDataParseError`] if the name or a
+    /// block header is truncated, an [`ASEError::UTF16Error`] if the name is not valid
+    /// UTF-16 and an [`ASEError::BlockTypeError`] if a block following the name has an
+    /// unknown type.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue, Group};
+    /// let color = ColorBlock::new("b".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// // name "a", followed by a color entry
+    /// let mut data = vec![0, 2, 0, 97, 0, 0];
+    /// data.extend(color.to_bytes());
+    /// assert_eq!(
+    ///     Group::parse(&data).unwrap(),
+    ///     Group::new("a".to_owned(), vec![color])
+    /// );
+    /// ```
+    pub fn parse(bytes: &[u8]) -> Result<Self, ASEError> {
+        Self::parse_with_entries(bytes, MalformedEntry::Stop, |_, _| {})
+    }
+
+    /// Parses a [`Group`] from the data of a group start block, like [`Group::parse`],
+    /// requiring that all of the data is read.
+    ///
+    /// # Errors
+    /// This function will return the errors of [`Group::parse`] and an
+    /// [`ConformationError::GroupData`] naming the group and the position within the data,
+    /// if the colors end before the data, like at a color, which cannot be parsed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ASEError, ColorBlock, ColorType, ColorValue, ConformationError, Group};
+    /// let color = ColorBlock::new("b".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// // name "a", followed by a color entry and a group end
+    /// let mut data = vec![0, 2, 0, 97, 0, 0];
+    /// data.extend(color.to_bytes());
+    /// assert_eq!(Group::parse_strict(&data).unwrap().blocks, vec![color]);
+    ///
+    /// data.extend([0xc0, 0x02]);
+    /// assert!(matches!(
+    ///     Group::parse_strict(&data),
+    ///     Err(ASEError::Invalid(ConformationError::GroupData { position: 28, .. }))
+    /// ));
+    /// ```
+    pub fn parse_strict(bytes: &[u8]) -> Result<Self, ASEError> {
+        let mut name = String::new();
+        let mut entries = Self::parse_into(bytes, &mut name)?;
+        let blocks =
+            entries.parse_colors(MalformedEntry::Stop, ColorBlock::parse_entry, |_, _| {})?;
+        if entries.end() < bytes.len() {
+            return Err(ASEError::Invalid(ConformationError::GroupData {
+                group: name,
+                position: entries.end() as u64,
+            }));
+        }
+        Ok(Self::new(name, blocks))
+    }
+
+    /// Parses a [`Group`] from a group start block, its colors and the `GroupEnd` block,
+    /// as returned by [`Group::to_bytes`].
+    ///
+    /// The group start block starts with its type and length, followed by the data read
+    /// by [`Group::parse`]. If it does not contain any colors, the colors may follow it
+    /// as separate color entry blocks, like Adobe applications write them.
+    ///
+    /// # Errors
+    /// This function will return an [`ASEError::BlockTypeError`] if the bytes do not start
+    /// with a group start block, an [`ASEError::Invalid`] if a block is truncated, the
+    /// `GroupEnd` block is missing or followed by more data, and the errors of
+    /// [`Group::parse`] and [`ColorBlock::parse`] otherwise. Unlike [`Group::parse`], colors,
+    /// which cannot be parsed, are returned as errors.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorBlock, ColorType, ColorValue, Group};
+    /// let color = ColorBlock::new("b".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// let group = Group::new("a".to_owned(), vec![color.clone()]);
+    /// assert_eq!(Group::from_bytes(&group.to_bytes()).unwrap(), group);
+    ///
+    /// // the group start block only contains the name
+    /// let mut bytes = vec![0xc0, 0x01, 0, 0, 0, 6, 0, 2, 0, 97, 0, 0];
+    /// bytes.extend(color.to_bytes());
+    /// bytes.extend([0xc0, 0x02]);
+    /// assert_eq!(Group::from_bytes(&bytes).unwrap(), group);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ASEError> {
+        let (data, mut rest) = block::split_block(bytes, BlockType::GroupStart)?;
+        let mut group = Self::parse_with_entries(data, MalformedEntry::Fail, |_, _| {})?;
+        if group.blocks.is_empty() {
+            while wire::get_u16_be(rest).ok() == Some(BlockType::ColorEntry.into()) {
+                let (data, next) = block::split_block(rest, BlockType::ColorEntry)?;
+                match ColorBlock::parse(data) {
+                    Ok(block) => group.blocks.push(block),
+                    Err(err) => return Err(err.in_swatch(group.name)),
+                }
+                rest = next;
             }
+        }
+        if wire::get_u16_be(rest).ok() != Some(BlockType::GroupEnd.into()) {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+        block::reject_trailing(rest.get(2..).unwrap_or_default())?;
+        Ok(group)
+    }
+
+    /// Parses a [`Group`] from bytes, like [`Group::parse`], handling color entries,
+    /// which cannot be read, as given by `malformed`.
+    ///
+    /// `inspect` is called with each color entry, which is read or skipped, and the
+    /// layout of the entries, which are read.
+    ///
+    /// # Errors
+    /// This function will return an error if the name cannot be constructed.
+    pub(crate) fn parse_with_entries(
+        bytes: &[u8],
+        malformed: MalformedEntry,
+        inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<Self, ASEError> {
+        let mut name = String::new();
+        let blocks = match Self::parse_into(bytes, &mut name)?.parse_colors(
+            malformed,
+            ColorBlock::parse_entry,
+            inspect,
+        ) {
+            Ok(blocks) => blocks,
+            Err(err) => return Err(err.in_swatch(name)),
+        };
+        Ok(Self::new(name, blocks))
+    }
+
+    /// Parses the name of a [`Group`] into `name`, returning the color entries following it.
+    ///
+    /// # Errors
+    /// This function will return an error if the name cannot be constructed.
+    pub(crate) fn parse_into<'a>(
+        bytes: &'a [u8],
+        name: &mut String,
+    ) -> Result<ColorEntries<'a>, ASEError> {
+        let group_name = GroupName::parse(bytes)?;
+        wire::decode_utf16_units_into(group_name.units, name)?;
+        Ok(ColorEntries::new(bytes, group_name.end))
+    }
+}
+
+/// How color entries within a group block, which cannot be read, are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MalformedEntry {
+    /// The colors of the group end before the entry.
+    Stop,
+    /// The entry is skipped using its length.
+    Skip,
+    /// Parsing the group fails.
+    Fail,
+}
+
+/// The name at the start of the data of a group block.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GroupName<'a> {
+    /// The UTF-16 code units of the name, up to its null terminator.
+    pub(crate) units: &'a [u8],
+    /// Offset of the data following the name.
+    pub(crate) end: usize,
+    /// Where the null terminator of the name is found.
+    pub(crate) terminator: Terminator,
+}
+
+impl<'a> GroupName<'a> {
+    /// Parses the name at the start of `bytes`.
+    ///
+    /// Some applications omit the null terminator of group names, writing either the
+    /// number of characters or a length including the missing terminator. If a color
+    /// entry or the end of the data takes the place of the terminator, the length includes
+    /// it, otherwise the name continues up to the length. Names followed by a terminator,
+    /// which is not counted by the length, are read as well.
+    ///
+    /// # Errors
+    /// This function will return an error if the name is truncated.
+    pub(crate) fn parse(bytes: &'a [u8]) -> Result<Self, ASEError> {
+        let (units, end) = wire::get_utf16_nul_units(bytes)?;
+        // the terminator is the last code unit counted by the length
+        let terminator = end.saturating_sub(2);
+        if units.len() < terminator.saturating_sub(2) || bytes.get(terminator..end) == Some(&[0, 0])
+        {
+            return Ok(Self {
+                units,
+                end,
+                terminator: Terminator::Counted,
+            });
+        }
+
+        let next = bytes.get(terminator..);
+        if next.map_or(true, |next| {
+            next.len() < 2 || wire::get_u16_be(next).ok() == Some(BlockType::ColorEntry.into())
+        }) {
+            return Ok(Self {
+                units,
+                end: terminator,
+                terminator: Terminator::Missing,
+            });
+        }
+        if let Some((units, end)) = wire::get_utf16_uncounted_nul_units(bytes) {
+            return Ok(Self {
+                units,
+                end,
+                terminator: Terminator::Uncounted,
+            });
+        }
+        Ok(Self {
+            units: bytes.get(2..end).ok_or(ASEError::InputDataParseError)?,
+            end,
+            terminator: Terminator::Missing,
+        })
+    }
+}
+
+/// A color entry nested in the data of a group block.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ColorEntry<'a> {
+    /// Offset of the block identifier from the start of the group data.
+    pub(crate) offset: usize,
+    /// The length declared by the block.
+    pub(crate) length: u32,
+    /// The block data, which extends until the end of the group.
+    pub(crate) data: &'a [u8],
+}
+
+/// The color entries nested in the data of a group block.
+///
+/// Yields each entry, until the data is 'empty' or the next block is not a [`ColorBlock`].
+#[derive(Debug, Clone)]
+pub(crate) struct ColorEntries<'a> {
+    bytes: &'a [u8],
+    pointer: usize,
+    /// Whether entries, whose declared length is too small, continue after their color.
+    derive_lengths: bool,
+}
+
+impl<'a> ColorEntries<'a> {
+    /// Returns the color entries starting at `pointer`, directly after the group name.
+    pub(crate) fn new(bytes: &'a [u8], pointer: usize) -> Self {
+        Self {
+            bytes,
+            pointer,
+            derive_lengths: false,
+        }
+    }
 
-            let block_type = BlockType::try_from(u16::from_be_bytes(
-                bytes
-                    .get(pointer..(pointer + 2))
-                    .ok_or(ASEError::InputDataParseError)?
-                    .try_into()?,
-            ))?;
+    /// Sets whether entries, whose declared length is smaller than any color entry, are
+    /// followed by the next entry directly after their color type, when they are parsed
+    /// by [`Self::parse_colors`].
+    pub(crate) fn derive_lengths(mut self, derive_lengths: bool) -> Self {
+        self.derive_lengths = derive_lengths;
+        self
+    }
 
-            if block_type != BlockType::ColorEntry {
-                break;
+    /// Parses the data of each entry using `parse`, handling entries, which cannot be
+    /// read, as given by `malformed`.
+    ///
+    /// `inspect` is called like by [`Group::parse_with_entries`].
+    pub(crate) fn parse_colors<C>(
+        &mut self,
+        malformed: MalformedEntry,
+        mut parse: impl FnMut(&[u8]) -> Result<(C, EntryLayout), ASEError>,
+        mut inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<Vec<C>, ASEError> {
+        let mut colors = Vec::new();
+        while let Some(entry) = self.next() {
+            let entry = entry?;
+            trace_span!(
+                _span,
+                "color entry",
+                offset = entry.offset,
+                length = entry.length
+            );
+            let derived = self.derive_lengths && entry.length < MIN_ENTRY_LENGTH;
+            match (parse(entry.data), malformed) {
+                (Ok((color, layout)), _) => {
+                    if derived {
+                        trace_event!(
+                            length = layout.length,
+                            "derived the length of the entry from its data"
+                        );
+                        // the entry data follows its identifier and length
+                        self.pointer = entry.offset.saturating_add(6).saturating_add(layout.length);
+                    }
+                    inspect(&entry, Some(&layout));
+                    colors.push(color);
+                }
+                (Err(_), MalformedEntry::Stop) => {
+                    trace_event!("stopped reading the entries at a malformed entry");
+                    // the entry is left unread
+                    self.pointer = entry.offset;
+                    break;
+                }
+                // the end of the entry is unknown, so the following entries cannot be found
+                (Err(_), MalformedEntry::Skip) if derived => {
+                    trace_event!("skipped a malformed entry of unknown length and the rest");
+                    inspect(&entry, None);
+                    break;
+                }
+                (Err(_), MalformedEntry::Skip) => {
+                    trace_event!("skipped a malformed entry");
+                    inspect(&entry, None);
+                }
+                (Err(err), MalformedEntry::Fail) => return Err(err),
             }
-            pointer += 2;
-
-            let block_length = u32::from_be_bytes(
-                bytes
-                    .get(pointer..(pointer + 4))
-                    .ok_or(ASEError::InputDataParseError)?
-                    .try_into()?,
-            ) as usize;
-            pointer += 4;
-
-            let Ok(block) =
-                ColorBlock::parse(bytes.get(pointer..).ok_or(ASEError::InputDataParseError)?)
-            else {
-                break;
-            };
-            pointer += block_length;
-            blocks.push(block);
         }
+        Ok(colors)
+    }
+
+    /// Returns the offset in the data, at which the entries read or skipped by
+    /// [`Self::parse_colors`] end.
+    ///
+    /// Any data from this offset on is not part of the colors, like an entry, which
+    /// cannot be read, and the entries following it.
+    pub(crate) fn end(&self) -> usize {
+        self.pointer.min(self.bytes.len())
+    }
+
+    fn next_entry(&mut self) -> Result<Option<ColorEntry<'a>>, ASEError> {
+        // stop if there are not enough bytes left for another block type
+        if self.pointer.saturating_add(2) > self.bytes.len() {
+            return Ok(None);
+        }
+
+        let block_type = BlockType::parse(wire::get_u16_be(
+            self.bytes
+                .get(self.pointer..)
+                .ok_or(ASEError::InputDataParseError)?,
+        )?)?;
+
+        if block_type != BlockType::ColorEntry {
+            return Ok(None);
+        }
+        let offset = self.pointer;
+        self.pointer = self.pointer.saturating_add(2);
+
+        let block_length = wire::get_u32_be(
+            self.bytes
+                .get(self.pointer..)
+                .ok_or(ASEError::InputDataParseError)?,
+        )?;
+        self.pointer = self.pointer.saturating_add(4);
+
+        let data = self
+            .bytes
+            .get(self.pointer..)
+            .ok_or(ASEError::InputDataParseError)?;
+        self.pointer = self.pointer.saturating_add(block_length as usize);
+        Ok(Some(ColorEntry {
+            offset,
+            length: block_length,
+            data,
+        }))
+    }
+}
+
+impl<'a> Iterator for ColorEntries<'a> {
+    type Item = Result<ColorEntry<'a>, ASEError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.next_entry();
+        if entry.is_err() {
+            // do not continue after malformed data
+            self.pointer = self.bytes.len();
+        }
+        entry.transpose()
+    }
+}
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Group {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let name = super::arbitrary_name(u)?;
+        let blocks = u.arbitrary_iter()?.take(16).collect::<Result<_, _>>()?;
         Ok(Self::new(name, blocks))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ColorType, ColorValue};
+    use crate::{test_util::named, ColorValue, WriteOptions};
 
     use super::*;
 
@@ -156,16 +538,8 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
         assert_eq!(group.calculate_length(), 108);
@@ -176,31 +550,22 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
-        let mut buf = Buffer::with_capacity(108);
-        group.write(&mut buf);
-        assert_eq!(
-            buf.into_vec(),
-            vec![
-                192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0, 114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110,
-                0, 97, 0, 109, 0, 101, 0, 0, 0, 1, 0, 0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0,
-                104, 0, 116, 0, 32, 0, 103, 0, 114, 0, 101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0,
-                0, 0, 0, 2, 0, 1, 0, 0, 0, 38, 0, 9, 0, 100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114,
-                0, 101, 0, 100, 0, 0, 82, 71, 66, 32, 63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204,
-                205, 0, 2, 192, 2
-            ]
-        );
+        let options = WriteOptions::default();
+        let mut writer = Writer::new(108, &options);
+        group.write(&mut writer).unwrap();
+        let bytes = vec![
+            192, 1, 0, 0, 0, 108, 0, 11, 0, 103, 0, 114, 0, 111, 0, 117, 0, 112, 0, 32, 0, 110, 0,
+            97, 0, 109, 0, 101, 0, 0, 0, 1, 0, 0, 0, 34, 0, 11, 0, 108, 0, 105, 0, 103, 0, 104, 0,
+            116, 0, 32, 0, 103, 0, 114, 0, 101, 0, 121, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2,
+            0, 1, 0, 0, 0, 38, 0, 9, 0, 100, 0, 97, 0, 114, 0, 107, 0, 32, 0, 114, 0, 101, 0, 100,
+            0, 0, 82, 71, 66, 32, 63, 0, 0, 0, 62, 153, 153, 154, 61, 204, 204, 205, 0, 2, 192, 2,
+        ];
+        assert_eq!(writer.into_vec(), bytes);
+        assert_eq!(group.to_bytes(), bytes);
     }
 
     #[test]
@@ -208,16 +573,8 @@ mod tests {
         let group = Group::new(
             "group name".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
         assert_eq!(
@@ -233,21 +590,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_reads_whole_groups() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let nested = group.to_bytes();
+        assert_eq!(Group::from_bytes(&nested).unwrap(), group);
+
+        let options = WriteOptions {
+            group_layout: GroupLayout::Flat,
+            ..Default::default()
+        };
+        let mut writer = Writer::new(0, &options);
+        group.write(&mut writer).unwrap();
+        let flat = writer.into_vec();
+        assert_ne!(flat, nested);
+        assert_eq!(Group::from_bytes(&flat).unwrap(), group);
+
+        assert!(matches!(
+            Group::from_bytes(&nested[..nested.len() - 2]),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+        let mut trailing = nested.clone();
+        trailing.extend([0, 0]);
+        assert!(matches!(
+            Group::from_bytes(&trailing),
+            Err(ASEError::Invalid(ConformationError::TrailingData {
+                length: 2
+            }))
+        ));
+        // colors, which cannot be read, are not skipped
+        let mut malformed = nested;
+        let color_type = malformed.len() - 3;
+        malformed[color_type] = 9;
+        assert!(matches!(
+            Group::from_bytes(&malformed).map_err(|err| err.root_cause().to_string()),
+            Err(err) if err == ASEError::ColorTypeError.to_string()
+        ));
+    }
+
+    #[test]
+    fn it_reads_names_without_terminator() {
+        let color = named("a", ColorValue::Gray(0.5));
+        let entry = color.to_bytes();
+        for length in [2, 3] {
+            // the length counts either the characters or the missing terminator
+            let mut bytes = vec![0, length, 0, b'a', 0, b'b'];
+            bytes.extend(&entry);
+            let name = GroupName::parse(&bytes).unwrap();
+            assert_eq!(name.terminator, Terminator::Missing);
+            assert_eq!(name.end, 6);
+            assert_eq!(
+                Group::parse(&bytes).unwrap(),
+                Group::new("ab".to_owned(), vec![color.clone()])
+            );
+        }
+        assert_eq!(
+            GroupName::parse(&[0, 2, 0, b'a', 0, 0]).unwrap().terminator,
+            Terminator::Counted
+        );
+    }
+
     #[test]
     fn it_reads_empty_name_correctly() {
         let group = Group::new(
             "".to_owned(),
             vec![
-                ColorBlock::new(
-                    "light grey".to_owned(),
-                    ColorValue::Gray(0.5),
-                    ColorType::Normal,
-                ),
-                ColorBlock::new(
-                    "dark red".to_owned(),
-                    ColorValue::Rgb(0.5, 0.3, 0.1),
-                    ColorType::Normal,
-                ),
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
             ],
         );
         assert_eq!(
@@ -306,11 +721,7 @@ mod tests {
     fn it_returns_on_invalid_block_type() {
         let group = Group::new(
             "".to_owned(),
-            vec![ColorBlock::new(
-                "light grey".to_owned(),
-                ColorValue::Gray(0.5),
-                ColorType::Normal,
-            )],
+            vec![named("light grey", ColorValue::Gray(0.5))],
         );
         assert_eq!(
             group,
@@ -331,11 +742,7 @@ mod tests {
     fn it_returns_on_invalid_block() {
         let group = Group::new(
             "".to_owned(),
-            vec![ColorBlock::new(
-                "light grey".to_owned(),
-                ColorValue::Gray(0.5),
-                ColorType::Normal,
-            )],
+            vec![named("light grey", ColorValue::Gray(0.5))],
         );
         assert_eq!(
             group,
@@ -350,6 +757,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_rejects_unread_data_strictly() {
+        let light_grey = named("light grey", ColorValue::Gray(0.5)).to_bytes();
+        let dark_red = named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)).to_bytes();
+        let mut bytes = vec![0, 2, 0, 97, 0, 0];
+        bytes.extend(&light_grey);
+        bytes.extend(&dark_red);
+        assert_eq!(Group::parse_strict(&bytes).unwrap().blocks.len(), 2);
+
+        // a truncated color and a block, which is not a color entry, end the colors
+        let truncated = bytes.len() - 10;
+        let mut group_end = bytes.clone();
+        group_end.extend([0xc0, 2]);
+        for (bytes, position) in [
+            (&bytes[..truncated], 6 + light_grey.len()),
+            (&group_end[..], bytes.len()),
+        ] {
+            assert_eq!(Group::parse(bytes).unwrap().name, "a");
+            match Group::parse_strict(bytes) {
+                Err(ASEError::Invalid(ConformationError::GroupData {
+                    group,
+                    position: read,
+                })) => assert_eq!((group.as_str(), read), ("a", position as u64)),
+                result => panic!("unexpected result: {result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_skips_invalid_block() {
+        let first = named("first", ColorValue::Gray(0.5));
+        let last = named("last", ColorValue::Gray(0.25));
+        let mut invalid = named("invalid", ColorValue::Gray(0.75)).to_bytes();
+        // replace the color model
+        let model = invalid.len() - 10;
+        invalid[model..model + 4].copy_from_slice(b"XXXX");
+        let mut bytes = vec![0, 1, 0, 0];
+        bytes.extend(first.to_bytes());
+        bytes.extend(invalid);
+        bytes.extend(last.to_bytes());
+
+        let mut skipped = Vec::new();
+        let group = Group::parse_with_entries(&bytes, MalformedEntry::Skip, |entry, layout| {
+            if layout.is_none() {
+                skipped.push(entry.offset);
+            }
+        })
+        .unwrap();
+        assert_eq!(group.blocks, vec![first.clone(), last]);
+        assert_eq!(skipped, vec![4 + 30]);
+
+        assert_eq!(Group::parse(&bytes).unwrap().blocks, vec![first]);
+        assert!(matches!(
+            Group::parse_with_entries(&bytes, MalformedEntry::Fail, |_, _| {})
+                .map_err(|err| err.root_cause().to_string()),
+            Err(err) if err == ASEError::ColorFormat.to_string()
+        ));
+    }
+
     #[test]
     fn it_returns_error_on_invalid_block_length() {
         let parser_result = Group::parse(&[
@@ -370,11 +836,7 @@ mod tests {
     fn it_returns_block_on_invalid_block_length() {
         let group = Group::new(
             "".to_owned(),
-            vec![ColorBlock::new(
-                "light grey".to_owned(),
-                ColorValue::Gray(0.5),
-                ColorType::Normal,
-            )],
+            vec![named("light grey", ColorValue::Gray(0.5))],
         );
         let parser_result = Group::parse(&[
             // has block length of `34`, replacing it with invalid length of 130
@@ -0,0 +1,13 @@
+/// The header of an .ase file, as returned by [`read_ase_header`](crate::read_ase_header).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AseHeader {
+    /// Major version of the file format.
+    pub version_major: u16,
+    /// Minor version of the file format.
+    pub version_minor: u16,
+    /// The number of blocks declared in the header.
+    ///
+    /// Writers disagree on whether colors in groups and the ends of groups are counted,
+    /// so this is not necessarily the number of colors.
+    pub block_count: u32,
+}
@@ -0,0 +1,170 @@
+use super::ColorValue;
+
+/// Colors, whose channels differ by less than a single 8-bit step, have no hue.
+const HUELESS_TOLERANCE: f32 = 1.0 / 255.0;
+
+/// One of twelve 30° wide ranges of hue, centered on the primary, secondary and
+/// tertiary colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HueBucket {
+    /// Hue from 345° to 15°.
+    Red,
+    /// Hue from 15° to 45°.
+    Orange,
+    /// Hue from 45° to 75°.
+    Yellow,
+    /// Hue from 75° to 105°.
+    Chartreuse,
+    /// Hue from 105° to 135°.
+    Green,
+    /// Hue from 135° to 165°.
+    SpringGreen,
+    /// Hue from 165° to 195°.
+    Cyan,
+    /// Hue from 195° to 225°.
+    Azure,
+    /// Hue from 225° to 255°.
+    Blue,
+    /// Hue from 255° to 285°.
+    Violet,
+    /// Hue from 285° to 315°.
+    Magenta,
+    /// Hue from 315° to 345°.
+    Rose,
+}
+
+impl HueBucket {
+    /// All buckets, ordered by their hue.
+    const ALL: [HueBucket; 12] = [
+        HueBucket::Red,
+        HueBucket::Orange,
+        HueBucket::Yellow,
+        HueBucket::Chartreuse,
+        HueBucket::Green,
+        HueBucket::SpringGreen,
+        HueBucket::Cyan,
+        HueBucket::Azure,
+        HueBucket::Blue,
+        HueBucket::Violet,
+        HueBucket::Magenta,
+        HueBucket::Rose,
+    ];
+
+    /// Returns the bucket containing the given hue in degrees.
+    #[allow(clippy::indexing_slicing)]
+    fn from_hue(hue: f32) -> Self {
+        let index = ((hue + 15.0).rem_euclid(360.0) / 30.0) as usize;
+        Self::ALL[index.min(11)]
+    }
+}
+
+impl ColorValue {
+    /// Returns `true` if the color is a shade of gray.
+    ///
+    /// Gray colors are always achromatic, RGB and CMYK colors if their (colored) channels
+    /// differ by at most `tolerance`. Lab colors are achromatic if their chroma is at most
+    /// `tolerance * 100`, as L* spans `0..=100`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::ColorValue;
+    /// assert!(ColorValue::Rgb(0.5, 0.51, 0.5).is_achromatic(0.02));
+    /// assert!(!ColorValue::Rgb(0.5, 0.51, 0.5).is_achromatic(0.001));
+    /// assert!(ColorValue::Lab(50.0, 0.5, -0.5).is_achromatic(0.01));
+    /// ```
+    pub fn is_achromatic(&self, tolerance: f32) -> bool {
+        let spread = |a: f32, b: f32, c: f32| a.max(b).max(c) - a.min(b).min(c);
+        match *self {
+            ColorValue::Gray(_) => true,
+            ColorValue::Rgb(r, g, b) => spread(r, g, b) <= tolerance,
+            ColorValue::Cmyk(c, m, y, _) => spread(c, m, y) <= tolerance,
+            ColorValue::Lab(_, a, b) => a.hypot(b) <= tolerance * 100.0,
+        }
+    }
+
+    /// Returns the bucket containing the hue of the color, or `None` for achromatic colors.
+    ///
+    /// The hue is that of the color in sRGB, as used by HSL. CMYK colors are converted
+    /// without a color profile, Lab colors are assumed to use a D50 white point.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{ColorValue, HueBucket};
+    /// assert_eq!(ColorValue::Rgb(1.0, 0.5, 0.0).hue_bucket(), Some(HueBucket::Orange));
+    /// assert_eq!(ColorValue::Gray(0.5).hue_bucket(), None);
+    /// ```
+    pub fn hue_bucket(&self) -> Option<HueBucket> {
+        if self.is_achromatic(HUELESS_TOLERANCE) {
+            return None;
+        }
+        let (r, g, b) = self.to_srgb();
+        hue(r, g, b).map(HueBucket::from_hue)
+    }
+}
+
+/// Returns the HSL hue of the color in degrees, or `None` if all channels are equal.
+fn hue(r: f32, g: f32, b: f32) -> Option<f32> {
+    let max = r.max(g).max(b);
+    let chroma = max - r.min(g).min(b);
+    if chroma <= 0.0 {
+        return None;
+    }
+    let sector = if max == r {
+        (g - b) / chroma
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    Some((sector * 60.0).rem_euclid(360.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_classifies_primaries_and_secondaries() {
+        let colors = [
+            (ColorValue::Rgb(1.0, 0.0, 0.0), HueBucket::Red),
+            (ColorValue::Rgb(1.0, 1.0, 0.0), HueBucket::Yellow),
+            (ColorValue::Rgb(0.0, 1.0, 0.0), HueBucket::Green),
+            (ColorValue::Rgb(0.0, 1.0, 1.0), HueBucket::Cyan),
+            (ColorValue::Rgb(0.0, 0.0, 1.0), HueBucket::Blue),
+            (ColorValue::Rgb(1.0, 0.0, 1.0), HueBucket::Magenta),
+            (ColorValue::Rgb(1.0, 0.0, 0.1), HueBucket::Red),
+            (ColorValue::Cmyk(1.0, 0.0, 0.0, 0.0), HueBucket::Cyan),
+            (ColorValue::Cmyk(0.0, 1.0, 0.0, 0.5), HueBucket::Magenta),
+            (ColorValue::Cmyk(0.0, 0.0, 1.0, 0.0), HueBucket::Yellow),
+            // sRGB red, green and blue
+            (ColorValue::Lab(54.29, 80.81, 69.89), HueBucket::Red),
+            (ColorValue::Lab(87.82, -79.29, 80.99), HueBucket::Green),
+            (ColorValue::Lab(29.57, 68.3, -112.03), HueBucket::Blue),
+        ];
+        for (color, bucket) in colors {
+            assert_eq!(color.hue_bucket(), Some(bucket), "{color:?}");
+            assert!(!color.is_achromatic(0.01), "{color:?}");
+        }
+    }
+
+    #[test]
+    fn it_classifies_achromatic_colors() {
+        assert!(ColorValue::Gray(0.3).is_achromatic(0.0));
+        assert_eq!(ColorValue::Gray(0.3).hue_bucket(), None);
+
+        // near gray
+        let color = ColorValue::Rgb(0.5, 0.52, 0.5);
+        assert!(color.is_achromatic(0.05));
+        assert!(!color.is_achromatic(0.01));
+        assert_eq!(color.hue_bucket(), Some(HueBucket::Green));
+        assert!(ColorValue::Cmyk(0.1, 0.1, 0.1, 0.5).is_achromatic(0.0));
+        assert_eq!(ColorValue::Rgb(0.5, 0.5, 0.501).hue_bucket(), None);
+
+        // Lab neutral
+        let color = ColorValue::Lab(50.0, 0.0, 0.0);
+        assert!(color.is_achromatic(0.0));
+        assert_eq!(color.hue_bucket(), None);
+        assert!(ColorValue::Lab(50.0, 3.0, 4.0).is_achromatic(0.05));
+        assert!(!ColorValue::Lab(50.0, 3.0, 4.0).is_achromatic(0.04));
+    }
+}
@@ -1,19 +1,67 @@
+mod ase;
+mod block;
+mod block_ref;
 mod block_type;
+mod bulk;
 mod color_block;
 mod color_type;
 mod color_value;
+mod css;
+mod file_info;
+mod ggr;
 mod group;
+mod header;
+mod hue;
+mod packed;
+mod path;
+mod provenance;
+mod set_ops;
 
-pub(super) use block_type::BlockType;
+pub(crate) use ase::{merge_groups, sanitize_name, sanitize_names};
+pub use ase::{Ase, NameMatch};
+pub use block::{Block, RawBlock};
+pub use block_ref::{ColorBlockRef, GroupRef};
+pub use block_type::BlockType;
+pub use bulk::ContrastPair;
 pub use color_block::ColorBlock;
+pub(crate) use color_block::{EntryLayout, MIN_ENTRY_LENGTH};
 pub use color_type::ColorType;
-pub use color_value::ColorValue;
+pub(crate) use color_value::values_length;
+pub use color_value::{ColorModel, ColorValue, GrayPolarity, RgbRange};
+pub use css::CssGradientOptions;
+pub use file_info::FileInfo;
+pub use ggr::GgrBlend;
 pub use group::Group;
-pub(crate) use group::GroupHold;
+pub(crate) use group::{ColorEntries, ColorEntry, GroupHold, GroupName, MalformedEntry};
+pub use header::AseHeader;
+pub use hue::HueBucket;
+pub use path::escape_path_segment;
+pub use provenance::{BlockProvenance, ProvenanceTarget};
+pub use set_ops::MatchBy;
 
 /// Magic Bytes for .ase files.
 /// Equal to the bytes of `ASEF`.
 pub(crate) const FILE_SIGNATURE: &[u8; 4] = b"ASEF";
 
-/// Version of the ASE file.
-pub(crate) const VERSION: u32 = 0x0001_0000;
+/// Version of the ASE format written by default, `1.0`.
+///
+/// The major version is stored in the upper 16 bits, the minor version in the lower ones.
+pub const FILE_VERSION: u32 = 0x0001_0000;
+
+/// Maximum number of characters in names generated by [`arbitrary::Arbitrary`].
+#[cfg(feature = "arbitrary")]
+const ARBITRARY_NAME_LENGTH: usize = 32;
+
+/// Generates a name of bounded length, which may contain any character except for null.
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_name(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<String> {
+    let length = u.int_in_range(0..=ARBITRARY_NAME_LENGTH)?;
+    let mut name = String::with_capacity(length);
+    for _ in 0..length {
+        match u.arbitrary::<char>()? {
+            '\0' => name.push(' '),
+            c => name.push(c),
+        }
+    }
+    Ok(name)
+}
@@ -0,0 +1,136 @@
+#![allow(clippy::arithmetic_side_effects)]
+
+use super::{Ase, ColorModel, ColorValue};
+
+impl Ase {
+    /// Returns the colors as tightly packed RGBA values, with an alpha of `1.0`.
+    ///
+    /// Colors are returned in document order, first those in groups, followed by the
+    /// single colors. Colors in other models are converted to sRGB and clamped to `0..=1`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue};
+    /// let ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal)],
+    /// );
+    /// assert_eq!(ase.to_rgba_f32(), vec![0.5, 0.5, 0.5, 1.0]);
+    /// ```
+    pub fn to_rgba_f32(&self) -> Vec<f32> {
+        let mut rgba = Vec::with_capacity(self.color_count() * 4);
+        for block in self.colors() {
+            let (r, g, b) = block.color.to_srgb();
+            rgba.extend([r, g, b].map(|v| v.clamp(0.0, 1.0)));
+            rgba.push(1.0);
+        }
+        rgba
+    }
+
+    /// Returns the colors as tightly packed 8-bit RGBA values, with an alpha of `255`,
+    /// see [`Ase::to_rgba_f32`].
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.to_rgba_f32()
+            .into_iter()
+            .map(|v| (v * 255.0).round() as u8)
+            .collect()
+    }
+
+    /// Returns the components of the colors in their own model, together with the models.
+    ///
+    /// Each color takes up four components, unused components are set to `0.0`.
+    /// Lab colors use a lightness in `0..=100`, like [`ColorValue::Lab`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorModel, ColorType, ColorValue};
+    /// let ase = Ase::new(
+    ///     vec![],
+    ///     vec![ColorBlock::new("name".to_owned(), ColorValue::Lab(50.0, 10.0, -10.0), ColorType::Normal)],
+    /// );
+    /// let (components, models) = ase.to_components_raw();
+    /// assert_eq!(components, vec![50.0, 10.0, -10.0, 0.0]);
+    /// assert_eq!(models, vec![ColorModel::Lab]);
+    /// ```
+    pub fn to_components_raw(&self) -> (Vec<f32>, Vec<ColorModel>) {
+        let mut components = Vec::with_capacity(self.color_count() * 4);
+        let mut models = Vec::with_capacity(self.color_count());
+        for block in self.colors() {
+            components.extend(match block.color {
+                ColorValue::Cmyk(c, m, y, k) => [c, m, y, k],
+                ColorValue::Rgb(r, g, b) => [r, g, b, 0.0],
+                ColorValue::Lab(l, a, b) => [l, a, b, 0.0],
+                ColorValue::Gray(value) => [value, 0.0, 0.0, 0.0],
+            });
+            models.push(block.color.model());
+        }
+        (components, models)
+    }
+
+    /// Returns the number of colors, including those in groups.
+    fn color_count(&self) -> usize {
+        self.colors.len()
+            + self
+                .groups
+                .iter()
+                .map(|group| group.blocks.len())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::named, Group};
+
+    use super::*;
+
+    fn palette() -> Ase {
+        Ase::new(
+            vec![Group::new(
+                "group".to_owned(),
+                vec![
+                    named("cmyk", ColorValue::Cmyk(0.0, 1.0, 1.0, 0.0)),
+                    named("out of gamut", ColorValue::Rgb(1.2, -0.1, 0.5)),
+                ],
+            )],
+            vec![
+                named("lab", ColorValue::Lab(100.0, 0.0, 0.0)),
+                named("gray", ColorValue::Gray(0.2)),
+            ],
+        )
+    }
+
+    #[test]
+    fn it_packs_rgba() {
+        let rgba = palette().to_rgba_f32();
+        assert_eq!(rgba.len(), 16);
+        assert_eq!(rgba[..8], [1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.5, 1.0]);
+        // D50 white
+        assert!(rgba[8..11].iter().all(|v| (v - 1.0).abs() < 1e-3));
+        assert_eq!(rgba[11..], [1.0, 0.2, 0.2, 0.2, 1.0]);
+
+        assert_eq!(
+            palette().to_rgba8(),
+            vec![255, 0, 0, 255, 255, 0, 128, 255, 255, 255, 255, 255, 51, 51, 51, 255]
+        );
+    }
+
+    #[test]
+    fn it_packs_raw_components() {
+        let (components, models) = palette().to_components_raw();
+        assert_eq!(
+            components,
+            vec![0.0, 1.0, 1.0, 0.0, 1.2, -0.1, 0.5, 0.0, 100.0, 0.0, 0.0, 0.0, 0.2, 0.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            models,
+            vec![
+                ColorModel::Cmyk,
+                ColorModel::Rgb,
+                ColorModel::Lab,
+                ColorModel::Gray
+            ]
+        );
+        assert!(Ase::default().to_components_raw().0.is_empty());
+    }
+}
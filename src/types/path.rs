@@ -0,0 +1,250 @@
+use super::{Ase, ColorBlock, ColorType, ColorValue, Group};
+
+/// Escape character for separators in path segments.
+const ESCAPE: char = '\\';
+
+/// Escapes the separator and escape character in `name`, so that it can be used as a
+/// segment of a path.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::escape_path_segment;
+/// assert_eq!(escape_path_segment("Black/White", '/'), "Black\\/White");
+/// assert_eq!(escape_path_segment("C:\\", '/'), "C:\\\\");
+/// ```
+pub fn escape_path_segment(name: &str, separator: char) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == separator || c == ESCAPE {
+            escaped.push(ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Splits the path at unescaped separators, removing the escape characters.
+// the current segment is always present
+#[allow(clippy::unwrap_used)]
+fn split_path(path: &str, separator: char) -> Vec<String> {
+    let mut segments = vec![String::new()];
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // a trailing escape character is kept as is
+            ESCAPE => segments
+                .last_mut()
+                .unwrap()
+                .push(chars.next().unwrap_or(ESCAPE)),
+            c if c == separator => segments.push(String::new()),
+            c => segments.last_mut().unwrap().push(c),
+        }
+    }
+    segments
+}
+
+impl Ase {
+    /// Returns the color at the given path.
+    ///
+    /// A path consists of the group name and the color name, separated by `separator`,
+    /// or only the name for single colors. Separators in names are escaped using a
+    /// backslash, see [`escape_path_segment`]. If multiple groups or colors share a name,
+    /// the first one in document order is used.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue, Group};
+    /// let blue = ColorBlock::new("Blue 500".to_owned(), ColorValue::Rgb(0.2, 0.5, 0.9), ColorType::Normal);
+    /// let ase = Ase::new(vec![Group::new("Primary".to_owned(), vec![blue.clone()])], vec![]);
+    /// assert_eq!(ase.get_path("Primary/Blue 500", '/'), Some(&blue));
+    /// assert_eq!(ase.get_path("Blue 500", '/'), None);
+    /// ```
+    pub fn get_path(&self, path: &str, separator: char) -> Option<&ColorBlock> {
+        match split_path(path, separator).as_slice() {
+            [name] => self.colors.iter().find(|block| &block.name == name),
+            [group, name] => self
+                .groups
+                .iter()
+                .find(|g| &g.name == group)?
+                .blocks
+                .iter()
+                .find(|block| &block.name == name),
+            _ => None,
+        }
+    }
+
+    /// Returns the color at the given path mutably, see [`Ase::get_path`].
+    pub fn get_path_mut(&mut self, path: &str, separator: char) -> Option<&mut ColorBlock> {
+        match split_path(path, separator).as_slice() {
+            [name] => self.colors.iter_mut().find(|block| &block.name == name),
+            [group, name] => self
+                .groups
+                .iter_mut()
+                .find(|g| &g.name == group)?
+                .blocks
+                .iter_mut()
+                .find(|block| &block.name == name),
+            _ => None,
+        }
+    }
+
+    /// Sets the color at the given path, inserting it if it does not exist yet,
+    /// see [`Ase::insert_path`].
+    ///
+    /// Returns `None` if the path has more segments than supported.
+    pub fn set_path(
+        &mut self,
+        path: &str,
+        separator: char,
+        color: ColorValue,
+    ) -> Option<&mut ColorBlock> {
+        // looked up twice, as returning the borrow from the first lookup is not
+        // supported by the borrow checker
+        if self.get_path(path, separator).is_some() {
+            let block = self.get_path_mut(path, separator)?;
+            block.color = color;
+            return Some(block);
+        }
+        self.insert_path(path, separator, color)
+    }
+
+    /// Appends a color of type [`ColorType::Normal`] at the given path, even if another
+    /// color with the same name exists.
+    ///
+    /// The group is created at the end of the palette, if there is no group with that name.
+    /// Returns `None` if the path has more segments than supported.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorValue};
+    /// let mut ase = Ase::default();
+    /// ase.insert_path("Brand/Red", '/', ColorValue::Rgb(1.0, 0.0, 0.0));
+    /// assert_eq!(ase.groups[0].name, "Brand");
+    /// assert_eq!(ase.groups[0].blocks[0].name, "Red");
+    /// ```
+    #[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+    pub fn insert_path(
+        &mut self,
+        path: &str,
+        separator: char,
+        color: ColorValue,
+    ) -> Option<&mut ColorBlock> {
+        let mut segments = split_path(path, separator);
+        let name = segments.pop()?;
+        let blocks = match segments.as_slice() {
+            [] => &mut self.colors,
+            [group] => {
+                let index = match self.groups.iter().position(|g| &g.name == group) {
+                    Some(index) => index,
+                    None => {
+                        self.groups.push(Group::new(group.clone(), vec![]));
+                        self.groups.len() - 1
+                    }
+                };
+                &mut self.groups[index].blocks
+            }
+            _ => return None,
+        };
+        blocks.push(ColorBlock::new(name, color, ColorType::Normal));
+        blocks.last_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::named;
+
+    use super::*;
+
+    fn palette() -> Ase {
+        Ase::new(
+            vec![
+                Group::new(
+                    "Brand".to_owned(),
+                    vec![
+                        named("Blue 500", ColorValue::Gray(0.1)),
+                        named("Blue 500", ColorValue::Gray(0.2)),
+                    ],
+                ),
+                Group::new(
+                    "Black/White".to_owned(),
+                    vec![named("50\\50", ColorValue::Gray(0.5))],
+                ),
+            ],
+            vec![named("Blue 500", ColorValue::Gray(0.3))],
+        )
+    }
+
+    #[test]
+    fn it_gets_paths() {
+        let ase = palette();
+        assert_eq!(
+            ase.get_path("Blue 500", '/').unwrap().color,
+            ColorValue::Gray(0.3)
+        );
+        // the first match is used
+        assert_eq!(
+            ase.get_path("Brand/Blue 500", '/').unwrap().color,
+            ColorValue::Gray(0.1)
+        );
+        assert_eq!(ase.get_path("Brand/Red", '/'), None);
+        assert_eq!(ase.get_path("Missing/Blue 500", '/'), None);
+        assert_eq!(ase.get_path("Brand/Primary/Blue 500", '/'), None);
+    }
+
+    #[test]
+    fn it_escapes_separators() {
+        let ase = palette();
+        let path = format!(
+            "{}/{}",
+            escape_path_segment("Black/White", '/'),
+            escape_path_segment("50\\50", '/')
+        );
+        assert_eq!(path, "Black\\/White/50\\\\50");
+        assert_eq!(
+            ase.get_path(&path, '/').unwrap().color,
+            ColorValue::Gray(0.5)
+        );
+        // the separator is only special if it is the chosen one
+        assert_eq!(
+            ase.get_path("Black/White.50\\\\50", '.').unwrap().color,
+            ColorValue::Gray(0.5)
+        );
+        assert_eq!(split_path("trailing\\", '/'), vec!["trailing\\"]);
+    }
+
+    #[test]
+    fn it_sets_paths() {
+        let mut ase = palette();
+        ase.set_path("Brand/Blue 500", '/', ColorValue::Gray(1.0));
+        assert_eq!(ase.groups[0].blocks[0].color, ColorValue::Gray(1.0));
+        assert_eq!(ase.groups[0].blocks.len(), 2);
+
+        ase.set_path("Green", '/', ColorValue::Gray(0.0));
+        assert_eq!(ase.colors[1], named("Green", ColorValue::Gray(0.0)));
+    }
+
+    #[test]
+    fn it_inserts_paths() {
+        let mut ase = palette();
+        ase.insert_path("Accent/Orange", '/', ColorValue::Rgb(1.0, 0.5, 0.0))
+            .unwrap();
+        assert_eq!(ase.groups.len(), 3);
+        assert_eq!(
+            ase.groups[2],
+            Group::new(
+                "Accent".to_owned(),
+                vec![named("Orange", ColorValue::Rgb(1.0, 0.5, 0.0))]
+            )
+        );
+
+        ase.insert_path("Brand/Blue 500", '/', ColorValue::Gray(0.4))
+            .unwrap();
+        assert_eq!(ase.groups[0].blocks.len(), 3);
+
+        assert!(ase
+            .insert_path("Brand/Primary/Blue", '/', ColorValue::Gray(0.4))
+            .is_none());
+        assert_eq!(ase.groups.len(), 3);
+    }
+}
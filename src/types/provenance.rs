@@ -0,0 +1,26 @@
+/// Where a parsed group or color was located in the file.
+///
+/// Returned by [`read_ase_traced`](crate::read_ase_traced), in the order the blocks
+/// appear in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockProvenance {
+    /// Position of the group or color among all groups and colors, in document order.
+    pub index: usize,
+    /// Offset of the block identifier from the start of the file.
+    pub offset: u64,
+    /// The length declared by the block, without identifier and length.
+    pub length: u32,
+    /// The parsed group or color.
+    pub target: ProvenanceTarget,
+}
+
+/// The parsed item, which a [`BlockProvenance`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvenanceTarget {
+    /// Index into the read groups.
+    Group(usize),
+    /// Index into the read groups, and the index of the color within that group.
+    GroupColor(usize, usize),
+    /// Index into the read single colors.
+    Color(usize),
+}
@@ -0,0 +1,159 @@
+use super::{Ase, ColorBlock, Group};
+
+/// How colors of two palettes are matched, see [`Ase::intersection`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchBy {
+    /// Colors match if they have the same name.
+    Name,
+    /// Colors match if their color difference (CIE76 Delta E) is at most `tolerance`.
+    ///
+    /// Colors in different models are compared in CIE Lab, converting them through sRGB.
+    Value { tolerance: f32 },
+    /// Colors match if they have the same name and their values match.
+    Both { tolerance: f32 },
+}
+
+impl MatchBy {
+    /// Returns whether the colors match.
+    fn matches(self, a: &ColorBlock, b: &ColorBlock) -> bool {
+        match self {
+            MatchBy::Name => a.name == b.name,
+            MatchBy::Value { tolerance } => a.color.delta_e(&b.color) <= tolerance,
+            MatchBy::Both { tolerance } => {
+                a.name == b.name && a.color.delta_e(&b.color) <= tolerance
+            }
+        }
+    }
+}
+
+impl Ase {
+    /// Returns the colors, which have a matching color in `other`.
+    ///
+    /// Colors are matched regardless of their group. The groups of `self` are kept,
+    /// groups without any remaining colors are removed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use adobe_swatch_exchange::{Ase, ColorBlock, ColorType, ColorValue, MatchBy};
+    /// let red = ColorBlock::new("Red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Normal);
+    /// let gray = ColorBlock::new("Gray".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    /// let a = Ase::new(vec![], vec![red.clone(), gray]);
+    /// let b = Ase::new(vec![], vec![red.clone()]);
+    /// assert_eq!(a.intersection(&b, MatchBy::Name).colors, vec![red]);
+    /// ```
+    pub fn intersection(&self, other: &Ase, matcher: MatchBy) -> Ase {
+        self.retain_matching(other, matcher, true)
+    }
+
+    /// Returns the colors, which have no matching color in `other`, see [`Ase::intersection`].
+    pub fn difference(&self, other: &Ase, matcher: MatchBy) -> Ase {
+        self.retain_matching(other, matcher, false)
+    }
+
+    /// Returns the colors, for which the existence of a match in `other` equals `matching`.
+    fn retain_matching(&self, other: &Ase, matcher: MatchBy, matching: bool) -> Ase {
+        let keep =
+            |block: &&ColorBlock| other.colors().any(|o| matcher.matches(block, o)) == matching;
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                Group::new(
+                    group.name.clone(),
+                    group.blocks.iter().filter(keep).cloned().collect(),
+                )
+            })
+            .filter(|group| !group.blocks.is_empty())
+            .collect();
+        let colors = self.colors.iter().filter(keep).cloned().collect();
+        Ase::new(groups, colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_util::named, ColorValue};
+
+    use super::*;
+
+    fn names(ase: &Ase) -> Vec<(Option<&str>, &str)> {
+        ase.groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .blocks
+                    .iter()
+                    .map(|block| (Some(group.name.as_str()), block.name.as_str()))
+            })
+            .chain(ase.colors.iter().map(|block| (None, block.name.as_str())))
+            .collect()
+    }
+
+    fn palettes() -> (Ase, Ase) {
+        let a = Ase::new(
+            vec![
+                Group::new(
+                    "Brand".to_owned(),
+                    vec![
+                        named("Red", ColorValue::Rgb(1.0, 0.0, 0.0)),
+                        named("Gray", ColorValue::Gray(0.5)),
+                    ],
+                ),
+                Group::new(
+                    "Accent".to_owned(),
+                    vec![named("Blue", ColorValue::Rgb(0.0, 0.0, 1.0))],
+                ),
+            ],
+            vec![named("Teal", ColorValue::Rgb(0.0, 0.5, 0.5))],
+        );
+        let b = Ase::new(
+            vec![Group::new(
+                "Other".to_owned(),
+                vec![
+                    // same color in other models, with rounding
+                    named("Crimson", ColorValue::Cmyk(0.0, 1.0, 0.996, 0.0)),
+                    named("Gray", ColorValue::Rgb(0.502, 0.502, 0.502)),
+                ],
+            )],
+            vec![named("Blue", ColorValue::Rgb(0.0, 1.0, 0.0))],
+        );
+        (a, b)
+    }
+
+    #[test]
+    fn it_matches_by_name() {
+        let (a, b) = palettes();
+        assert_eq!(
+            names(&a.intersection(&b, MatchBy::Name)),
+            vec![(Some("Brand"), "Gray"), (Some("Accent"), "Blue")]
+        );
+        assert_eq!(
+            names(&a.difference(&b, MatchBy::Name)),
+            vec![(Some("Brand"), "Red"), (None, "Teal")]
+        );
+    }
+
+    #[test]
+    fn it_matches_by_value() {
+        let (a, b) = palettes();
+        let matcher = MatchBy::Value { tolerance: 1.0 };
+        assert_eq!(
+            names(&a.intersection(&b, matcher)),
+            vec![(Some("Brand"), "Red"), (Some("Brand"), "Gray")]
+        );
+        assert_eq!(
+            names(&a.difference(&b, matcher)),
+            vec![(Some("Accent"), "Blue"), (None, "Teal")]
+        );
+        // the encodings differ slightly
+        assert!(a
+            .intersection(&b, MatchBy::Value { tolerance: 0.0 })
+            .groups
+            .is_empty());
+
+        assert_eq!(
+            names(&a.intersection(&b, MatchBy::Both { tolerance: 1.0 })),
+            vec![(Some("Brand"), "Gray")]
+        );
+    }
+}
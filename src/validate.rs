@@ -0,0 +1,310 @@
+use std::{collections::HashMap, hash::Hash, io::Read};
+
+use crate::{
+    error::{ASEError, ConformationError},
+    reader::BlockReader,
+    types::{ColorEntries, ColorEntry, EntryLayout, GroupName, MalformedEntry},
+    wire, Blocks, ColorBlock, ColorModel, ColorType, Document, FileInfo, ParseOptions,
+    ParseWarning,
+};
+
+/// The strictest [`ParseOptions`] reading an .ase file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceLevel {
+    /// The file follows the format and is read by [`ParseOptions::STRICT`].
+    Strict,
+    /// The file deviates from the format in ways tolerated by [`ParseOptions::DEFAULT`],
+    /// like [`read_ase`](crate::read_ase) does.
+    Default,
+}
+
+/// Summary of an .ase file, as returned by [`validate_ase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AseSummary {
+    /// Number of groups in the file.
+    pub groups: usize,
+    /// Number of colors in the file, including those in groups.
+    pub colors: usize,
+    /// Information about the file.
+    ///
+    /// Unlike for [`read_ase_info`](crate::read_ase_info), `bytes_read` includes the data
+    /// after the last block.
+    pub info: FileInfo,
+    /// The strictest options reading the file.
+    pub conformance: ConformanceLevel,
+}
+
+/// Statistics of an .ase file, as returned by [`ase_statistics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AseStats {
+    /// Number of groups in the file.
+    pub groups: usize,
+    /// Number of colors in the file, including those in groups.
+    pub colors: usize,
+    /// Number of colors in the group with the most colors.
+    pub largest_group: usize,
+    /// Number of colors of each color type, types without colors are left out.
+    pub color_types: HashMap<ColorType, usize>,
+    /// Number of colors of each color model, models without colors are left out.
+    pub color_models: HashMap<ColorModel, usize>,
+    /// Information about the file, including the declared number of blocks and the
+    /// number of groups and colors read.
+    pub info: FileInfo,
+}
+
+/// Check that the .ase file is read by [`read_ase`](crate::read_ase), without building
+/// its groups and colors.
+///
+/// The blocks are read the same way as [`read_ase`](crate::read_ase) reads them,
+/// including their names and color models, but names are not decoded into strings and
+/// no colors are stored. The data after the last block is read as well, to determine
+/// whether [`ParseOptions::STRICT`] reads the file.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails, or the
+/// ASE file is invalid, exactly like [`read_ase`](crate::read_ase) does.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{create_ase, validate_ase, ColorBlock, ColorType, ColorValue, ConformanceLevel, Group};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let group = Group::new("group".to_owned(), vec![color.clone()]);
+/// let mut source = create_ase(vec![group], vec![color]);
+///
+/// let summary = validate_ase(&*source).unwrap();
+/// assert_eq!((summary.groups, summary.colors), (1, 2));
+/// assert_eq!(summary.conformance, ConformanceLevel::Strict);
+///
+/// source.extend(b"garbage");
+/// let summary = validate_ase(&*source).unwrap();
+/// assert_eq!(summary.conformance, ConformanceLevel::Default);
+/// assert_eq!(summary.info.bytes_read, source.len() as u64);
+/// ```
+pub fn validate_ase<T: Read>(ase: T) -> Result<AseSummary, ASEError> {
+    let mut blocks = BlockReader::with_options(ase, &ParseOptions::DEFAULT)?;
+    let (mut validated, validator) = validate_blocks(&mut blocks)?;
+
+    // data after the blocks is not read by default, but rejected by strict reading
+    let trailing_data = match blocks.finish() {
+        Ok(()) => false,
+        Err(ASEError::Invalid(ConformationError::TrailingData { .. })) => true,
+        Err(err) => return Err(blocks.locate(err)),
+    };
+    let strict = !trailing_data
+        && !validator.stopped
+        && validated
+            .warnings
+            .iter()
+            .all(|warning| matches!(warning, ParseWarning::SeparateGroupColors { .. }));
+
+    let grouped_colors: usize = validated.groups.iter().map(Vec::len).sum();
+    validated.info.bytes_read = blocks.offset();
+    Ok(AseSummary {
+        groups: validated.groups.len(),
+        colors: grouped_colors.saturating_add(validated.colors.len()),
+        info: validated.info,
+        conformance: if strict {
+            ConformanceLevel::Strict
+        } else {
+            ConformanceLevel::Default
+        },
+    })
+}
+
+/// Count the groups and colors in the .ase file, without building them.
+///
+/// The blocks are read the same way as [`read_ase`](crate::read_ase) reads them,
+/// including the deviations from the format it tolerates, so the statistics describe
+/// the document it returns. Like [`validate_ase`], names are not decoded into strings
+/// and no colors are stored.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails, or the
+/// ASE file is invalid, exactly like [`read_ase`](crate::read_ase) does.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{ase_statistics, create_ase, ColorBlock, ColorModel, ColorType, ColorValue, Group};
+/// let gray = ColorBlock::new("gray".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+/// let red = ColorBlock::new("red".to_owned(), ColorValue::Rgb(1.0, 0.0, 0.0), ColorType::Spot);
+/// let group = Group::new("group".to_owned(), vec![gray.clone(), red]);
+/// let source = create_ase(vec![group], vec![gray]);
+///
+/// let stats = ase_statistics(&*source).unwrap();
+/// assert_eq!((stats.groups, stats.colors, stats.largest_group), (1, 3, 2));
+/// assert_eq!(stats.color_types[&ColorType::Normal], 2);
+/// assert_eq!(stats.color_models[&ColorModel::Rgb], 1);
+/// // groups containing their colors are counted as a single block
+/// assert_eq!((stats.info.declared_blocks, stats.info.parsed_blocks), (2, 4));
+/// ```
+pub fn ase_statistics<T: Read>(ase: T) -> Result<AseStats, ASEError> {
+    let mut blocks = BlockReader::with_options(ase, &ParseOptions::DEFAULT)?;
+    let (validated, validator) = validate_blocks(&mut blocks)?;
+
+    let grouped_colors: usize = validated.groups.iter().map(Vec::len).sum();
+    Ok(AseStats {
+        groups: validated.groups.len(),
+        colors: grouped_colors.saturating_add(validated.colors.len()),
+        largest_group: validated.groups.iter().map(Vec::len).max().unwrap_or(0),
+        color_types: validator.color_types,
+        color_models: validator.color_models,
+        info: validated.info,
+    })
+}
+
+/// Reads the blocks like [`read_ase`](crate::read_ase), checking them with a [`Validator`].
+fn validate_blocks<T: Read>(
+    blocks: &mut BlockReader<T>,
+) -> Result<(Blocks<Validator>, Validator), ASEError> {
+    let mut validator = Validator {
+        stopped: false,
+        color_types: HashMap::new(),
+        color_models: HashMap::new(),
+    };
+    let validated =
+        crate::parse_blocks(blocks, &mut validator, &ParseOptions::DEFAULT, None, false)
+            .map_err(|err| blocks.locate(err))?;
+    Ok((validated, validator))
+}
+
+/// Checks groups and colors, without building them.
+struct Validator {
+    /// Whether the colors of a group ended before a color, which cannot be read.
+    ///
+    /// Strict reading rejects these colors, instead of ignoring the rest of the group.
+    stopped: bool,
+    /// Number of colors read of each color type.
+    color_types: HashMap<ColorType, usize>,
+    /// Number of colors read of each color model.
+    color_models: HashMap<ColorModel, usize>,
+}
+
+impl Validator {
+    /// Checks the data of a color entry block, like [`ColorBlock::parse_entry`] reads it.
+    fn check_color(&mut self, data: &[u8]) -> Result<((), EntryLayout), ASEError> {
+        let parts = ColorBlock::parse_parts(data, false)?;
+        wire::validate_utf16_units(parts.name)?;
+        count(&mut self.color_types, parts.color_type);
+        count(&mut self.color_models, parts.color.model());
+        Ok(((), parts.layout))
+    }
+}
+
+impl Document for Validator {
+    type Group = Vec<()>;
+    type Color = ();
+
+    fn parse_group(
+        &mut self,
+        data: &[u8],
+        malformed: MalformedEntry,
+        inspect: impl FnMut(&ColorEntry<'_>, Option<&EntryLayout>),
+    ) -> Result<(Self::Group, usize), ASEError> {
+        let name = GroupName::parse(data)?;
+        wire::validate_utf16_units(name.units)?;
+        let mut entries = ColorEntries::new(data, name.end);
+        let colors = entries.parse_colors(
+            malformed,
+            |data| {
+                let color = self.check_color(data);
+                self.stopped |= color.is_err();
+                color
+            },
+            inspect,
+        );
+        let colors =
+            colors.map_err(|err| err.in_swatch(wire::decode_utf16_units_lossy(name.units)))?;
+        Ok((colors, entries.end()))
+    }
+
+    fn parse_color(&mut self, data: &[u8]) -> Result<(Self::Color, EntryLayout), ASEError> {
+        self.check_color(data)
+    }
+
+    fn colors(group: &mut Self::Group) -> &mut Vec<Self::Color> {
+        group
+    }
+}
+
+/// Increments the number of colors counted for `key`.
+fn count<K: Hash + Eq>(counts: &mut HashMap<K, usize>, key: K) {
+    let count = counts.entry(key).or_default();
+    *count = count.saturating_add(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{read_ase, read_ase_info, test_util::named, ColorValue, Group};
+
+    use super::*;
+
+    #[test]
+    fn it_counts_what_is_read() {
+        let mut inputs =
+            vec![
+                std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/aurora.ase")).unwrap(),
+            ];
+        for kind in ["valid", "lenient"] {
+            let dir = format!("{}/tests/fixtures/{kind}", env!("CARGO_MANIFEST_DIR"));
+            for entry in std::fs::read_dir(dir).unwrap() {
+                inputs.push(std::fs::read(entry.unwrap().path()).unwrap());
+            }
+        }
+
+        for input in inputs {
+            let (groups, colors, info) = read_ase_info(&*input).unwrap();
+            let all_colors = || groups.iter().flat_map(|group| &group.blocks).chain(&colors);
+            let mut color_types = HashMap::new();
+            let mut color_models = HashMap::new();
+            for color in all_colors() {
+                count(&mut color_types, color.color_type.clone());
+                count(&mut color_models, color.color.model());
+            }
+
+            let stats = ase_statistics(&*input).unwrap();
+            assert_eq!(
+                stats,
+                AseStats {
+                    groups: groups.len(),
+                    colors: all_colors().count(),
+                    largest_group: groups
+                        .iter()
+                        .map(|group| group.blocks.len())
+                        .max()
+                        .unwrap_or(0),
+                    color_types,
+                    color_models,
+                    info,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn it_counts_colors_per_type_and_model() {
+        let mut spot = named("spot", ColorValue::Cmyk(0.0, 0.5, 1.0, 0.0));
+        spot.color_type = ColorType::Spot;
+        let groups = vec![
+            Group::new("empty".to_owned(), vec![]),
+            Group::new(
+                "colors".to_owned(),
+                vec![named("gray", ColorValue::Gray(0.5)), spot.clone()],
+            ),
+        ];
+        let source = crate::create_ase(groups, vec![spot]);
+        assert!(read_ase(&*source).is_ok());
+
+        let stats = ase_statistics(&*source).unwrap();
+        assert_eq!((stats.groups, stats.colors, stats.largest_group), (2, 3, 2));
+        assert_eq!(
+            stats.color_types,
+            HashMap::from([(ColorType::Normal, 1), (ColorType::Spot, 2)])
+        );
+        assert_eq!(
+            stats.color_models,
+            HashMap::from([(ColorModel::Gray, 1), (ColorModel::Cmyk, 2)])
+        );
+    }
+}
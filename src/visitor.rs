@@ -0,0 +1,296 @@
+use std::{io::Read, ops::ControlFlow};
+
+use crate::{error::ASEError, reader::BlockReader, wire, BlockType};
+
+/// Receives the blocks of an ASE file, exactly as they are framed in the file.
+///
+/// Every callback may return [`ControlFlow::Break`] to stop visiting early.
+pub trait BlockVisitor {
+    /// Called once the file header has been read.
+    ///
+    /// `block_count` is the number of blocks declared in the header.
+    fn on_header(&mut self, _version: u32, _block_count: u32) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every block in the file, including [`BlockType::GroupEnd`] and
+    /// unknown [`BlockType::Raw`] blocks.
+    ///
+    /// `offset` is the position of the block identifier from the start of the file,
+    /// `payload` the block data without identifier and length.
+    fn on_block(&mut self, block_type: BlockType, offset: u64, payload: &[u8]) -> ControlFlow<()>;
+
+    /// Called when a [`BlockType::GroupEnd`] block was followed by a (nonconformant) zero
+    /// length field.
+    ///
+    /// `offset` is the position of the length field from the start of the file, `length`
+    /// the number of bytes it occupies.
+    fn on_group_end_length(&mut self, _offset: u64, _length: u64) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+impl<V: BlockVisitor + ?Sized> BlockVisitor for &mut V {
+    fn on_header(&mut self, version: u32, block_count: u32) -> ControlFlow<()> {
+        (**self).on_header(version, block_count)
+    }
+
+    fn on_block(&mut self, block_type: BlockType, offset: u64, payload: &[u8]) -> ControlFlow<()> {
+        (**self).on_block(block_type, offset, payload)
+    }
+
+    fn on_group_end_length(&mut self, offset: u64, length: u64) -> ControlFlow<()> {
+        (**self).on_group_end_length(offset, length)
+    }
+}
+
+/// Walk the blocks of an .ase file, without interpreting them.
+///
+/// Blocks are read the same way as [`read_ase`](crate::read_ase) reads them, but
+/// each one is handed to the `visitor` instead.
+///
+/// # Errors
+///
+/// This function will return an error if either a read to the given data fails,
+/// or the ASE header is invalid.
+///
+/// # Examples
+/// ```rust
+/// # use std::ops::ControlFlow;
+/// # use adobe_swatch_exchange::{visit_blocks, BlockType, BlockVisitor};
+/// struct Counter(usize);
+///
+/// impl BlockVisitor for Counter {
+///     fn on_block(&mut self, _: BlockType, _: u64, _: &[u8]) -> ControlFlow<()> {
+///         self.0 += 1;
+///         ControlFlow::Continue(())
+///     }
+/// }
+///
+/// let source = vec![65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 0];
+/// let mut counter = Counter(0);
+/// visit_blocks(&*source, &mut counter).unwrap();
+/// # assert_eq!(counter.0, 0);
+/// ```
+pub fn visit_blocks<T: Read, V: BlockVisitor>(reader: T, mut visitor: V) -> Result<(), ASEError> {
+    let mut blocks = BlockReader::new(reader, true)?;
+    if visitor
+        .on_header(blocks.version, blocks.block_count)
+        .is_break()
+    {
+        return Ok(());
+    }
+
+    while let Some(frame) = blocks.next_block()? {
+        if frame.padding > 0
+            && visitor
+                .on_group_end_length(frame.offset.saturating_sub(frame.padding), frame.padding)
+                .is_break()
+        {
+            return Ok(());
+        }
+
+        // groups which already contain their colors are not counted with their end
+        if frame.block_type == BlockType::GroupStart && contains_colors(&frame.data) {
+            blocks.expect_additional_block();
+        }
+
+        if visitor
+            .on_block(frame.block_type, frame.offset, &frame.data)
+            .is_break()
+        {
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns whether the payload of a `GroupStart` block continues after the group name.
+fn contains_colors(payload: &[u8]) -> bool {
+    let Ok(name_length) = wire::get_u16_be(payload) else {
+        return false;
+    };
+    // the name length is followed by the name and a null terminator, which is not
+    // counted by some applications
+    let name_end = usize::from(name_length).saturating_mul(2).saturating_add(4);
+    payload.len() > name_end
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_ase, test_util::named, ColorValue, Group};
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct CountingVisitor {
+        header: Option<(u32, u32)>,
+        blocks: Vec<(BlockType, u64, usize)>,
+        group_end_lengths: Vec<(u64, u64)>,
+        stop_after: Option<usize>,
+    }
+
+    impl BlockVisitor for CountingVisitor {
+        fn on_header(&mut self, version: u32, block_count: u32) -> ControlFlow<()> {
+            self.header = Some((version, block_count));
+            ControlFlow::Continue(())
+        }
+
+        fn on_block(
+            &mut self,
+            block_type: BlockType,
+            offset: u64,
+            payload: &[u8],
+        ) -> ControlFlow<()> {
+            self.blocks.push((block_type, offset, payload.len()));
+            match self.stop_after {
+                Some(limit) if self.blocks.len() >= limit => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            }
+        }
+
+        fn on_group_end_length(&mut self, offset: u64, length: u64) -> ControlFlow<()> {
+            self.group_end_lengths.push((offset, length));
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn group_and_color() -> Vec<u8> {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let block = named("name", ColorValue::Gray(0.5));
+        create_ase(vec![group], vec![block])
+    }
+
+    #[test]
+    fn it_visits_empty() {
+        let mut visitor = CountingVisitor::default();
+        visit_blocks(&*create_ase(vec![], vec![]), &mut visitor).unwrap();
+        assert_eq!(visitor.header, Some((0x0001_0000, 0)));
+        assert!(visitor.blocks.is_empty());
+    }
+
+    #[test]
+    fn it_visits_group_and_single_color() {
+        let mut visitor = CountingVisitor::default();
+        visit_blocks(&*group_and_color(), &mut visitor).unwrap();
+        assert_eq!(visitor.header, Some((0x0001_0000, 2)));
+        assert_eq!(
+            visitor.blocks,
+            vec![
+                (BlockType::GroupStart, 12, 108),
+                (BlockType::GroupEnd, 126, 0),
+                (BlockType::ColorEntry, 128, 22),
+            ]
+        );
+        assert!(visitor.group_end_lengths.is_empty());
+    }
+
+    #[test]
+    fn it_visits_group_block_name_only_size_and_explicit_group_end_size() {
+        let input_ase_bytes = group_and_color();
+        // same layout as in the reader tests, the group block only contains its name
+        // and the group end is followed by a zero length field
+        let mut modified_ase_bytes = Vec::new();
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[..8]);
+        modified_ase_bytes.extend_from_slice(&(5_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[12..14]);
+        modified_ase_bytes.extend_from_slice(&(24_u32.to_be_bytes()));
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[18..128]);
+        modified_ase_bytes.extend_from_slice(&[0; 4]);
+        modified_ase_bytes.extend_from_slice(&input_ase_bytes[128..]);
+
+        let mut visitor = CountingVisitor::default();
+        visit_blocks(&*modified_ase_bytes, &mut visitor).unwrap();
+        assert_eq!(visitor.header, Some((0x0001_0000, 5)));
+        assert_eq!(
+            visitor.blocks,
+            vec![
+                (BlockType::GroupStart, 12, 24),
+                (BlockType::ColorEntry, 42, 34),
+                (BlockType::ColorEntry, 82, 38),
+                (BlockType::GroupEnd, 126, 0),
+                (BlockType::ColorEntry, 132, 22),
+            ]
+        );
+        assert_eq!(visitor.group_end_lengths, vec![(128, 4)]);
+    }
+
+    /// Visits the file, returning the position after the last visited block.
+    fn visited_end(input: &[u8]) -> u64 {
+        let mut visitor = CountingVisitor::default();
+        visit_blocks(input, &mut visitor).unwrap();
+        visitor
+            .blocks
+            .last()
+            .map_or(12, |&(block_type, offset, length)| match block_type {
+                BlockType::GroupEnd => offset + 2,
+                _ => offset + 6 + length as u64,
+            })
+    }
+
+    #[test]
+    fn it_visits_fixtures_like_read_ase() {
+        for kind in ["valid", "lenient"] {
+            let dir = format!("{}/tests/fixtures/{kind}", env!("CARGO_MANIFEST_DIR"));
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                let input = std::fs::read(&path).unwrap();
+                let (_, _, info) = crate::read_ase_info(&*input).unwrap();
+                assert_eq!(visited_end(&input), info.bytes_read, "{}", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn it_visits_end_of_group_with_invalid_color() {
+        // the group contains a color with an invalid color model, which is not parsed
+        let input = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/invalid/color_model_nul_padded.ase"
+        ))
+        .unwrap();
+        assert_eq!(visited_end(&input), input.len() as u64);
+    }
+
+    #[test]
+    fn it_visits_unknown_blocks() {
+        let input = vec![
+            65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 1, 0, 2, 0, 0, 0, 22, 0, 5, 0, 110, 0, 97, 0, 109,
+            0, 101, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2,
+        ];
+        let mut visitor = CountingVisitor::default();
+        visit_blocks(&*input, &mut visitor).unwrap();
+        assert_eq!(visitor.blocks, vec![(BlockType::Raw(2), 12, 22)]);
+    }
+
+    #[test]
+    fn it_stops_early() {
+        let mut visitor = CountingVisitor {
+            stop_after: Some(1),
+            ..Default::default()
+        };
+        visit_blocks(&*group_and_color(), &mut visitor).unwrap();
+        assert_eq!(visitor.blocks, vec![(BlockType::GroupStart, 12, 108)]);
+    }
+
+    #[test]
+    fn it_returns_error_on_invalid_header() {
+        let mut visitor = CountingVisitor::default();
+        let res = visit_blocks(&*vec![65, 80, 69, 70, 1, 1, 0, 0, 0, 0, 0, 0], &mut visitor);
+        assert!(
+            matches!(
+                res.err(),
+                Some(ASEError::Invalid(crate::ConformationError::FileSignature))
+            ),
+            "Only ASEError::Invalid(error::ConformationError::FileSignature) should be returned"
+        );
+        assert!(visitor.header.is_none());
+    }
+}
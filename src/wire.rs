@@ -0,0 +1,353 @@
+//! Encoding of the primitive values used by ASE files.
+//!
+//! All numbers are big-endian. Strings are stored as the number of UTF-16 code units,
+//! including a null terminator, followed by the null terminated UTF-16 code units.
+
+use crate::ASEError;
+
+/// Appends `n` to `buf`.
+pub fn put_u16_be(buf: &mut Vec<u8>, n: u16) {
+    buf.extend_from_slice(&n.to_be_bytes());
+}
+
+/// Appends `n` to `buf`.
+pub fn put_u32_be(buf: &mut Vec<u8>, n: u32) {
+    buf.extend_from_slice(&n.to_be_bytes());
+}
+
+/// Appends `n` to `buf`.
+pub fn put_f32_be(buf: &mut Vec<u8>, n: f32) {
+    buf.extend_from_slice(&n.to_be_bytes());
+}
+
+/// Appends the length of `src`, followed by the null terminated UTF-16 encoding of `src`.
+///
+/// Null characters in `src` are written as they are.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::wire::put_utf16_nul_str;
+/// let mut buf = Vec::new();
+/// put_utf16_nul_str(&mut buf, "ä");
+/// assert_eq!(buf, vec![0, 2, 0, 228, 0, 0]);
+/// ```
+#[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+pub fn put_utf16_nul_str(buf: &mut Vec<u8>, src: &str) {
+    let length_position = buf.len();
+    put_u16_be(buf, 0);
+    let units = put_utf16_nul_terminated(buf, src);
+    // +1 for the null terminator
+    let length = (units as u16).wrapping_add(1);
+    buf[length_position..length_position + 2].copy_from_slice(&length.to_be_bytes());
+}
+
+/// Appends the null terminated UTF-16 encoding of `src`, without its length.
+///
+/// Returns the number of code units of `src`, without the null terminator.
+#[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+fn put_utf16_nul_terminated(buf: &mut Vec<u8>, src: &str) -> usize {
+    let start = buf.len();
+    // every byte of UTF-8 encodes to at most one UTF-16 code unit, the zeroed space
+    // left after the name includes the null terminator
+    buf.resize(start + src.len() * 2 + 2, 0);
+    let mut units = 0;
+    for (bytes, unit) in buf[start..].chunks_exact_mut(2).zip(src.encode_utf16()) {
+        bytes.copy_from_slice(&unit.to_be_bytes());
+        units += 1;
+    }
+    buf.truncate(start + units * 2 + 2);
+    units
+}
+
+/// Reads a u16 from the start of `bytes`.
+///
+/// # Errors
+/// This function will return an error if `bytes` is too short.
+pub fn get_u16_be(bytes: &[u8]) -> Result<u16, ASEError> {
+    Ok(u16::from_be_bytes(
+        bytes
+            .get(..2)
+            .ok_or(ASEError::InputDataParseError)?
+            .try_into()?,
+    ))
+}
+
+/// Reads a u32 from the start of `bytes`.
+///
+/// # Errors
+/// This function will return an error if `bytes` is too short.
+pub fn get_u32_be(bytes: &[u8]) -> Result<u32, ASEError> {
+    Ok(u32::from_be_bytes(
+        bytes
+            .get(..4)
+            .ok_or(ASEError::InputDataParseError)?
+            .try_into()?,
+    ))
+}
+
+/// Reads a f32 from the start of `bytes`.
+///
+/// # Errors
+/// This function will return an error if `bytes` is too short.
+pub fn get_f32_be(bytes: &[u8]) -> Result<f32, ASEError> {
+    get_u32_be(bytes).map(f32::from_bits)
+}
+
+/// Reads a string written by [`put_utf16_nul_str`] from the start of `bytes`.
+///
+/// Returns the string and the number of bytes it takes up. Like Adobe applications, the
+/// string ends at the first null character, even if the length includes more characters.
+///
+/// # Errors
+/// This function will return an error if the string is truncated or not valid UTF-16.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::wire::get_utf16_nul_str;
+/// let (name, length) = get_utf16_nul_str(&[0, 2, 0, 228, 0, 0, 1, 2]).unwrap();
+/// assert_eq!(name, "ä");
+/// assert_eq!(length, 6);
+/// ```
+pub fn get_utf16_nul_str(bytes: &[u8]) -> Result<(String, usize), ASEError> {
+    let mut string = String::new();
+    let length = get_utf16_nul_str_into(bytes, &mut string)?;
+    Ok((string, length))
+}
+
+/// Decodes a string written by [`put_utf16_nul_str`] into `string`, replacing its
+/// previous content.
+///
+/// Returns the number of bytes the string takes up, see [`get_utf16_nul_str`].
+///
+/// # Errors
+/// This function will return an error if the string is truncated or not valid UTF-16.
+pub(crate) fn get_utf16_nul_str_into(bytes: &[u8], string: &mut String) -> Result<usize, ASEError> {
+    let (units, length) = get_utf16_nul_units(bytes)?;
+    decode_utf16_units_into(units, string)?;
+    Ok(length)
+}
+
+/// Decodes big-endian UTF-16 code units into `string`, replacing its previous content.
+///
+/// # Errors
+/// This function will return an error if the code units are not valid UTF-16.
+pub(crate) fn decode_utf16_units_into(units: &[u8], string: &mut String) -> Result<(), ASEError> {
+    string.clear();
+    for c in decode_utf16_units(units) {
+        string.push(c.map_err(|_| ASEError::UTF16Error)?);
+    }
+    Ok(())
+}
+
+/// Decodes big-endian UTF-16 code units, replacing invalid code units with
+/// [`char::REPLACEMENT_CHARACTER`].
+pub(crate) fn decode_utf16_units_lossy(units: &[u8]) -> String {
+    decode_utf16_units(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Decodes little-endian UTF-16 code units into `string`, replacing its previous content.
+///
+/// Only used to recover names of broken files, names are always written as big-endian.
+///
+/// # Errors
+/// This function will return an error if the code units are not valid UTF-16.
+pub(crate) fn decode_utf16_le_units_into(
+    units: &[u8],
+    string: &mut String,
+) -> Result<(), ASEError> {
+    string.clear();
+    let units = units
+        .chunks_exact(2)
+        .filter_map(|bytes| bytes.try_into().ok())
+        .map(u16::from_le_bytes);
+    for c in char::decode_utf16(units) {
+        string.push(c.map_err(|_| ASEError::UTF16Error)?);
+    }
+    Ok(())
+}
+
+/// Returns whether big-endian UTF-16 code units look like a little-endian string.
+///
+/// Swapped Latin-1 characters leave the low byte of every code unit zero, while none
+/// of them have a zero high byte, unlike big-endian Latin-1 text. To not mistake CJK
+/// characters, more than half of the swapped characters must be ASCII and a single
+/// character is never swapped.
+pub(crate) fn looks_like_utf16_le(units: &[u8]) -> bool {
+    let mut ascii = 0_usize;
+    let mut count = 0_usize;
+    for unit in units.chunks_exact(2) {
+        match unit {
+            [high, 0] if *high != 0 => {
+                count = count.saturating_add(1);
+                if high.is_ascii() {
+                    ascii = ascii.saturating_add(1);
+                }
+            }
+            _ => return false,
+        }
+    }
+    count > 1 && ascii > count / 2
+}
+
+/// Checks that big-endian UTF-16 code units are valid, without decoding them into a string.
+///
+/// # Errors
+/// This function will return an error if the code units are not valid UTF-16.
+pub(crate) fn validate_utf16_units(units: &[u8]) -> Result<(), ASEError> {
+    if decode_utf16_units(units).any(|c| c.is_err()) {
+        return Err(ASEError::UTF16Error);
+    }
+    Ok(())
+}
+
+/// Returns the UTF-16 code units of a string written by [`put_utf16_nul_str`], up to its
+/// first null character, without decoding them.
+///
+/// Returns the code units and the number of bytes the string takes up, see [`get_utf16_nul_str`].
+///
+/// # Errors
+/// This function will return an error if the string is truncated.
+pub(crate) fn get_utf16_nul_units(bytes: &[u8]) -> Result<(&[u8], usize), ASEError> {
+    let length = get_u16_be(bytes)?;
+    // the length includes the null terminator, which follows the length field
+    let end = usize::from(length).saturating_mul(2);
+    let units = bytes.get(2..end).ok_or(ASEError::InputDataParseError)?;
+    // stop before null byte
+    let name_length = units
+        .chunks_exact(2)
+        .position(|unit| unit == [0, 0])
+        .map_or(units.len(), |position| position.saturating_mul(2));
+    Ok((
+        units.get(..name_length).unwrap_or_default(),
+        end.saturating_add(2),
+    ))
+}
+
+/// Returns the UTF-16 code units of a string, whose length does not count its null
+/// terminator, and the number of bytes the string takes up.
+///
+/// Returns `None`, if the string is not followed by a null terminator or contains one.
+pub(crate) fn get_utf16_uncounted_nul_units(bytes: &[u8]) -> Option<(&[u8], usize)> {
+    let length = get_u16_be(bytes).ok()?;
+    let end = usize::from(length).saturating_mul(2).saturating_add(2);
+    let units = bytes.get(2..end)?;
+    let terminated = bytes.get(end..end.saturating_add(2))? == [0, 0];
+    (terminated && !units.chunks_exact(2).any(|unit| unit == [0, 0]))
+        .then_some((units, end.saturating_add(2)))
+}
+
+/// Where the null terminator of a string is found, relative to its length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Terminator {
+    /// The length counts the terminator, like strings written by [`put_utf16_nul_str`].
+    Counted,
+    /// The terminator follows the characters counted by the length.
+    Uncounted,
+    /// The string is not followed by a terminator.
+    Missing,
+}
+
+/// Decodes big-endian UTF-16 code units, as returned by [`get_utf16_nul_units`].
+pub(crate) fn decode_utf16_units(
+    units: &[u8],
+) -> impl Iterator<Item = Result<char, std::char::DecodeUtf16Error>> + '_ {
+    char::decode_utf16(
+        units
+            .chunks_exact(2)
+            .filter_map(|bytes| bytes.try_into().ok())
+            .map(u16::from_be_bytes),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_what_it_writes() {
+        let mut buf = Vec::new();
+        put_u16_be(&mut buf, 0xc001);
+        put_u32_be(&mut buf, 0x0102_0304);
+        put_f32_be(&mut buf, -0.25);
+        assert_eq!(buf, vec![0xc0, 0x01, 1, 2, 3, 4, 0xbe, 0x80, 0, 0]);
+        assert_eq!(get_u16_be(&buf).unwrap(), 0xc001);
+        assert_eq!(get_u32_be(&buf[2..]).unwrap(), 0x0102_0304);
+        assert_eq!(get_f32_be(&buf[6..]).unwrap(), -0.25);
+        assert!(matches!(
+            get_f32_be(&buf[7..]),
+            Err(ASEError::InputDataParseError)
+        ));
+    }
+
+    #[test]
+    fn it_reads_what_it_writes_for_strings() {
+        for string in ["", "name", "aä😀", "Grün – 緑"] {
+            let mut buf = Vec::new();
+            put_utf16_nul_str(&mut buf, string);
+            // trailing data is not read
+            buf.extend_from_slice(&[0xff; 3]);
+            let (read, length) = get_utf16_nul_str(&buf).unwrap();
+            assert_eq!(read, string);
+            assert_eq!(length, buf.len() - 3);
+            assert_eq!(
+                usize::from(get_u16_be(&buf).unwrap()),
+                string.encode_utf16().count() + 1
+            );
+        }
+    }
+
+    #[test]
+    fn it_reads_strings_up_to_null() {
+        let mut buf = Vec::new();
+        put_utf16_nul_str(&mut buf, "null\0inside");
+        buf.extend_from_slice(&[0xff; 3]);
+        let (read, length) = get_utf16_nul_str(&buf).unwrap();
+        assert_eq!(read, "null");
+        // the whole string is skipped
+        assert_eq!(length, buf.len() - 3);
+    }
+
+    #[test]
+    fn it_detects_little_endian_strings() {
+        let little_endian = |string: &str| -> Vec<u8> {
+            string.encode_utf16().flat_map(u16::to_le_bytes).collect()
+        };
+        let big_endian = |string: &str| -> Vec<u8> {
+            string.encode_utf16().flat_map(u16::to_be_bytes).collect()
+        };
+        for string in ["name", "Grün", "ab"] {
+            let units = little_endian(string);
+            assert!(looks_like_utf16_le(&units), "{string}");
+            assert!(!looks_like_utf16_le(&big_endian(string)), "{string}");
+            let mut read = String::new();
+            decode_utf16_le_units_into(&units, &mut read).unwrap();
+            assert_eq!(read, string);
+        }
+        // CJK characters with a zero low byte, which swap to ASCII
+        for string in ["", "a", "一", "一丁", "緑", "äöü"] {
+            assert!(!looks_like_utf16_le(&little_endian(string)), "{string}");
+        }
+        // "一丁" as big-endian
+        assert!(!looks_like_utf16_le(&[0x4e, 0x00, 0x4e, 0x01]));
+    }
+
+    #[test]
+    fn it_rejects_truncated_strings() {
+        let mut buf = Vec::new();
+        put_utf16_nul_str(&mut buf, "name");
+        assert!(matches!(
+            get_utf16_nul_str(&buf[..6]),
+            Err(ASEError::InputDataParseError)
+        ));
+        assert!(matches!(
+            get_utf16_nul_str(&[0xd8, 0x00]),
+            Err(ASEError::InputDataParseError)
+        ));
+        assert!(matches!(
+            get_utf16_nul_str(&[0, 2, 0xd8, 0x00, 0, 0]),
+            Err(ASEError::UTF16Error)
+        ));
+    }
+}
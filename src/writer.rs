@@ -0,0 +1,1411 @@
+// the sizes of the written data are bounded by the data in memory
+#![allow(clippy::arithmetic_side_effects)]
+
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::HashSet,
+    io::{Seek, SeekFrom, Write},
+};
+
+use crate::{
+    buffer::Buffer,
+    error::{BlockError, ConformationError},
+    types::{self, BlockType},
+    ASEError, ColorBlock, ColorType, ColorValue, GrayPolarity, Group,
+};
+
+/// Options for writing .ase files, see [`create_ase_with_options`](crate::create_ase_with_options).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// How names, which cannot be read correctly by other applications, are handled.
+    pub name_encoding: NameEncoding,
+    /// How colors are stored in groups and counted in the header.
+    pub group_layout: GroupLayout,
+    /// Whether `GroupEnd` blocks are written with a length of zero.
+    ///
+    /// Adobe applications write the length, like for any other block, while it is
+    /// omitted by default.
+    pub group_end_length: bool,
+    /// Whether color components are clamped to the range of their color model before
+    /// writing them, see [`ColorValue::clamped`](crate::ColorValue::clamped).
+    ///
+    /// Components are written as they are by default, so they are read back unchanged.
+    pub clamp_components: bool,
+    /// Whether semantically equal documents are written as identical bytes.
+    ///
+    /// Groups, the colors within them and single colors are sorted by their name, ties
+    /// are broken by their color values and types. Negative zero components are written
+    /// as positive zero. The remaining options are applied as they are, so they have to
+    /// be the same to get identical bytes.
+    ///
+    /// Writers, which write blocks one at a time, like [`AseWriter`], cannot sort them and
+    /// only normalize the components.
+    pub canonical: bool,
+    /// The version written to the header, [`FILE_VERSION`](crate::FILE_VERSION) if `None`.
+    ///
+    /// Writing fails with [`ConformationError::FileVersion`] if the major version is not
+    /// `1`, unless [`WriteOptions::force_version`] is set.
+    pub version: Option<u32>,
+    /// Whether the [`WriteOptions::version`] is written, even if its major version is not
+    /// supported. These files are not read by [`read_ase`](crate::read_ase).
+    pub force_version: bool,
+    /// Whether colors with an empty name are written with a generated name, as some
+    /// applications drop them.
+    ///
+    /// RGB colors are named after their hex code, like `#bf616a`, other colors after
+    /// their position in the file, like `Color 17`. A number is appended to names, which
+    /// are already used by another color. The changes are recorded in the [`WriteReport`].
+    pub placeholder_names: bool,
+    /// How L* of Lab colors is stored.
+    pub lab_scaling: LabScaling,
+    /// The color types written in place of the color types of the colors.
+    pub color_types: ColorTypeMap,
+    /// How gray values are stored, see [`ParseOptions::gray_polarity`](crate::ParseOptions::gray_polarity).
+    pub gray_polarity: GrayPolarity,
+}
+
+/// Maps each [`ColorType`] to the color type written in its place.
+///
+/// # Examples
+/// ```rust
+/// # use adobe_swatch_exchange::{ColorType, ColorTypeMap};
+/// // only convert global colors
+/// let map = ColorTypeMap {
+///     global: ColorType::Normal,
+///     ..Default::default()
+/// };
+/// assert_eq!(map.map(&ColorType::Global), ColorType::Normal);
+/// assert_eq!(map.map(&ColorType::Spot), ColorType::Spot);
+/// assert_eq!(ColorTypeMap::all(ColorType::Spot).map(&ColorType::Normal), ColorType::Spot);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorTypeMap {
+    /// The color type written for [`ColorType::Global`] colors.
+    pub global: ColorType,
+    /// The color type written for [`ColorType::Spot`] colors.
+    pub spot: ColorType,
+    /// The color type written for [`ColorType::Normal`] colors.
+    pub normal: ColorType,
+}
+
+impl ColorTypeMap {
+    /// Keeps every color type, this is the default.
+    pub const IDENTITY: ColorTypeMap = ColorTypeMap {
+        global: ColorType::Global,
+        spot: ColorType::Spot,
+        normal: ColorType::Normal,
+    };
+
+    /// Writes `color_type` for all colors.
+    pub fn all(color_type: ColorType) -> Self {
+        Self {
+            global: color_type.clone(),
+            spot: color_type.clone(),
+            normal: color_type,
+        }
+    }
+
+    /// Returns the color type written in place of `color_type`.
+    pub fn map(&self, color_type: &ColorType) -> ColorType {
+        match color_type {
+            ColorType::Global => self.global.clone(),
+            ColorType::Spot => self.spot.clone(),
+            ColorType::Normal => self.normal.clone(),
+        }
+    }
+}
+
+impl Default for ColorTypeMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// How L* of Lab colors, ranging from `0.0` to `100.0`, is stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabScaling {
+    /// L* is divided by 100, so it ranges from `0.0` to `1.0`, like in files written by
+    /// Adobe applications. This is the scaling expected by [`read_ase`](crate::read_ase).
+    #[default]
+    Normalized,
+    /// L* is stored as it is, ranging from `0.0` to `100.0`.
+    Raw,
+}
+
+/// How colors are stored in groups and counted in the header.
+///
+/// Both layouts are read by [`read_ase`](crate::read_ase).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupLayout {
+    /// The length of the group block includes its colors.
+    ///
+    /// The header counts each group as a single block, and its colors are not counted.
+    /// As readers cannot distinguish empty groups from groups in the [`GroupLayout::Flat`]
+    /// layout, their `GroupEnd` block is counted as well.
+    #[default]
+    Nested,
+    /// The length of the group block only includes its name, the colors follow as
+    /// separate blocks.
+    ///
+    /// The header counts every block, including colors and `GroupEnd` blocks. This is
+    /// the layout written by Adobe applications, like Illustrator and Photoshop.
+    Flat,
+}
+
+/// Handling of names, which cannot be read correctly by other applications.
+///
+/// Names are null terminated, so applications stop reading them at the first null
+/// character, misinterpreting the rest of the file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameEncoding {
+    /// Null characters are removed, the changes are recorded in the [`WriteReport`].
+    #[default]
+    Strip,
+    /// Names are written as they are.
+    ///
+    /// Names containing a null character are read up to it, including by
+    /// [`read_ase`](crate::read_ase).
+    Raw,
+    /// Writing fails with [`BlockError::NullInName`] on names containing a null character.
+    Strict,
+    /// Null characters are replaced by `U+FFFD`, the changes are recorded in the [`WriteReport`].
+    Lossy,
+}
+
+/// Changes made while writing an .ase file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriteReport {
+    /// Names, which were changed using [`NameEncoding::Strip`], [`NameEncoding::Lossy`] or
+    /// [`WriteOptions::placeholder_names`].
+    pub replaced_names: Vec<NameReplacement>,
+}
+
+/// A name, which was changed before writing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameReplacement {
+    /// The name of the group or color.
+    pub original: String,
+    /// The name written to the file.
+    pub written: String,
+}
+
+/// Writes blocks to a [`Buffer`], according to the [`WriteOptions`].
+pub(crate) struct Writer<'a> {
+    pub(crate) buf: Buffer,
+    pub(crate) options: &'a WriteOptions,
+    pub(crate) report: WriteReport,
+    /// Names of the colors, used to generate unique placeholder names.
+    color_names: HashSet<String>,
+    /// Number of colors written so far.
+    colors: usize,
+}
+
+impl<'a> Writer<'a> {
+    /// Create a new Writer with the specified capacity.
+    pub(crate) fn new(capacity: usize, options: &'a WriteOptions) -> Self {
+        Self {
+            buf: Buffer::with_capacity(capacity),
+            options,
+            report: WriteReport::default(),
+            color_names: HashSet::new(),
+            colors: 0,
+        }
+    }
+
+    /// Create a new Writer, which appends to `vec`.
+    pub(crate) fn from_vec(vec: Vec<u8>, options: &'a WriteOptions) -> Self {
+        Self {
+            buf: Buffer::from_vec(vec),
+            options,
+            report: WriteReport::default(),
+            color_names: HashSet::new(),
+            colors: 0,
+        }
+    }
+
+    /// Write the length of the name, followed by the null terminated name.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the name is too long, or it is rejected by
+    /// the [`WriteOptions::name_encoding`].
+    pub(crate) fn write_name(&mut self, name: &str) -> Result<(), ASEError> {
+        if is_name_too_long(name) {
+            return Err(ASEError::InvalidBlock {
+                name: name.to_owned(),
+                reason: BlockError::NameTooLong,
+            });
+        }
+
+        let contains_null = name.contains('\0');
+        let replacement = match self.options.name_encoding {
+            NameEncoding::Strict if contains_null => {
+                return Err(ASEError::InvalidBlock {
+                    name: name.to_owned(),
+                    reason: BlockError::NullInName,
+                })
+            }
+            NameEncoding::Strip if contains_null => "",
+            // both are a single UTF-16 code unit, so the block lengths do not change
+            NameEncoding::Lossy if contains_null => "\u{FFFD}",
+            _ => {
+                self.buf.write_utf16_nul_str(name);
+                return Ok(());
+            }
+        };
+
+        let replaced = name.replace('\0', replacement);
+        self.buf.write_utf16_nul_str(&replaced);
+        self.report.replaced_names.push(NameReplacement {
+            original: name.to_owned(),
+            written: replaced,
+        });
+        Ok(())
+    }
+
+    /// Write the length of a block, followed by its content written by `write_content`.
+    ///
+    /// The length is computed from the written bytes, instead of being calculated in advance.
+    ///
+    /// # Errors
+    ///
+    /// This function will return any error returned by `write_content`.
+    pub(crate) fn write_with_length(
+        &mut self,
+        write_content: impl FnOnce(&mut Self) -> Result<(), ASEError>,
+    ) -> Result<(), ASEError> {
+        let length_position = self.buf.position();
+        self.buf.write_u32(0);
+        write_content(self)?;
+        let length = self.buf.position() - length_position - 4;
+        self.buf.write_u32_at(length_position, length as u32);
+        Ok(())
+    }
+
+    /// Records the names of the colors, so placeholder names do not clash with colors
+    /// written later.
+    pub(crate) fn reserve_color_names<'n>(&mut self, names: impl IntoIterator<Item = &'n str>) {
+        if self.options.placeholder_names {
+            self.color_names
+                .extend(names.into_iter().map(ToOwned::to_owned));
+        }
+    }
+
+    /// Returns the name written for a color, see [`WriteOptions::placeholder_names`].
+    pub(crate) fn color_name<'n>(&mut self, name: &'n str, color: &ColorValue) -> Cow<'n, str> {
+        self.colors += 1;
+        if !self.options.placeholder_names {
+            return Cow::Borrowed(name);
+        }
+        if !name.is_empty() {
+            self.color_names.insert(name.to_owned());
+            return Cow::Borrowed(name);
+        }
+
+        let base = match color {
+            ColorValue::Rgb(..) => color.to_srgb_hex(),
+            _ => format!("Color {}", self.colors),
+        };
+        let mut placeholder = base.clone();
+        let mut suffix = 2;
+        while self.color_names.contains(&placeholder) {
+            placeholder = format!("{base} {suffix}");
+            suffix += 1;
+        }
+        self.color_names.insert(placeholder.clone());
+        self.report.replaced_names.push(NameReplacement {
+            original: name.to_owned(),
+            written: placeholder.clone(),
+        });
+        Cow::Owned(placeholder)
+    }
+
+    /// Write the file signature, version and number of blocks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the version is rejected by the options.
+    pub(crate) fn write_file_header(&mut self, blocks: u32) -> Result<(), ASEError> {
+        let version = self.options.version.unwrap_or(types::FILE_VERSION);
+        if version >> 16 != types::FILE_VERSION >> 16 && !self.options.force_version {
+            return Err(ASEError::Invalid(ConformationError::FileVersion));
+        }
+        self.buf.write_slice(types::FILE_SIGNATURE);
+        self.buf.write_u32(version);
+        self.buf.write_u32(blocks);
+        Ok(())
+    }
+
+    /// Write a `GroupEnd` block.
+    pub(crate) fn write_group_end(&mut self) {
+        self.buf.write_u16(BlockType::GroupEnd.into());
+        if self.options.group_end_length {
+            self.buf.write_u32(0);
+        }
+    }
+
+    /// Returns the number of blocks counted in the header for a group with
+    /// `colors` colors, see [`GroupLayout`].
+    pub(crate) fn group_block_count(&self, colors: usize) -> u32 {
+        match self.options.group_layout {
+            GroupLayout::Nested if colors == 0 => 2,
+            GroupLayout::Nested => 1,
+            // group start and end
+            GroupLayout::Flat => colors as u32 + 2,
+        }
+    }
+
+    /// Returns the color values as they are written, according to the options.
+    pub(crate) fn color_values<'c>(&self, color: &'c ColorValue) -> Cow<'c, ColorValue> {
+        let mut color = Cow::Borrowed(color);
+        if self.options.gray_polarity != GrayPolarity::ZeroIsBlack {
+            color = Cow::Owned(color.with_gray_polarity(self.options.gray_polarity));
+        }
+        if self.options.clamp_components {
+            color = Cow::Owned(color.clamped());
+        }
+        if self.options.canonical {
+            color = Cow::Owned(color.without_negative_zero());
+        }
+        color
+    }
+
+    /// Returns the written bytes.
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        self.buf.into_vec()
+    }
+}
+
+/// Returns `true` if the name does not fit into the length field of names.
+pub(crate) fn is_name_too_long(name: &str) -> bool {
+    // the length includes the null terminator, every byte of UTF-8 encodes to at most
+    // one UTF-16 code unit, so most names do not have to be counted
+    name.len() >= usize::from(u16::MAX) && name.encode_utf16().count() >= usize::from(u16::MAX)
+}
+
+/// Truncates the name to the longest prefix, which fits into the length field of names.
+pub(crate) fn truncate_long_name(name: &mut String) {
+    if !is_name_too_long(name) {
+        return;
+    }
+    let mut units = 0;
+    let end = name
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units >= usize::from(u16::MAX)
+        })
+        .map_or(name.len(), |(index, _)| index);
+    name.truncate(end);
+}
+
+/// Truncates the names of the group and its colors, see [`truncate_long_name`].
+pub(crate) fn truncate_group_names(group: &mut Group) {
+    truncate_long_name(&mut group.name);
+    for block in &mut group.blocks {
+        truncate_long_name(&mut block.name);
+    }
+}
+
+/// Truncates the names of all groups and colors, see [`truncate_long_name`].
+pub(crate) fn truncate_long_names(groups: &mut [Group], colors: &mut [ColorBlock]) {
+    groups.iter_mut().for_each(truncate_group_names);
+    for block in colors {
+        truncate_long_name(&mut block.name);
+    }
+}
+
+/// Returns the number of bytes written for the groups and colors using the given options.
+pub(crate) fn encoded_size(
+    groups: &[Group],
+    colors: &[ColorBlock],
+    options: &WriteOptions,
+) -> usize {
+    let groups: usize = groups.iter().map(|group| group_size(group, options)).sum();
+    let colors: usize = colors.iter().map(|block| color_size(block, options)).sum();
+    HEADER_SIZE + groups + colors
+}
+
+/// Size of the signature, version and number of blocks.
+pub(crate) const HEADER_SIZE: usize = 12;
+
+/// Returns the number of bytes written for the group, including its `GroupEnd` block.
+pub(crate) fn group_size(group: &Group, options: &WriteOptions) -> usize {
+    // the group end block, both layouts take up the same space otherwise
+    let group_end = if options.group_end_length { 2 + 4 } else { 2 };
+    let names = group.blocks.iter().map(|block| block.name.as_str());
+    let nulls = null_size(names.chain([group.name.as_str()]), options);
+    // block type and length
+    2 + 4 + group.calculate_length() as usize + group_end + nulls
+}
+
+/// Returns the number of bytes written for the color.
+pub(crate) fn color_size(block: &ColorBlock, options: &WriteOptions) -> usize {
+    // block type and length
+    2 + 4 + block.calculate_length() as usize + null_size([block.name.as_str()], options)
+}
+
+/// Returns the number of UTF-16 code units of the name, as it is written by default.
+pub(crate) fn name_units(name: &str) -> usize {
+    name.encode_utf16().filter(|&unit| unit != 0).count()
+}
+
+/// Returns the number of bytes taken up by null characters in the names, which are not
+/// stripped by the [`WriteOptions::name_encoding`].
+fn null_size<'n>(names: impl IntoIterator<Item = &'n str>, options: &WriteOptions) -> usize {
+    if options.name_encoding == NameEncoding::Strip {
+        return 0;
+    }
+    names
+        .into_iter()
+        .map(|name| name.matches('\0').count() * 2)
+        .sum()
+}
+
+/// Returns copies of the groups and colors, sorted in the order of [`WriteOptions::canonical`].
+pub(crate) fn canonical_order(
+    groups: &[Group],
+    colors: &[ColorBlock],
+) -> (Vec<Group>, Vec<ColorBlock>) {
+    let mut groups = groups.to_vec();
+    for group in &mut groups {
+        group.blocks.sort_by(compare_blocks);
+    }
+    groups.sort_by(|a, b| {
+        a.name.cmp(&b.name).then_with(|| {
+            // both are sorted already
+            a.blocks
+                .iter()
+                .zip(&b.blocks)
+                .map(|(a, b)| compare_blocks(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.blocks.len().cmp(&b.blocks.len()))
+        })
+    });
+    let mut colors = colors.to_vec();
+    colors.sort_by(compare_blocks);
+    (groups, colors)
+}
+
+/// Compares colors by their name, then by their written color values and type.
+fn compare_blocks(a: &ColorBlock, b: &ColorBlock) -> Ordering {
+    let values = |block: &ColorBlock| {
+        let mut buf = Buffer::with_capacity(20);
+        buf.write_slice(block.color.get_type());
+        block
+            .color
+            .without_negative_zero()
+            .write_values(&mut buf, LabScaling::default());
+        buf.write_u16(block.color_type.clone() as u16);
+        buf.into_vec()
+    };
+    a.name.cmp(&b.name).then_with(|| values(a).cmp(&values(b)))
+}
+
+/// The default options, usable for any lifetime.
+pub(crate) const DEFAULT_OPTIONS: WriteOptions = WriteOptions {
+    name_encoding: NameEncoding::Strip,
+    group_layout: GroupLayout::Nested,
+    group_end_length: false,
+    clamp_components: false,
+    canonical: false,
+    version: None,
+    force_version: false,
+    placeholder_names: false,
+    lab_scaling: LabScaling::Normalized,
+    color_types: ColorTypeMap::IDENTITY,
+    gray_polarity: GrayPolarity::ZeroIsBlack,
+};
+
+/// Writes an .ase file block by block, without knowing all groups and colors in advance.
+///
+/// The number of blocks is written to the header once the file is [finished](AseWriter::finish).
+/// Groups are kept in memory until they are ended, as their length includes their colors.
+///
+/// # Examples
+/// ```rust
+/// # use std::io::Cursor;
+/// # use adobe_swatch_exchange::{create_ase, AseWriter, ColorBlock, ColorType, ColorValue, Group};
+/// let color = ColorBlock::new("name".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+///
+/// let mut writer = AseWriter::new(Cursor::new(Vec::new())).unwrap();
+/// writer.start_group("group").unwrap();
+/// writer.write_color(color.clone()).unwrap();
+/// writer.end_group().unwrap();
+/// let bytes = writer.finish().unwrap().into_inner();
+///
+/// assert_eq!(bytes, create_ase(vec![Group::new("group".to_owned(), vec![color])], vec![]));
+/// ```
+pub struct AseWriter<'a, W: Write + Seek> {
+    sink: W,
+    /// Position of the header in the sink.
+    start: u64,
+    encoder: BlockEncoder<'a>,
+}
+
+impl<W: Write + Seek> AseWriter<'static, W> {
+    /// Writes the header of the file to `sink`, using the default [`WriteOptions`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if writing to `sink` fails.
+    pub fn new(sink: W) -> Result<Self, ASEError> {
+        Self::with_options(sink, &DEFAULT_OPTIONS)
+    }
+}
+
+impl<'a, W: Write + Seek> AseWriter<'a, W> {
+    /// Writes the header of the file to `sink`, using the given options.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to `sink` fails, or the version
+    /// is rejected by the options.
+    pub fn with_options(mut sink: W, options: &'a WriteOptions) -> Result<Self, ASEError> {
+        let start = sink.stream_position()?;
+        let mut ase_writer = Self {
+            sink,
+            start,
+            encoder: BlockEncoder::new(options)?,
+        };
+        ase_writer.flush()?;
+        Ok(ase_writer)
+    }
+
+    /// Starts a group, all colors written until [`AseWriter::end_group`] are part of it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if another group was not ended yet, or the name
+    /// is rejected by the options.
+    pub fn start_group(&mut self, name: &str) -> Result<(), ASEError> {
+        if self.encoder.start_group(name)? {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes a color, either to the current group or as a single color.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or the
+    /// name is rejected by the options.
+    pub fn write_color(&mut self, block: ColorBlock) -> Result<(), ASEError> {
+        if self.encoder.write_color(block)? {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Ends the current group.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or no
+    /// group was started.
+    pub fn end_group(&mut self) -> Result<(), ASEError> {
+        self.encoder.end_group()?;
+        self.flush()
+    }
+
+    /// Returns the changes made while writing so far.
+    pub fn report(&self) -> &WriteReport {
+        self.encoder.report()
+    }
+
+    /// Writes the number of blocks to the header, returning the sink.
+    ///
+    /// The sink is positioned at the end of the written file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either writing to the sink fails, or a
+    /// group was not ended.
+    pub fn finish(mut self) -> Result<W, ASEError> {
+        let blocks = self.encoder.finish()?;
+        let end = self.sink.stream_position()?;
+        self.sink.seek(SeekFrom::Start(self.start + 8))?;
+        self.sink.write_all(&blocks.to_be_bytes())?;
+        self.sink.seek(SeekFrom::Start(end))?;
+        Ok(self.sink)
+    }
+
+    /// Passes the buffered bytes to the sink.
+    fn flush(&mut self) -> Result<(), ASEError> {
+        self.sink.write_all(self.encoder.buffered())?;
+        self.encoder.clear();
+        Ok(())
+    }
+}
+
+/// Encodes the blocks written using an incremental writer, such as [`AseWriter`], into a
+/// buffer, independent of how the buffer is passed to the sink.
+pub(crate) struct BlockEncoder<'a> {
+    writer: Writer<'a>,
+    /// Number of blocks counted in the header.
+    blocks: u32,
+    group: Option<OpenGroup>,
+}
+
+/// A group, which was started, but not ended yet.
+struct OpenGroup {
+    /// Position of the group length in the buffer.
+    length_position: usize,
+    /// Number of colors in the group.
+    colors: usize,
+}
+
+impl<'a> BlockEncoder<'a> {
+    /// Creates an encoder, whose buffer contains the header of the file.
+    ///
+    /// The number of blocks in the header is left as zero, see [`BlockEncoder::finish`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the version is rejected by the options.
+    pub(crate) fn new(options: &'a WriteOptions) -> Result<Self, ASEError> {
+        let mut writer = Writer::new(12, options);
+        // number of blocks, written when finishing
+        writer.write_file_header(0)?;
+        Ok(Self {
+            writer,
+            blocks: 0,
+            group: None,
+        })
+    }
+
+    /// Starts a group, returning `true` if the buffer should be passed to the sink.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if another group was not ended yet, or the name
+    /// is rejected by the options.
+    pub(crate) fn start_group(&mut self, name: &str) -> Result<bool, ASEError> {
+        if self.group.is_some() {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+        self.writer.buf.write_u16(BlockType::GroupStart.into());
+        let length_position = self.writer.buf.position();
+        self.writer.buf.write_u32(0);
+        if let Err(err) = self.writer.write_name(name) {
+            self.writer.buf.clear();
+            return Err(err);
+        }
+        self.group = Some(OpenGroup {
+            length_position,
+            colors: 0,
+        });
+        if self.writer.options.group_layout == GroupLayout::Flat {
+            // the colors are not part of the group block, so it can be written right away
+            self.patch_group_length(length_position);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Writes a color, returning `true` if the buffer should be passed to the sink.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the name is rejected by the options.
+    pub(crate) fn write_color(&mut self, block: ColorBlock) -> Result<bool, ASEError> {
+        let position = self.writer.buf.position();
+        if let Err(err) = block.write(&mut self.writer) {
+            self.writer.buf.truncate(position);
+            return Err(err);
+        }
+        match self.group.as_mut() {
+            Some(group) => {
+                group.colors += 1;
+                Ok(self.writer.options.group_layout == GroupLayout::Flat)
+            }
+            None => {
+                self.blocks += 1;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Ends the current group, after which the buffer should be passed to the sink.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no group was started.
+    pub(crate) fn end_group(&mut self) -> Result<(), ASEError> {
+        let group = self
+            .group
+            .take()
+            .ok_or(ASEError::Invalid(ConformationError::GroupEnd))?;
+        if self.writer.options.group_layout == GroupLayout::Nested {
+            self.patch_group_length(group.length_position);
+        }
+        self.writer.write_group_end();
+        self.blocks += self.writer.group_block_count(group.colors);
+        Ok(())
+    }
+
+    /// Writes the length of the group block, whose length field is at `length_position`,
+    /// using the bytes written after it.
+    fn patch_group_length(&mut self, length_position: usize) {
+        let length = self.writer.buf.position() - length_position - 4;
+        self.writer.buf.write_u32_at(length_position, length as u32);
+    }
+
+    /// Returns the changes made while writing so far.
+    pub(crate) fn report(&self) -> &WriteReport {
+        &self.writer.report
+    }
+
+    /// Returns the number of blocks to write to the header.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a group was not ended.
+    pub(crate) fn finish(&self) -> Result<u32, ASEError> {
+        if self.group.is_some() {
+            return Err(ASEError::Invalid(ConformationError::GroupEnd));
+        }
+        Ok(self.blocks)
+    }
+
+    /// Returns the bytes, which were not passed to the sink yet.
+    pub(crate) fn buffered(&self) -> &[u8] {
+        self.writer.buf.as_slice()
+    }
+
+    /// Clears the buffer, after it was passed to the sink.
+    pub(crate) fn clear(&mut self) {
+        self.writer.buf.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        create_ase, create_ase_blocks, create_ase_from_iter, create_ase_into, create_ase_ref,
+        create_ase_with_options, create_ase_with_report, read_ase, read_ase_with_options,
+        test_util::named, ColorModel, ColorValue, Group, Limits, ParseOptions, FILE_VERSION,
+    };
+
+    use super::*;
+
+    /// Writes the document using an [`AseWriter`].
+    fn write_streaming(groups: Vec<Group>, colors: Vec<ColorBlock>) -> Vec<u8> {
+        write_streaming_with_options(groups, colors, &WriteOptions::default())
+    }
+
+    /// Writes the document using an [`AseWriter`] with the given options.
+    fn write_streaming_with_options(
+        groups: Vec<Group>,
+        colors: Vec<ColorBlock>,
+        options: &WriteOptions,
+    ) -> Vec<u8> {
+        let mut writer = AseWriter::with_options(Cursor::new(Vec::new()), options).unwrap();
+        for group in groups {
+            writer.start_group(&group.name).unwrap();
+            for block in group.blocks {
+                writer.write_color(block).unwrap();
+            }
+            writer.end_group().unwrap();
+        }
+        for block in colors {
+            writer.write_color(block).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn options(name_encoding: NameEncoding) -> WriteOptions {
+        WriteOptions {
+            name_encoding,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_rejects_null_in_strict_mode() {
+        let res = create_ase_with_options(
+            vec![],
+            vec![named("bad\0name", ColorValue::Gray(0.5))],
+            &options(NameEncoding::Strict),
+        );
+        assert!(
+            matches!(
+                res,
+                Err(ASEError::InvalidBlock { ref name, reason: BlockError::NullInName }) if name == "bad\0name"
+            ),
+            "Only ASEError::InvalidBlock should be returned"
+        );
+
+        let res = create_ase_with_options(
+            vec![Group::new("group\0".to_owned(), vec![])],
+            vec![],
+            &options(NameEncoding::Strict),
+        );
+        assert!(matches!(res, Err(ASEError::InvalidBlock { .. })));
+    }
+
+    #[test]
+    fn it_replaces_null_in_lossy_mode() {
+        let group = Group::new(
+            "group\0".to_owned(),
+            vec![named("bad\0name", ColorValue::Gray(0.5))],
+        );
+        let (bytes, report) =
+            create_ase_with_report(vec![group], vec![], &options(NameEncoding::Lossy)).unwrap();
+        assert_eq!(
+            report.replaced_names,
+            vec![
+                NameReplacement {
+                    original: "group\0".to_owned(),
+                    written: "group\u{FFFD}".to_owned(),
+                },
+                NameReplacement {
+                    original: "bad\0name".to_owned(),
+                    written: "bad\u{FFFD}name".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            read_ase(&*bytes).unwrap(),
+            (
+                vec![Group::new(
+                    "group\u{FFFD}".to_owned(),
+                    vec![named("bad\u{FFFD}name", ColorValue::Gray(0.5))],
+                )],
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn it_strips_null_by_default() {
+        let group = Group::new(
+            "null\0inside".to_owned(),
+            vec![named("\0bad\0name\0", ColorValue::Gray(0.5))],
+        );
+        let colors = vec![named("\0", ColorValue::Gray(0.1))];
+        let (bytes, report) = create_ase_with_report(
+            vec![group.clone()],
+            colors.clone(),
+            &WriteOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(report.replaced_names.len(), 3);
+        assert_eq!(report.replaced_names[0].written, "nullinside");
+        assert_eq!(bytes, create_ase(vec![group.clone()], colors.clone()));
+        assert_eq!(
+            bytes.len(),
+            encoded_size(&[group], &colors, &WriteOptions::default())
+        );
+        assert_eq!(
+            read_ase(&*bytes).unwrap(),
+            (
+                vec![Group::new(
+                    "nullinside".to_owned(),
+                    vec![named("badname", ColorValue::Gray(0.5))],
+                )],
+                vec![named("", ColorValue::Gray(0.1))]
+            )
+        );
+    }
+
+    #[test]
+    fn it_keeps_replacement_characters() {
+        let colors = vec![named("lossy \u{FFFD} name", ColorValue::Gray(0.5))];
+        for name_encoding in [
+            NameEncoding::Strip,
+            NameEncoding::Raw,
+            NameEncoding::Strict,
+            NameEncoding::Lossy,
+        ] {
+            let (bytes, report) =
+                create_ase_with_report(vec![], colors.clone(), &options(name_encoding)).unwrap();
+            assert_eq!(bytes, create_ase(vec![], colors.clone()));
+            assert!(report.replaced_names.is_empty());
+        }
+    }
+
+    #[test]
+    fn it_writes_raw_names() {
+        let colors = vec![named("bad\0name", ColorValue::Gray(0.5))];
+        let options = options(NameEncoding::Raw);
+        let (bytes, report) = create_ase_with_report(vec![], colors.clone(), &options).unwrap();
+        assert_eq!(bytes.len(), encoded_size(&[], &colors, &options));
+        assert!(bytes.windows(4).any(|unit| unit == [0, b'd', 0, 0]));
+        assert!(report.replaced_names.is_empty());
+    }
+
+    #[test]
+    fn it_writes_streaming() {
+        let group = Group::new(
+            "group name".to_owned(),
+            vec![
+                named("light grey", ColorValue::Gray(0.5)),
+                named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+            ],
+        );
+        let empty = Group::new("empty".to_owned(), vec![]);
+        let block = named("name", ColorValue::Gray(0.5));
+        let documents = [
+            (vec![], vec![]),
+            (vec![], vec![block.clone()]),
+            (vec![group.clone()], vec![]),
+            (vec![group.clone()], vec![block.clone()]),
+            (vec![empty, group], vec![block]),
+        ];
+        for (groups, colors) in documents {
+            assert_eq!(
+                write_streaming(groups.clone(), colors.clone()),
+                create_ase(groups, colors)
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_unpaired_groups() {
+        let mut writer = AseWriter::new(Cursor::new(Vec::new())).unwrap();
+        assert!(matches!(
+            writer.end_group(),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+        writer.start_group("group").unwrap();
+        assert!(matches!(
+            writer.start_group("nested"),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+        assert!(matches!(
+            writer.finish(),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+    }
+
+    #[test]
+    fn it_keeps_writing_after_rejected_names() {
+        let options = options(NameEncoding::Strict);
+        let mut writer = AseWriter::with_options(Cursor::new(Vec::new()), &options).unwrap();
+        assert!(writer.start_group("bad\0group").is_err());
+        writer.start_group("group").unwrap();
+        assert!(writer
+            .write_color(named("bad\0name", ColorValue::Gray(0.5)))
+            .is_err());
+        writer
+            .write_color(named("name", ColorValue::Gray(0.5)))
+            .unwrap();
+        writer.end_group().unwrap();
+        assert_eq!(
+            writer.finish().unwrap().into_inner(),
+            create_ase(
+                vec![Group::new(
+                    "group".to_owned(),
+                    vec![named("name", ColorValue::Gray(0.5))]
+                )],
+                vec![]
+            )
+        );
+    }
+
+    /// Returns all combinations of group layouts and `GroupEnd` lengths.
+    fn layout_options() -> Vec<WriteOptions> {
+        [GroupLayout::Nested, GroupLayout::Flat]
+            .into_iter()
+            .flat_map(|group_layout| {
+                [false, true].map(|group_end_length| WriteOptions {
+                    group_layout,
+                    group_end_length,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_writes_canonical_output() {
+        let red = named("red", ColorValue::Rgb(1.0, 0.0, 0.0));
+        let negative_red = named("red", ColorValue::Rgb(1.0, -0.0, 0.0));
+        let red_lab = named("red", ColorValue::Lab(54.29, 80.81, 69.89));
+        let gray = named("gray", ColorValue::Gray(0.5));
+        let blue = named("blue", ColorValue::Rgb(0.0, 0.0, 1.0));
+        let groups = vec![
+            Group::new("b".to_owned(), vec![red.clone(), gray.clone()]),
+            Group::new("a".to_owned(), vec![blue.clone()]),
+            Group::new("a".to_owned(), vec![]),
+        ];
+        let shuffled_groups = vec![
+            Group::new("a".to_owned(), vec![blue.clone()]),
+            Group::new("b".to_owned(), vec![gray.clone(), negative_red.clone()]),
+            Group::new("a".to_owned(), vec![]),
+        ];
+        let colors = vec![red_lab.clone(), blue.clone(), red.clone()];
+        let shuffled_colors = vec![negative_red, blue.clone(), red_lab];
+
+        let options = WriteOptions {
+            canonical: true,
+            ..Default::default()
+        };
+        let canonical = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+        assert_eq!(
+            canonical,
+            create_ase_with_options(shuffled_groups.clone(), shuffled_colors.clone(), &options)
+                .unwrap()
+        );
+        assert_ne!(
+            create_ase(groups, colors),
+            create_ase(shuffled_groups, shuffled_colors)
+        );
+
+        let (groups, colors) = read_ase(&*canonical).unwrap();
+        assert_eq!(
+            groups,
+            vec![
+                Group::new("a".to_owned(), vec![]),
+                Group::new("a".to_owned(), vec![blue.clone()]),
+                Group::new("b".to_owned(), vec![gray, red.clone()]),
+            ]
+        );
+        // ties are sorted by the color model
+        assert_eq!(colors[0], blue);
+        assert_eq!(colors[1].color.model(), ColorModel::Lab);
+        assert_eq!(colors[2], red);
+    }
+
+    #[test]
+    fn it_writes_placeholder_names() {
+        let groups = vec![Group::new(
+            "g".to_owned(),
+            vec![
+                named("", ColorValue::Rgb(1.0, 0.0, 0.0)),
+                named("", ColorValue::Gray(0.5)),
+            ],
+        )];
+        let colors = vec![
+            named("", ColorValue::Rgb(1.0, 0.0, 0.0)),
+            named("#ff0000", ColorValue::Rgb(1.0, 0.0, 0.0)),
+            named("", ColorValue::Cmyk(0.0, 0.0, 0.0, 1.0)),
+        ];
+        let (read_groups, read_colors) =
+            read_ase(&*create_ase(groups.clone(), colors.clone())).unwrap();
+        assert_eq!((&read_groups, &read_colors), (&groups, &colors));
+
+        let options = WriteOptions {
+            placeholder_names: true,
+            ..Default::default()
+        };
+        let (bytes, report) =
+            create_ase_with_report(groups.clone(), colors.clone(), &options).unwrap();
+        let (read_groups, read_colors) = read_ase(&*bytes).unwrap();
+        let names: Vec<_> = read_groups[0]
+            .blocks
+            .iter()
+            .chain(&read_colors)
+            .map(|block| block.name.as_str())
+            .collect();
+        // the later color named `#ff0000` keeps its name
+        assert_eq!(
+            names,
+            ["#ff0000 2", "Color 2", "#ff0000 3", "#ff0000", "Color 5"]
+        );
+        assert_eq!(report.replaced_names.len(), 4);
+        assert_eq!(report.replaced_names[1].original, "");
+        assert_eq!(report.replaced_names[1].written, "Color 2");
+
+        let streamed = write_streaming_with_options(groups, colors, &options);
+        let (_, read_colors) = read_ase(&*streamed).unwrap();
+        // streaming writers only know the names written so far
+        assert_eq!(read_colors[0].name, "#ff0000 2");
+        assert_eq!(read_colors[1].name, "#ff0000");
+    }
+
+    #[test]
+    fn it_maps_color_types() {
+        let color = |color_type| ColorBlock::new("c".to_owned(), ColorValue::Gray(0.5), color_type);
+        let groups = vec![Group::new(
+            "g".to_owned(),
+            vec![color(ColorType::Global), color(ColorType::Spot)],
+        )];
+        let colors = vec![color(ColorType::Normal), color(ColorType::Global)];
+        let color_types = |(groups, colors): (Vec<Group>, Vec<ColorBlock>)| {
+            groups
+                .iter()
+                .flat_map(|group| &group.blocks)
+                .chain(&colors)
+                .map(|block| block.color_type.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let options = WriteOptions {
+            color_types: ColorTypeMap::all(ColorType::Spot),
+            ..Default::default()
+        };
+        let bytes = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+        assert_eq!(
+            color_types(read_ase(&*bytes).unwrap()),
+            vec![ColorType::Spot; 4]
+        );
+        // the colors themselves are unchanged
+        assert_eq!(
+            color_types((groups.clone(), colors.clone())),
+            [
+                ColorType::Global,
+                ColorType::Spot,
+                ColorType::Normal,
+                ColorType::Global
+            ]
+        );
+
+        let options = WriteOptions {
+            color_types: ColorTypeMap {
+                global: ColorType::Normal,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let bytes = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+        assert_eq!(
+            color_types(read_ase(&*bytes).unwrap()),
+            [
+                ColorType::Normal,
+                ColorType::Spot,
+                ColorType::Normal,
+                ColorType::Normal
+            ]
+        );
+    }
+
+    #[test]
+    fn it_writes_custom_versions() {
+        let groups = vec![Group::new(
+            "g".to_owned(),
+            vec![named("c", ColorValue::Gray(0.5))],
+        )];
+        let colors = vec![named("c", ColorValue::Gray(0.5))];
+        for version in [FILE_VERSION, 0x0001_0001] {
+            let options = WriteOptions {
+                version: Some(version),
+                ..Default::default()
+            };
+            let bytes = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+            assert_eq!(bytes[4..8], version.to_be_bytes());
+            assert_eq!(read_ase(&*bytes).unwrap(), (groups.clone(), colors.clone()));
+
+            let mut writer = AseWriter::with_options(Cursor::new(Vec::new()), &options).unwrap();
+            writer.write_color(colors[0].clone()).unwrap();
+            let bytes = writer.finish().unwrap().into_inner();
+            assert_eq!(bytes[4..8], version.to_be_bytes());
+        }
+
+        let mut options = WriteOptions {
+            version: Some(0x0002_0000),
+            ..Default::default()
+        };
+        assert!(matches!(
+            create_ase_with_options(groups.clone(), colors.clone(), &options),
+            Err(ASEError::Invalid(ConformationError::FileVersion))
+        ));
+        assert!(matches!(
+            AseWriter::with_options(Cursor::new(Vec::new()), &options),
+            Err(ASEError::Invalid(ConformationError::FileVersion))
+        ));
+        options.force_version = true;
+        let bytes = create_ase_with_options(groups, colors, &options).unwrap();
+        assert_eq!(bytes[4..8], [0, 2, 0, 0]);
+        assert!(matches!(
+            read_ase(&*bytes),
+            Err(ASEError::Invalid(ConformationError::FileVersion))
+        ));
+    }
+
+    #[test]
+    fn it_writes_flat_groups_with_group_end_length() {
+        let group = Group::new("g".to_owned(), vec![named("c", ColorValue::Gray(0.5))]);
+        let options = WriteOptions {
+            group_layout: GroupLayout::Flat,
+            group_end_length: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            create_ase_with_options(vec![group], vec![], &options).unwrap(),
+            vec![
+                65, 83, 69, 70, 0, 1, 0, 0, 0, 0, 0, 3, 192, 1, 0, 0, 0, 6, 0, 2, 0, 103, 0, 0, 0,
+                1, 0, 0, 0, 16, 0, 2, 0, 99, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 192, 2, 0,
+                0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn it_writes_group_end_length() {
+        let groups = vec![Group::new(
+            "g".to_owned(),
+            vec![named("c", ColorValue::Gray(0.5))],
+        )];
+        let colors = vec![named("c", ColorValue::Gray(0.5))];
+        let default = create_ase(groups.clone(), colors.clone());
+        let options = WriteOptions {
+            group_end_length: true,
+            ..Default::default()
+        };
+        let bytes = create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+
+        // the zero length directly follows the group end, before the single color
+        let group_end = 12 + 6 + 6 + 22;
+        assert_eq!(default[group_end..group_end + 2], [192, 2]);
+        assert_eq!(bytes[group_end..group_end + 6], [192, 2, 0, 0, 0, 0]);
+        assert_eq!(bytes[..group_end + 2], default[..group_end + 2]);
+        assert_eq!(bytes[group_end + 6..], default[group_end + 2..]);
+        assert_eq!(read_ase(&*bytes).unwrap(), (groups, colors));
+    }
+
+    #[test]
+    fn it_reads_every_layout() {
+        let groups = vec![
+            Group::new(
+                "group name".to_owned(),
+                vec![
+                    named("light grey", ColorValue::Gray(0.5)),
+                    named("dark red", ColorValue::Rgb(0.5, 0.3, 0.1)),
+                ],
+            ),
+            Group::new("empty".to_owned(), vec![]),
+            Group::new(
+                "single".to_owned(),
+                vec![named("lab", ColorValue::Lab(50.0, 1.0, -1.0))],
+            ),
+        ];
+        let colors = vec![
+            named("name", ColorValue::Gray(0.5)),
+            named("cmyk", ColorValue::Cmyk(0.1, 0.2, 0.3, 0.4)),
+        ];
+        for options in layout_options() {
+            for (groups, colors) in [
+                (groups.clone(), colors.clone()),
+                (groups.clone(), vec![]),
+                (vec![], colors.clone()),
+                (vec![Group::new("empty".to_owned(), vec![])], vec![]),
+            ] {
+                let bytes =
+                    create_ase_with_options(groups.clone(), colors.clone(), &options).unwrap();
+                assert_eq!(
+                    read_ase(&*bytes).unwrap(),
+                    (groups.clone(), colors.clone()),
+                    "{options:?}"
+                );
+                assert_eq!(
+                    write_streaming_with_options(groups, colors, &options),
+                    bytes,
+                    "{options:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn it_predicts_encoded_size() {
+        let long_name = "long name ".repeat(500);
+        let documents = [
+            (vec![], vec![]),
+            (vec![], vec![named("", ColorValue::Gray(0.5))]),
+            (
+                vec![
+                    Group::new(String::new(), vec![]),
+                    Group::new(
+                        long_name.clone(),
+                        vec![
+                            named(&long_name, ColorValue::Cmyk(0.1, 0.2, 0.3, 0.4)),
+                            named("Grün 緑 😀", ColorValue::Lab(50.0, 1.0, -1.0)),
+                        ],
+                    ),
+                ],
+                vec![
+                    named("rgb", ColorValue::Rgb(0.1, 0.2, 0.3)),
+                    named("gray", ColorValue::Gray(0.5)),
+                ],
+            ),
+        ];
+        for options in layout_options() {
+            for (groups, colors) in documents.clone() {
+                let size = encoded_size(&groups, &colors, &options);
+                let bytes = create_ase_with_options(groups, colors, &options).unwrap();
+                assert_eq!(bytes.len(), size, "{options:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn it_rejects_too_long_names() {
+        let name = "a".repeat(70_000);
+        for name_encoding in [
+            NameEncoding::Strip,
+            NameEncoding::Raw,
+            NameEncoding::Strict,
+            NameEncoding::Lossy,
+        ] {
+            let res = create_ase_with_options(
+                vec![],
+                vec![named(&name, ColorValue::Gray(0.5))],
+                &options(name_encoding),
+            );
+            assert!(
+                matches!(
+                    res,
+                    Err(ASEError::InvalidBlock { name: ref invalid, reason: BlockError::NameTooLong }) if *invalid == name
+                ),
+                "Only ASEError::InvalidBlock should be returned"
+            );
+        }
+
+        let group = Group::new(name.clone(), vec![named(&name, ColorValue::Gray(0.5))]);
+        assert!(matches!(
+            create_ase_ref(std::slice::from_ref(&group), &[]),
+            Err(ASEError::InvalidBlock { name: ref invalid, reason: BlockError::NameTooLong }) if *invalid == name
+        ));
+        assert!(matches!(
+            create_ase_blocks(vec![named(&name, ColorValue::Gray(0.5)).into()]),
+            Err(ASEError::InvalidBlock {
+                reason: BlockError::NameTooLong,
+                ..
+            })
+        ));
+        assert!(create_ase_from_iter([group.clone()], []).is_err());
+        let mut out = Vec::new();
+        assert!(create_ase_into(vec![group], vec![], &mut out).is_err());
+        assert!(out.is_empty());
+
+        let mut writer = AseWriter::new(Cursor::new(Vec::new())).unwrap();
+        assert!(writer.start_group(&name).is_err());
+        assert!(writer
+            .write_color(named(&name, ColorValue::Gray(0.5)))
+            .is_err());
+        assert_eq!(
+            writer.finish().unwrap().into_inner(),
+            create_ase(vec![], vec![])
+        );
+    }
+
+    #[test]
+    fn it_truncates_too_long_names_by_default() {
+        let name = "a".repeat(70_000);
+        let group = Group::new(name.clone(), vec![named(&name, ColorValue::Gray(0.5))]);
+        let read = |ase: &[u8]| {
+            let options = ParseOptions {
+                limits: Limits::NONE,
+                ..ParseOptions::DEFAULT
+            };
+            read_ase_with_options(ase, &options).unwrap()
+        };
+        let ase = create_ase(vec![group.clone()], vec![]);
+        let (groups, _) = read(&ase);
+        assert_eq!(groups[0].name.len(), 65_534);
+        assert_eq!(groups[0].blocks[0].name.len(), 65_534);
+        assert_eq!(group.to_bytes(), ase[12..]);
+
+        // surrogate pairs are not split
+        let name = "🎨".repeat(40_000);
+        let ase = create_ase(vec![], vec![named(&name, ColorValue::Gray(0.5))]);
+        let (_, colors) = read(&ase);
+        assert_eq!(colors[0].name, "🎨".repeat(32_767));
+    }
+}
@@ -0,0 +1,92 @@
+//! Pins the code generated for the palette in `tests/fixtures/valid/aurora.ase`.
+//!
+//! The snapshots in `tests/snapshots` are written again when `UPDATE_SNAPSHOTS` is set.
+
+use adobe_swatch_exchange::{
+    codegen::{self, Layout, Options, Values},
+    read_ase, Ase,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+
+// the generated Rust code has to compile
+#[allow(dead_code)]
+mod constants {
+    include!("snapshots/aurora_constants.rs");
+}
+
+#[allow(dead_code)]
+mod table {
+    include!("snapshots/aurora_table.rs");
+}
+
+fn aurora() -> Ase {
+    let input = std::fs::read(format!("{FIXTURES}/valid/aurora.ase")).unwrap();
+    let (groups, colors) = read_ase(&*input).unwrap();
+    Ase::new(groups, colors)
+}
+
+fn assert_snapshot(file: &str, code: String) {
+    let path = format!("{SNAPSHOTS}/{file}");
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, &code).unwrap();
+    }
+    assert_eq!(code, std::fs::read_to_string(&path).unwrap(), "{file}");
+}
+
+#[test]
+fn it_generates_rust_constants() {
+    assert_snapshot(
+        "aurora_constants.rs",
+        codegen::to_rust(&aurora(), &Options::default()),
+    );
+    assert_eq!(
+        constants::aurora::RED,
+        [0.749_019_6, 0.380_392_16, 0.415_686_28]
+    );
+    // "Purple / Magenta" and "2nd Polar Night"
+    assert_eq!(
+        constants::aurora::PURPLE_MAGENTA,
+        [0.705_882_4, 0.556_862_8, 0.678_431_4]
+    );
+    assert_eq!(
+        constants::_2ND_POLAR_NIGHT,
+        [0.180_392_16, 0.203_921_57, 0.250_980_4]
+    );
+}
+
+#[test]
+fn it_generates_rust_tables() {
+    let options = Options {
+        values: Values::Hex,
+        layout: Layout::Table,
+        ..Default::default()
+    };
+    assert_snapshot("aurora_table.rs", codegen::to_rust(&aurora(), &options));
+    assert_eq!(table::aurora::SWATCHES[4].name, "Purple / Magenta");
+    assert_eq!(table::aurora::SWATCHES[4].color, "#b48ead");
+    assert_eq!(table::SWATCHES[0].color, "#2e3440");
+
+    let flat = Options {
+        flatten_groups: true,
+        ..options
+    };
+    let code = codegen::to_rust(&aurora(), &flat);
+    assert!(code.contains("pub const AURORA_SWATCHES: [Swatch; 5] = ["));
+}
+
+#[test]
+fn it_generates_typescript() {
+    let hex = Options {
+        values: Values::Hex,
+        ..Default::default()
+    };
+    assert_snapshot("aurora_hex.ts", codegen::to_typescript(&aurora(), &hex));
+
+    let flat = Options {
+        flatten_groups: true,
+        ..Default::default()
+    };
+    assert_snapshot("aurora_flat.ts", codegen::to_typescript(&aurora(), &flat));
+}
@@ -0,0 +1,1376 @@
+//! Runs the conformance harness over the files in `tests/fixtures`.
+//!
+//! Files in `tests/fixtures/invalid` are expected to fail, every other file has to be read
+//! and be stable when written again. Problem files from issues can be dropped into the
+//! directory and checked using `cargo test --test conformance -- --ignored`.
+
+use adobe_swatch_exchange::{
+    conformance::{check_dir, check_file, summarize, FileReport, Summary},
+    ASEError, ConformationError,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn is_invalid(report: &FileReport) -> bool {
+    report
+        .path
+        .parent()
+        .map_or(false, |parent| parent.ends_with("invalid"))
+}
+
+#[test]
+fn it_reads_lenient_fixtures() {
+    for (file, groups, colors) in [
+        ("group_end_length.ase", 1, 3),
+        ("group_name_only_length.ase", 1, 3),
+        ("group_name_only_length_and_group_end_length.ase", 1, 3),
+        ("unterminated_group.ase", 1, 2),
+        ("trailing_zeros.ase", 0, 2),
+        ("trailing_garbage.ase", 0, 2),
+        ("color_entry_data_2_bytes.ase", 0, 2),
+        ("color_entry_data_16_bytes.ase", 1, 2),
+        ("group_name_without_terminator.ase", 2, 2),
+        ("name_length_uncounted.ase", 2, 5),
+        ("group_corrupt_color.ase", 1, 1),
+        ("name_little_endian.ase", 1, 2),
+        ("rgb_byte_range.ase", 1, 4),
+        ("group_end_inside_group.ase", 2, 3),
+    ] {
+        let report = check_file(format!("{FIXTURES}/lenient/{file}"));
+        assert!(report.is_ok(), "{report}");
+        assert_eq!((report.groups, report.colors), (groups, colors), "{file}");
+    }
+}
+
+#[test]
+fn it_reports_invalid_fixtures() {
+    let report = check_file(format!("{FIXTURES}/invalid/unknown_block_type.ase"));
+    assert!(matches!(
+        report.error.as_ref().map(ASEError::root_cause),
+        Some(ASEError::BlockTypeError)
+    ));
+
+    let report = check_file(format!("{FIXTURES}/invalid/missing_group_end.ase"));
+    assert!(matches!(
+        report.error.as_ref().map(ASEError::root_cause),
+        Some(ASEError::Invalid(ConformationError::GroupEnd))
+    ));
+
+    let report = check_file(format!("{FIXTURES}/invalid/truncated.ase"));
+    assert!(matches!(
+        report.error.as_ref().map(ASEError::root_cause),
+        Some(ASEError::Invalid(ConformationError::Truncated {
+            missing: 26
+        }))
+    ));
+}
+
+#[test]
+fn it_reports_missing_directory() {
+    let reports = check_dir(format!("{FIXTURES}/missing"));
+    assert_eq!(reports.len(), 1);
+    assert!(matches!(reports[0].error, Some(ASEError::Io(_))));
+}
+
+#[test]
+#[ignore = "checks every file in tests/fixtures"]
+fn it_checks_fixture_directory() {
+    let reports = check_dir(FIXTURES);
+    for report in &reports {
+        println!("{report}");
+    }
+    let summary = summarize(&reports);
+    println!("{summary}");
+
+    let invalid = reports.iter().filter(|report| is_invalid(report)).count();
+    assert_eq!(
+        summary,
+        Summary {
+            files: reports.len(),
+            passed: reports.len() - invalid,
+            failed: invalid,
+            unstable: 0,
+        }
+    );
+    for report in reports {
+        assert_eq!(report.is_ok(), !is_invalid(&report), "{report}");
+    }
+}
+
+#[test]
+fn it_reads_fixtures_with_parse_options() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_options, ParseOptions};
+
+    let read = |file: &str, options: &ParseOptions| {
+        let input = std::fs::read(format!("{FIXTURES}/{file}")).unwrap();
+        read_ase_with_options(&*input, options).map(|(groups, colors)| (groups.len(), colors.len()))
+    };
+
+    for file in [
+        "lenient/group_end_length.ase",
+        "lenient/group_name_only_length.ase",
+        "lenient/group_name_only_length_and_group_end_length.ase",
+        "lenient/unterminated_group.ase",
+    ] {
+        let input = std::fs::read(format!("{FIXTURES}/{file}")).unwrap();
+        let (groups, colors) = read_ase(&*input).unwrap();
+        let expected = (groups.len(), colors.len());
+        assert_eq!(read(file, &ParseOptions::default()).unwrap(), expected);
+        assert_eq!(read(file, &ParseOptions::LENIENT).unwrap(), expected);
+    }
+
+    // the layout written by Adobe applications
+    assert!(read("lenient/group_name_only_length.ase", &ParseOptions::STRICT).is_ok());
+    for file in [
+        "lenient/group_end_length.ase",
+        "lenient/group_name_only_length_and_group_end_length.ase",
+    ] {
+        assert!(matches!(
+            read(file, &ParseOptions::STRICT)
+                .as_ref()
+                .map_err(ASEError::root_cause),
+            Err(ASEError::BlockTypeError)
+        ));
+    }
+    assert!(matches!(
+        read("lenient/unterminated_group.ase", &ParseOptions::STRICT)
+            .as_ref()
+            .map_err(ASEError::root_cause),
+        Err(ASEError::Invalid(ConformationError::GroupEnd))
+    ));
+
+    assert_eq!(
+        read("invalid/missing_group_end.ase", &ParseOptions::LENIENT).unwrap(),
+        (1, 1)
+    );
+    assert_eq!(
+        read("invalid/unknown_block_type.ase", &ParseOptions::LENIENT).unwrap(),
+        (1, 0)
+    );
+    assert!(read("invalid/truncated.ase", &ParseOptions::LENIENT).is_err());
+}
+
+#[test]
+fn it_reports_parse_warnings() {
+    use adobe_swatch_exchange::{read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    for (file, expected) in [
+        (
+            "group_end_length.ase",
+            vec![ParseWarning::GroupEndLength {
+                offset: 128,
+                length: 4,
+            }],
+        ),
+        (
+            "group_name_only_length.ase",
+            vec![ParseWarning::SeparateGroupColors { offset: 12 }],
+        ),
+        (
+            "group_name_only_length_and_group_end_length.ase",
+            vec![
+                ParseWarning::SeparateGroupColors { offset: 12 },
+                ParseWarning::GroupEndLength {
+                    offset: 128,
+                    length: 4,
+                },
+            ],
+        ),
+        (
+            "unterminated_group.ase",
+            vec![
+                ParseWarning::SeparateGroupColors { offset: 12 },
+                ParseWarning::UnterminatedGroup { offset: 12 },
+            ],
+        ),
+    ] {
+        let input = std::fs::read(format!("{FIXTURES}/lenient/{file}")).unwrap();
+        let (_, _, warnings) = read_ase_with_warnings(&*input, &ParseOptions::default()).unwrap();
+        assert_eq!(warnings, expected, "{file}");
+    }
+}
+
+#[test]
+fn it_skips_unknown_blocks() {
+    use adobe_swatch_exchange::{
+        read_ase, read_ase_with_options, read_ase_with_warnings, ColorValue, ParseOptions,
+        ParseWarning,
+    };
+
+    let input = std::fs::read(format!(
+        "{FIXTURES}/invalid/unknown_block_between_colors.ase"
+    ))
+    .unwrap();
+    assert!(matches!(
+        read_ase(&*input).as_ref().map_err(ASEError::root_cause),
+        Err(ASEError::BlockTypeError)
+    ));
+
+    let options = ParseOptions {
+        skip_unknown_blocks: true,
+        ..ParseOptions::STRICT
+    };
+    let (groups, colors, warnings) = read_ase_with_warnings(&*input, &options).unwrap();
+    assert!(groups.is_empty());
+    assert_eq!(
+        colors
+            .iter()
+            .map(|color| (color.name.as_str(), &color.color))
+            .collect::<Vec<_>>(),
+        vec![
+            ("first", &ColorValue::Gray(0.25)),
+            ("second", &ColorValue::Gray(0.75))
+        ]
+    );
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::UnknownBlock {
+            offset: 42,
+            block_type: 0x0002
+        }]
+    );
+    assert_eq!(
+        read_ase_with_options(&*input, &ParseOptions::LENIENT).unwrap(),
+        (groups, colors)
+    );
+}
+
+#[test]
+fn it_ignores_block_count() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    let too_small = std::fs::read(format!("{FIXTURES}/lenient/block_count_too_small.ase")).unwrap();
+    let too_large = std::fs::read(format!("{FIXTURES}/invalid/block_count_too_large.ase")).unwrap();
+
+    assert_eq!(read_ase(&*too_small).unwrap().1.len(), 1);
+    assert!(matches!(
+        read_ase(&*too_large).as_ref().map_err(ASEError::root_cause),
+        Err(ASEError::Io(_))
+    ));
+
+    for (input, declared) in [(too_small, 1), (too_large, 3)] {
+        let (groups, colors, warnings) =
+            read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+        assert!(groups.is_empty());
+        assert_eq!(colors.len(), 2);
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::BlockCount { declared, read: 2 }]
+        );
+    }
+
+    // the counts of files with groups match
+    for file in [
+        "lenient/group_end_length.ase",
+        "lenient/group_name_only_length.ase",
+        "lenient/group_name_only_length_and_group_end_length.ase",
+        "lenient/unterminated_group.ase",
+        "invalid/missing_group_end.ase",
+    ] {
+        let input = std::fs::read(format!("{FIXTURES}/{file}")).unwrap();
+        let (_, _, warnings) = read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+        assert!(
+            !warnings
+                .iter()
+                .any(|warning| matches!(warning, ParseWarning::BlockCount { .. })),
+            "{file}: {warnings:?}"
+        );
+    }
+}
+
+#[test]
+fn it_reads_blocks_after_zero_block_count() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    let input = std::fs::read(format!("{FIXTURES}/lenient/zero_block_count.ase")).unwrap();
+    assert_eq!(read_ase(&*input).unwrap(), (vec![], vec![]));
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "group name");
+    assert_eq!(groups[0].blocks.len(), 2);
+    assert_eq!(
+        colors
+            .iter()
+            .map(|color| color.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["name"]
+    );
+    // the group and the single color, as counted by `create_ase`
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::BlockCount {
+            declared: 0,
+            read: 2
+        }]
+    );
+}
+
+#[test]
+fn it_tolerates_trailing_data() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    let zeros = std::fs::read(format!("{FIXTURES}/lenient/trailing_zeros.ase")).unwrap();
+    let garbage = std::fs::read(format!("{FIXTURES}/lenient/trailing_garbage.ase")).unwrap();
+    let read = |input: &[u8], options: &ParseOptions| {
+        read_ase_with_warnings(input, options).map(|(_, colors, warnings)| (colors.len(), warnings))
+    };
+
+    for input in [&zeros, &garbage] {
+        assert_eq!(read_ase(&**input).unwrap().1.len(), 2);
+    }
+    for options in [ParseOptions::STRICT, ParseOptions::LENIENT] {
+        assert_eq!(read(&zeros, &options).unwrap(), (2, vec![]));
+    }
+
+    assert_eq!(
+        read(&garbage, &ParseOptions::LENIENT).unwrap(),
+        (
+            2,
+            vec![ParseWarning::TrailingData {
+                offset: 74,
+                length: 4
+            }]
+        )
+    );
+    assert!(matches!(
+        read(&garbage, &ParseOptions::STRICT)
+            .as_ref()
+            .map_err(ASEError::root_cause),
+        Err(ASEError::Invalid(ConformationError::TrailingData {
+            length: 4
+        }))
+    ));
+
+    // more zero bytes than padding
+    let mut zeros = zeros;
+    zeros.extend([0; 8]);
+    assert!(matches!(
+        read(&zeros, &ParseOptions::STRICT)
+            .as_ref()
+            .map_err(ASEError::root_cause),
+        Err(ASEError::Invalid(ConformationError::TrailingData {
+            length: 11
+        }))
+    ));
+    assert_eq!(
+        read(&zeros, &ParseOptions::LENIENT).unwrap(),
+        (
+            2,
+            vec![ParseWarning::TrailingData {
+                offset: 74,
+                length: 11
+            }]
+        )
+    );
+}
+
+#[test]
+fn it_skips_padding_between_blocks() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    let read = |padding: u64, options: &ParseOptions| {
+        let input = std::fs::read(format!(
+            "{FIXTURES}/invalid/block_padding_{padding}_bytes.ase"
+        ))
+        .unwrap();
+        read_ase_with_warnings(&*input, options)
+    };
+    let padding = |offset, length| ParseWarning::BlockPadding { offset, length };
+
+    let (groups, colors, warnings) = read(4, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(
+        (groups.len(), groups[0].blocks.len(), colors.len()),
+        (1, 1, 2)
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            padding(34, 4),
+            padding(58, 4),
+            ParseWarning::SeparateGroupColors { offset: 38 },
+            padding(84, 4),
+            ParseWarning::GroupEndLength {
+                offset: 90,
+                length: 4
+            },
+        ]
+    );
+
+    // zero bytes following a group end, which are more than its length
+    let (eight_groups, eight_colors, warnings) = read(8, &ParseOptions::LENIENT).unwrap();
+    assert_eq!((&eight_groups, &eight_colors), (&groups, &colors));
+    assert_eq!(
+        warnings,
+        vec![
+            padding(34, 8),
+            padding(62, 8),
+            ParseWarning::SeparateGroupColors { offset: 42 },
+            padding(92, 8),
+            padding(102, 8),
+        ]
+    );
+
+    for padding in [4, 8] {
+        for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+            let err = read(padding, &options).unwrap_err();
+            assert!(matches!(err, ASEError::Block { offset: 34, .. }));
+            assert!(matches!(err.root_cause(), ASEError::BlockTypeError));
+        }
+    }
+
+    // zero bytes beyond the limit are not skipped
+    let input = std::fs::read(format!("{FIXTURES}/invalid/block_padding_4_bytes.ase")).unwrap();
+    let mut padded = input[..34].to_vec();
+    padded.extend([0; 66]);
+    padded.extend(&input[38..]);
+    let err = read_ase_with_warnings(&*padded, &ParseOptions::LENIENT).unwrap_err();
+    assert!(matches!(err, ASEError::Block { offset: 98, .. }));
+    padded.drain(34..36);
+    assert_eq!(
+        read_ase_with_warnings(&*padded, &ParseOptions::LENIENT)
+            .unwrap()
+            .2[0],
+        padding(34, 64)
+    );
+    assert!(read_ase(&*padded).is_err());
+}
+
+#[test]
+fn it_recovers_color_model_codes() {
+    use adobe_swatch_exchange::{
+        create_ase, read_ase, read_ase_with_warnings, ColorValue, ParseOptions, ParseWarning,
+    };
+
+    let read = |file: &str, options: &ParseOptions| {
+        let input = std::fs::read(format!("{FIXTURES}/invalid/{file}")).unwrap();
+        read_ase_with_warnings(&*input, options)
+    };
+    let recovered = |offset| ParseWarning::ColorModelCode { offset };
+
+    let (groups, colors, warnings) =
+        read("color_model_lowercase.ase", &ParseOptions::LENIENT).unwrap();
+    assert!(groups.is_empty());
+    assert_eq!(
+        colors.iter().map(|color| &color.color).collect::<Vec<_>>(),
+        [
+            &ColorValue::Rgb(1.0, 0.0, 0.0),
+            &ColorValue::Cmyk(1.0, 0.0, 0.0, 0.0),
+            &ColorValue::Gray(0.5),
+        ]
+    );
+    assert_eq!(warnings, vec![recovered(12), recovered(46), recovered(86)]);
+
+    let (groups, colors, warnings) =
+        read("color_model_nul_padded.ase", &ParseOptions::LENIENT).unwrap();
+    assert_eq!(colors[0].color, ColorValue::Lab(50.0, 10.0, -10.0));
+    assert_eq!(groups[0].blocks[0].color, ColorValue::Rgb(0.0, 1.0, 0.0));
+    assert_eq!(groups[0].blocks.len(), 2);
+    assert_eq!(warnings, vec![recovered(12), recovered(66)]);
+
+    // the canonical codes are written
+    let written = create_ase(groups.clone(), colors.clone());
+    assert!(written.windows(4).any(|code| code == b"LAB "));
+    assert!(!written.windows(4).any(|code| code == b"LAB\0"));
+    assert_eq!(read_ase(&*written).unwrap(), (groups, colors));
+
+    for file in ["color_model_lowercase.ase", "color_model_nul_padded.ase"] {
+        for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+            let err = read(file, &options).unwrap_err();
+            assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+            assert!(matches!(err.root_cause(), ASEError::ColorFormat));
+        }
+    }
+}
+
+#[test]
+fn it_normalizes_rgb_byte_range() {
+    use adobe_swatch_exchange::{
+        read_ase, read_ase_with_warnings, Ase, ColorValue, ParseOptions, ParseWarning, RgbRange,
+    };
+
+    let input = std::fs::read(format!("{FIXTURES}/lenient/rgb_byte_range.ase")).unwrap();
+    let values = |ase: &Ase| {
+        ase.groups
+            .iter()
+            .flat_map(|group| &group.blocks)
+            .chain(&ase.colors)
+            .map(|block| block.color.clone())
+            .collect::<Vec<_>>()
+    };
+    let read = |rgb_range| {
+        let options = ParseOptions {
+            rgb_range,
+            ..Default::default()
+        };
+        let (groups, colors, warnings) = read_ase_with_warnings(&*input, &options).unwrap();
+        (values(&Ase::new(groups, colors)), warnings)
+    };
+
+    let raw = vec![
+        ColorValue::Rgb(171.0, 42.0, 13.0),
+        ColorValue::Rgb(1.0, 0.0, 0.0),
+        ColorValue::Rgb(255.0, 255.0, 255.0),
+        ColorValue::Gray(0.5),
+    ];
+    assert_eq!(read(RgbRange::AsStored), (raw.clone(), vec![]));
+
+    let rust = ColorValue::Rgb(171.0 / 255.0, 42.0 / 255.0, 13.0 / 255.0);
+    let white = ColorValue::Rgb(1.0, 1.0, 1.0);
+    assert_eq!(
+        read(RgbRange::PerColor),
+        (
+            vec![rust.clone(), raw[1].clone(), white.clone(), raw[3].clone()],
+            vec![ParseWarning::RgbRange { colors: 2 }]
+        )
+    );
+    let per_file = vec![
+        rust,
+        ColorValue::Rgb(1.0 / 255.0, 0.0, 0.0),
+        white,
+        raw[3].clone(),
+    ];
+    assert_eq!(
+        read(RgbRange::PerFile),
+        (per_file.clone(), vec![ParseWarning::RgbRange { colors: 3 }])
+    );
+
+    // normalizing after reading the file
+    let (groups, colors) = read_ase(&*input).unwrap();
+    let mut ase = Ase::new(groups, colors);
+    assert_eq!(ase.normalize_component_ranges(), 3);
+    assert_eq!(values(&ase), per_file);
+    assert_eq!(ase.normalize_component_ranges(), 0);
+}
+
+#[test]
+fn it_derives_color_entry_lengths() {
+    use adobe_swatch_exchange::{read_ase_with_warnings, ColorValue, ParseOptions, ParseWarning};
+
+    let input = std::fs::read(format!("{FIXTURES}/invalid/color_entry_zero_length.ase")).unwrap();
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(
+        colors.iter().map(|color| &color.color).collect::<Vec<_>>(),
+        [&ColorValue::Lab(50.0, 10.0, -10.0), &ColorValue::Gray(0.25)]
+    );
+    assert_eq!(
+        groups[0]
+            .blocks
+            .iter()
+            .map(|color| color.name.as_str())
+            .collect::<Vec<_>>(),
+        ["red", "cyan", "gray"]
+    );
+    let size = |offset, declared| ParseWarning::ColorEntrySize { offset, declared };
+    assert_eq!(
+        warnings,
+        vec![size(12, 0), size(66, 0), size(100, 4), size(140, 0)]
+    );
+
+    for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+        let err = read_ase_with_warnings(&*input, &options).unwrap_err();
+        assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+        assert!(matches!(err.root_cause(), ASEError::InputDataParseError));
+    }
+
+    // the file ends within the derived length
+    let err = read_ase_with_warnings(&input[..40], &ParseOptions::LENIENT).unwrap_err();
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::Invalid(ConformationError::Truncated { missing: 6 })
+    ));
+}
+
+#[test]
+fn it_rejects_groups_ending_with_the_file() {
+    use adobe_swatch_exchange::{
+        create_ase, read_ase, read_ase_with_warnings, ParseOptions, ParseWarning,
+    };
+
+    let input = std::fs::read(format!("{FIXTURES}/invalid/group_end_chopped.ase")).unwrap();
+    for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+        let err = read_ase_with_warnings(&*input, &options).unwrap_err();
+        assert!(matches!(err, ASEError::Block { offset: 76, .. }));
+        assert!(matches!(
+            err.root_cause(),
+            ASEError::Invalid(ConformationError::GroupEnd)
+        ));
+    }
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(groups[0].blocks.len(), 2);
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::UnterminatedGroup { offset: 12 }]
+    );
+    // the recovered group is terminated when written again
+    let written = create_ase(groups.clone(), colors.clone());
+    assert_eq!(written[..input.len()], input[..]);
+    assert_eq!(
+        read_ase(&*written).unwrap(),
+        (groups.clone(), colors.clone())
+    );
+
+    // groups, whose colors follow them, are only closed at the end of the file, which
+    // takes the place of their `GroupEnd` block, when lenient
+    let flat = std::fs::read(format!("{FIXTURES}/invalid/group_end_chopped_flat.ase")).unwrap();
+    for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+        let err = read_ase_with_warnings(&*flat, &options).unwrap_err();
+        assert!(matches!(err, ASEError::Block { offset: 60, .. }));
+        assert!(matches!(
+            err.root_cause(),
+            ASEError::Invalid(ConformationError::GroupEnd)
+        ));
+    }
+    let (flat_groups, _, warnings) =
+        read_ase_with_warnings(&*flat, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(flat_groups.len(), 1);
+    assert_eq!(flat_groups[0].blocks.len(), 1);
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::SeparateGroupColors { offset: 12 },
+            ParseWarning::UnterminatedGroup { offset: 12 },
+            ParseWarning::BlockCount {
+                declared: 3,
+                read: 2
+            },
+        ]
+    );
+
+    // the end of the file closes the group, once the declared blocks have been read
+    let mut counted = flat;
+    counted[8..12].copy_from_slice(&2u32.to_be_bytes());
+    let (groups, _) = read_ase(&*counted).unwrap();
+    assert_eq!(groups, flat_groups);
+}
+
+#[test]
+fn it_skips_doubled_group_ends() {
+    use adobe_swatch_exchange::{read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    // every group is followed by two `GroupEnd` blocks
+    let input = std::fs::read(format!("{FIXTURES}/invalid/group_end_doubled.ase")).unwrap();
+    for options in [ParseOptions::STRICT, ParseOptions::DEFAULT] {
+        let err = read_ase_with_warnings(&*input, &options).unwrap_err();
+        assert!(matches!(err, ASEError::Block { offset: 52, .. }));
+        assert!(matches!(
+            err.root_cause(),
+            ASEError::Invalid(ConformationError::GroupEnd)
+        ));
+    }
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+    let names = groups.iter().map(|group| group.name.as_str());
+    assert_eq!(names.collect::<Vec<_>>(), ["one", "two"]);
+    assert!(groups.iter().all(|group| group.blocks.len() == 1));
+    assert_eq!(colors.len(), 1);
+    assert_eq!(colors[0].name, "c");
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::UnmatchedGroupEnd { offset: 52 },
+            ParseWarning::UnmatchedGroupEnd { offset: 94 },
+            // the doubled blocks are not declared in the header
+            ParseWarning::BlockCount {
+                declared: 3,
+                read: 5
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_enforces_limits() {
+    use adobe_swatch_exchange::{
+        create_ase, create_ase_with_options, read_ase_with_options, ColorBlock, ColorType,
+        ColorValue, Group, GroupLayout, Limit, Limits, ParseOptions, WriteOptions,
+    };
+
+    let read = |input: &[u8], limits: Limits| {
+        let options = ParseOptions {
+            limits,
+            ..ParseOptions::DEFAULT
+        };
+        match read_ase_with_options(input, &options) {
+            Ok(_) => None,
+            Err(err) => match err.root_cause() {
+                ASEError::LimitExceeded { limit, maximum } => Some((*limit, *maximum)),
+                err => panic!("unexpected error: {err}"),
+            },
+        }
+    };
+    let gray =
+        |name: &str| ColorBlock::new(name.to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    let groups = vec![Group::new("group".to_owned(), vec![gray("a"), gray("b")])];
+    let colors = vec![gray("c")];
+    // a group containing its colors, its group end and a color
+    let input = create_ase(groups.clone(), colors.clone());
+    assert_eq!(read(&input, Limits::DEFAULT), None);
+
+    let exact = Limits {
+        max_blocks: 3,
+        max_name_length: 5,
+        max_group_colors: 2,
+        max_decoded_bytes: input.len() as u64,
+    };
+    assert_eq!(read(&input, exact), None);
+    for (limits, exceeded) in [
+        (
+            Limits {
+                max_blocks: 2,
+                ..exact
+            },
+            (Limit::Blocks, 2),
+        ),
+        (
+            Limits {
+                max_name_length: 4,
+                ..exact
+            },
+            (Limit::NameLength, 4),
+        ),
+        (
+            Limits {
+                max_group_colors: 1,
+                ..exact
+            },
+            (Limit::GroupColors, 1),
+        ),
+        (
+            Limits {
+                max_decoded_bytes: input.len() as u64 - 1,
+                ..exact
+            },
+            (Limit::DecodedBytes, input.len() as u64 - 1),
+        ),
+    ] {
+        assert_eq!(read(&input, limits), Some(exceeded), "{limits:?}");
+    }
+
+    // the colors of groups written by Adobe applications follow them as separate blocks
+    let options = WriteOptions {
+        group_layout: GroupLayout::Flat,
+        ..Default::default()
+    };
+    let flat = create_ase_with_options(groups, colors, &options).unwrap();
+    let limits = Limits {
+        max_group_colors: 1,
+        ..Limits::DEFAULT
+    };
+    assert_eq!(read(&flat, limits), Some((Limit::GroupColors, 1)));
+
+    // a header declaring more blocks than allowed fails before reading them
+    let mut huge = b"ASEF\0\x01\0\0".to_vec();
+    huge.extend(u32::MAX.to_be_bytes());
+    assert_eq!(
+        read(&huge, Limits::DEFAULT),
+        Some((Limit::Blocks, 1_000_000))
+    );
+
+    // blocks are counted, when the declared number of blocks is ignored
+    let mut group_ends = b"ASEF\0\x01\0\0\0\0\0\0".to_vec();
+    for _ in 0..5 {
+        group_ends.extend([0xc0, 0x02]);
+    }
+    let options = ParseOptions {
+        limits: Limits {
+            max_blocks: 4,
+            ..Limits::DEFAULT
+        },
+        ..ParseOptions::LENIENT
+    };
+    let err = read_ase_with_options(&*group_ends, &options).unwrap_err();
+    assert!(matches!(err, ASEError::Block { index: 4, .. }));
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::LimitExceeded {
+            limit: Limit::Blocks,
+            maximum: 4
+        }
+    ));
+}
+
+#[test]
+fn it_preserves_block_order() {
+    use adobe_swatch_exchange::{
+        create_ase_blocks, read_ase_blocks, Block, ColorBlock, ColorType, ColorValue, Group,
+        ParseOptions,
+    };
+
+    let gray = |name: &str, value| {
+        ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal)
+    };
+    let expected = vec![
+        Block::Color(gray("first", 0.25)),
+        Block::Group(Group::new("group".to_owned(), vec![gray("inner", 0.5)])),
+        Block::Color(gray("last", 0.75)),
+    ];
+
+    let input = std::fs::read(format!("{FIXTURES}/valid/color_group_color.ase")).unwrap();
+    let blocks = read_ase_blocks(&*input, &ParseOptions::default()).unwrap();
+    assert_eq!(blocks, expected);
+
+    let written = create_ase_blocks(blocks).unwrap();
+    assert_eq!(written, input);
+    assert_eq!(
+        read_ase_blocks(&*written, &ParseOptions::default()).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn it_flattens_nested_groups() {
+    use adobe_swatch_exchange::{
+        read_ase, read_ase_with_warnings, ColorBlock, ColorType, ColorValue, Group, ParseOptions,
+        ParseWarning,
+    };
+
+    let gray = |name: &str, value| {
+        ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal)
+    };
+    let group = |name: &str, blocks| Group::new(name.to_owned(), blocks);
+
+    let one_level = std::fs::read(format!("{FIXTURES}/invalid/nested_group.ase")).unwrap();
+    let two_levels =
+        std::fs::read(format!("{FIXTURES}/invalid/nested_groups_two_levels.ase")).unwrap();
+    for input in [&one_level, &two_levels] {
+        assert!(matches!(
+            read_ase(&**input).as_ref().map_err(ASEError::root_cause),
+            Err(ASEError::Invalid(ConformationError::GroupEnd))
+        ));
+    }
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*one_level, &ParseOptions::LENIENT).unwrap();
+    assert!(colors.is_empty());
+    assert_eq!(
+        groups,
+        vec![
+            group("outer", vec![gray("a", 0.25), gray("c", 0.75)]),
+            group("inner", vec![gray("b", 0.5)]),
+        ]
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::SeparateGroupColors { offset: 12 },
+            ParseWarning::NestedGroup { offset: 54 },
+            ParseWarning::SeparateGroupColors { offset: 54 },
+        ]
+    );
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*two_levels, &ParseOptions::LENIENT).unwrap();
+    assert!(colors.is_empty());
+    assert_eq!(
+        groups,
+        vec![
+            group("outer", vec![gray("a", 0.1), gray("e", 0.9)]),
+            group("middle", vec![gray("b", 0.3), gray("d", 0.7)]),
+            group("inner", vec![gray("c", 0.5)]),
+        ]
+    );
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|warning| matches!(warning, ParseWarning::NestedGroup { .. }))
+            .count(),
+        2
+    );
+
+    // ends after the inner group, leaving the groups containing it unterminated
+    let truncated = &two_levels[..142];
+    let (groups, _, warnings) = read_ase_with_warnings(truncated, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|warning| matches!(warning, ParseWarning::UnterminatedGroup { .. }))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn it_skips_data_after_color_types() {
+    use adobe_swatch_exchange::{
+        read_ase, read_ase_with_warnings, ColorBlock, ColorType, ColorValue, Group, ParseOptions,
+        ParseWarning,
+    };
+
+    let gray = |name: &str, value| {
+        ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal)
+    };
+    let two_bytes =
+        std::fs::read(format!("{FIXTURES}/lenient/color_entry_data_2_bytes.ase")).unwrap();
+    let sixteen_bytes =
+        std::fs::read(format!("{FIXTURES}/lenient/color_entry_data_16_bytes.ase")).unwrap();
+
+    let expected = (vec![], vec![gray("first", 0.25), gray("second", 0.75)]);
+    assert_eq!(read_ase(&*two_bytes).unwrap(), expected);
+    for options in [ParseOptions::DEFAULT, ParseOptions::LENIENT] {
+        assert_eq!(
+            read_ase_with_warnings(&*two_bytes, &options).unwrap(),
+            (
+                expected.0.clone(),
+                expected.1.clone(),
+                vec![ParseWarning::ColorEntryLength {
+                    offset: 12,
+                    length: 2
+                }]
+            )
+        );
+    }
+
+    // the next color entry of the group is read after the data
+    let group = Group::new("group".to_owned(), vec![gray("a", 0.25), gray("b", 0.75)]);
+    assert_eq!(
+        read_ase(&*sixteen_bytes).unwrap(),
+        (vec![group.clone()], vec![])
+    );
+    assert_eq!(
+        read_ase_with_warnings(&*sixteen_bytes, &ParseOptions::LENIENT).unwrap(),
+        (
+            vec![group],
+            vec![],
+            vec![ParseWarning::ColorEntryLength {
+                offset: 32,
+                length: 16
+            }]
+        )
+    );
+
+    for (input, length) in [(&two_bytes, 2), (&sixteen_bytes, 16)] {
+        let err = read_ase_with_warnings(&**input, &ParseOptions::STRICT).unwrap_err();
+        assert!(
+            matches!(
+                err.root_cause(),
+                ASEError::Invalid(ConformationError::ColorEntryLength { length: l }) if *l == length
+            ),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn it_reads_group_names_without_terminator() {
+    use adobe_swatch_exchange::{
+        read_ase_with_warnings, ColorBlock, ColorType, ColorValue, Group, ParseOptions,
+        ParseWarning,
+    };
+
+    let gray = |name: &str, value| {
+        ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal)
+    };
+    let input = std::fs::read(format!(
+        "{FIXTURES}/lenient/group_name_without_terminator.ase"
+    ))
+    .unwrap();
+
+    for options in [ParseOptions::DEFAULT, ParseOptions::LENIENT] {
+        assert_eq!(
+            read_ase_with_warnings(&*input, &options).unwrap(),
+            (
+                vec![
+                    Group::new("first".to_owned(), vec![gray("a", 0.25)]),
+                    Group::new("second".to_owned(), vec![gray("b", 0.75)]),
+                ],
+                vec![],
+                vec![
+                    ParseWarning::NameTerminator { offset: 12 },
+                    ParseWarning::NameTerminator { offset: 54 },
+                    ParseWarning::SeparateGroupColors { offset: 54 },
+                ]
+            )
+        );
+    }
+
+    let err = read_ase_with_warnings(&*input, &ParseOptions::STRICT).unwrap_err();
+    assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::Invalid(ConformationError::NameTerminator)
+    ));
+}
+
+#[test]
+fn it_reads_name_lengths_without_terminator() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    let counted = std::fs::read(format!("{FIXTURES}/valid/name_length_counted.ase")).unwrap();
+    let uncounted = std::fs::read(format!("{FIXTURES}/lenient/name_length_uncounted.ase")).unwrap();
+    let expected = read_ase(&*counted).unwrap();
+    assert_eq!(expected.0[0].name, "Brand");
+    assert_eq!(read_ase(&*uncounted).unwrap(), expected);
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*uncounted, &ParseOptions::LENIENT).unwrap();
+    assert_eq!((groups, colors), expected);
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::NameLength { offset: 12 },
+            ParseWarning::NameLength { offset: 32 },
+            ParseWarning::SeparateGroupColors { offset: 12 },
+            ParseWarning::NameLength { offset: 68 },
+            ParseWarning::NameLength { offset: 110 },
+            ParseWarning::NameLength { offset: 144 },
+            ParseWarning::NameLength { offset: 172 },
+            ParseWarning::NameLength { offset: 194 },
+        ]
+    );
+
+    assert!(read_ase_with_warnings(&*counted, &ParseOptions::STRICT).is_ok());
+    let err = read_ase_with_warnings(&*uncounted, &ParseOptions::STRICT).unwrap_err();
+    assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::Invalid(ConformationError::NameLength)
+    ));
+}
+
+#[test]
+fn it_reads_little_endian_names() {
+    use adobe_swatch_exchange::{
+        create_ase, read_ase, read_ase_with_warnings, NameByteOrder, ParseOptions, ParseWarning,
+    };
+
+    let input = std::fs::read(format!("{FIXTURES}/lenient/name_little_endian.ase")).unwrap();
+    let (groups, _) = read_ase(&*input).unwrap();
+    assert_eq!(groups[0].name, "䈀爀愀渀搀");
+    assert_eq!(groups[0].blocks[0].name, "渀愀洀攀");
+
+    let (groups, colors, warnings) =
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(groups[0].name, "Brand");
+    assert_eq!(groups[0].blocks[0].name, "name");
+    assert_eq!(colors[0].name, "Grün");
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::NameByteOrder { offset: 12 },
+            ParseWarning::NameByteOrder { offset: 32 },
+            ParseWarning::SeparateGroupColors { offset: 12 },
+            ParseWarning::NameByteOrder { offset: 70 },
+        ]
+    );
+
+    let options = ParseOptions {
+        name_byte_order: NameByteOrder::LittleEndian,
+        ..Default::default()
+    };
+    let (read_groups, read_colors, _) = read_ase_with_warnings(&*input, &options).unwrap();
+    assert_eq!((&read_groups, &read_colors), (&groups, &colors));
+
+    // names are always written as big-endian
+    let written = create_ase(groups.clone(), colors.clone());
+    assert!(written
+        .windows(10)
+        .any(|bytes| bytes == [0, 0x42, 0, 0x72, 0, 0x61, 0, 0x6e, 0, 0x64]));
+    assert_eq!(read_ase(&*written).unwrap(), (groups, colors));
+    let (_, _, warnings) = read_ase_with_warnings(&*written, &ParseOptions::LENIENT).unwrap();
+    assert!(!warnings
+        .iter()
+        .any(|warning| matches!(warning, ParseWarning::NameByteOrder { .. })));
+}
+
+#[test]
+fn it_skips_malformed_colors_in_groups() {
+    use adobe_swatch_exchange::{
+        read_ase, read_ase_with_warnings, ColorBlock, ColorType, ColorValue, Group, ParseOptions,
+        ParseWarning,
+    };
+
+    let gray = |name: &str, value| {
+        ColorBlock::new(name.to_owned(), ColorValue::Gray(value), ColorType::Normal)
+    };
+    let input = std::fs::read(format!("{FIXTURES}/lenient/group_corrupt_color.ase")).unwrap();
+
+    // the colors of the group end before the malformed color
+    let (groups, _) = read_ase(&*input).unwrap();
+    assert_eq!(
+        groups,
+        vec![Group::new("group".to_owned(), vec![gray("a", 0.25)])]
+    );
+
+    assert_eq!(
+        read_ase_with_warnings(&*input, &ParseOptions::LENIENT).unwrap(),
+        (
+            vec![Group::new(
+                "group".to_owned(),
+                vec![gray("a", 0.25), gray("c", 0.75)]
+            )],
+            vec![],
+            vec![ParseWarning::MalformedColor {
+                offset: 54,
+                index: 1
+            }]
+        )
+    );
+
+    // the colors of the group end before the malformed color, which is reported
+    let (_, _, warnings) = read_ase_with_warnings(&*input, &ParseOptions::DEFAULT).unwrap();
+    assert_eq!(
+        warnings,
+        vec![ParseWarning::GroupData {
+            offset: 54,
+            length: 44
+        }]
+    );
+
+    let err = read_ase_with_warnings(&*input, &ParseOptions::STRICT).unwrap_err();
+    assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+    assert!(matches!(err.root_cause(), ASEError::ColorFormat));
+}
+
+#[test]
+fn it_reports_data_after_group_colors() {
+    use adobe_swatch_exchange::{read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    // the data of each group block ends with a `GroupEnd` block after its color
+    let input = std::fs::read(format!("{FIXTURES}/lenient/group_end_inside_group.ase")).unwrap();
+    let err = read_ase_with_warnings(&*input, &ParseOptions::STRICT).unwrap_err();
+    assert!(matches!(err, ASEError::Block { offset: 12, .. }));
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::Invalid(ConformationError::GroupData { group, position: 32 }) if group == "one"
+    ));
+
+    for options in [ParseOptions::DEFAULT, ParseOptions::LENIENT] {
+        let (groups, colors, warnings) = read_ase_with_warnings(&*input, &options).unwrap();
+        assert!(groups.iter().all(|group| group.blocks.len() == 1));
+        assert_eq!(colors.len(), 1);
+        assert_eq!(
+            warnings,
+            vec![
+                ParseWarning::GroupData {
+                    offset: 50,
+                    length: 2
+                },
+                ParseWarning::GroupData {
+                    offset: 92,
+                    length: 2
+                },
+            ]
+        );
+    }
+}
+
+#[test]
+fn it_names_swatches_in_errors() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_options, ParseOptions};
+
+    let input = std::fs::read(format!("{FIXTURES}/valid/color_group_color.ase")).unwrap();
+    let model = |input: &[u8], name: &str| {
+        let name: Vec<u8> = name.encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let start = input
+            .windows(name.len())
+            .position(|window| window == name)
+            .unwrap();
+        // the name is followed by its null terminator
+        start + name.len() + 2
+    };
+
+    let mut single = input.clone();
+    let first = model(&single, "first");
+    single[first..first + 4].copy_from_slice(b"XXXX");
+    assert!(matches!(read_ase(&*single), Err(ASEError::ColorFormat)));
+    let err = read_ase_with_options(&*single, &ParseOptions::DEFAULT).unwrap_err();
+    assert!(matches!(err.root_cause(), ASEError::ColorFormat));
+    assert_eq!(
+        err.to_string(),
+        "Error parsing color format in \"first\" (block 0 at offset 12)"
+    );
+
+    let mut grouped = input;
+    let inner = model(&grouped, "inner");
+    grouped[inner..inner + 4].copy_from_slice(b"XXXX");
+    let err = read_ase_with_options(&*grouped, &ParseOptions::STRICT).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error parsing color format in \"inner\" (block 1 at offset 42)"
+    );
+}
+
+#[test]
+fn it_merges_duplicate_groups() {
+    use adobe_swatch_exchange::{
+        create_ase_blocks, read_ase_blocks, read_ase_with_warnings, Block, ColorBlock, ColorType,
+        ColorValue, Group, NameMatch, ParseOptions, ParseWarning,
+    };
+
+    // three groups named "Brand", one named "Brand\u{200B} " and one named "Other"
+    let input = std::fs::read(format!("{FIXTURES}/valid/duplicate_groups.ase")).unwrap();
+    let names = |group: &Group| -> Vec<String> {
+        group
+            .blocks
+            .iter()
+            .map(|block| block.name.clone())
+            .collect()
+    };
+
+    let (groups, _, warnings) = read_ase_with_warnings(&*input, &ParseOptions::DEFAULT).unwrap();
+    assert_eq!(groups.len(), 5);
+    assert!(warnings.is_empty());
+
+    let exact = ParseOptions {
+        merge_duplicate_groups: Some(NameMatch::Exact),
+        ..Default::default()
+    };
+    let (groups, colors, warnings) = read_ase_with_warnings(&*input, &exact).unwrap();
+    let group_names: Vec<_> = groups.iter().map(|group| group.name.as_str()).collect();
+    assert_eq!(group_names, ["Brand", "Other", "Brand\u{200B} "]);
+    assert_eq!(names(&groups[0]), ["a", "b", "c", "d"]);
+    assert_eq!(colors.len(), 1);
+    assert_eq!(warnings, vec![ParseWarning::MergedGroups { groups: 2 }]);
+
+    let normalized = ParseOptions {
+        merge_duplicate_groups: Some(NameMatch::Normalized),
+        ..Default::default()
+    };
+    let (groups, _, warnings) = read_ase_with_warnings(&*input, &normalized).unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(names(&groups[0]), ["a", "b", "c", "d", "e"]);
+    assert_eq!(warnings, vec![ParseWarning::MergedGroups { groups: 3 }]);
+
+    // the merged group keeps the position of the first group
+    let color =
+        |name: &str| ColorBlock::new(name.to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    let brand = |colors| Block::Group(Group::new("Brand".to_owned(), colors));
+    let input = create_ase_blocks(vec![
+        brand(vec![color("a")]),
+        Block::Color(color("single")),
+        brand(vec![color("b")]),
+    ])
+    .unwrap();
+    assert_eq!(
+        read_ase_blocks(&*input, &exact).unwrap(),
+        vec![
+            brand(vec![color("a"), color("b")]),
+            Block::Color(color("single"))
+        ]
+    );
+}
+
+#[test]
+fn it_resyncs_after_corrupt_blocks() {
+    use adobe_swatch_exchange::{read_ase, read_ase_with_warnings, ParseOptions, ParseWarning};
+
+    // the length and name of the sixth of ten colors are overwritten with 10 bytes
+    let input = std::fs::read(format!("{FIXTURES}/invalid/corrupt_block_middle.ase")).unwrap();
+    assert!(read_ase(&*input).is_err());
+
+    for options in [ParseOptions::DEFAULT, ParseOptions::LENIENT] {
+        let options = ParseOptions {
+            resync_corrupt_blocks: true,
+            ..options
+        };
+        let (groups, colors, warnings) = read_ase_with_warnings(&*input, &options).unwrap();
+        assert!(groups.is_empty());
+        let names: Vec<_> = colors.iter().map(|color| color.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [0, 1, 2, 3, 4, 6, 7, 8, 9].map(|index| format!("color {index}"))
+        );
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::CorruptBlock {
+                offset: 182,
+                length: 34
+            }]
+        );
+    }
+}
+
+#[test]
+fn it_reads_repeated_empty_blocks() {
+    use adobe_swatch_exchange::{read_ase_with_warnings, AseParser, ParseOptions, ParseWarning};
+
+    // blocks consuming only a few bytes each, which used to make parsing slow
+    let header = b"ASEF\0\x01\0\0\0\0\0\x01";
+    let mut group_ends = header.to_vec();
+    let mut groups = header.to_vec();
+    for _ in 0..10_000 {
+        group_ends.extend([0xc0, 2, 0, 0, 0, 0]);
+        groups.extend([0xc0, 1, 0, 0, 0, 4, 0, 1, 0, 0]);
+    }
+
+    let (read_groups, colors, warnings) =
+        read_ase_with_warnings(&*group_ends, &ParseOptions::LENIENT).unwrap();
+    assert!(read_groups.is_empty() && colors.is_empty());
+    assert_eq!(warnings.len(), 20_000);
+    assert_eq!(
+        warnings.first(),
+        Some(&ParseWarning::UnmatchedGroupEnd { offset: 12 })
+    );
+
+    let (read_groups, _, _) = read_ase_with_warnings(&*groups, &ParseOptions::LENIENT).unwrap();
+    assert_eq!(read_groups.len(), 10_000);
+
+    let mut parser = AseParser::new();
+    for byte in &groups {
+        parser.feed(std::slice::from_ref(byte)).unwrap();
+    }
+    assert!(parser.is_finished());
+
+    let mut parser = AseParser::new();
+    let error = group_ends
+        .iter()
+        .find_map(|byte| parser.feed(std::slice::from_ref(byte)).err());
+    assert!(matches!(
+        error.as_ref().map(ASEError::root_cause),
+        Some(ASEError::Invalid(ConformationError::GroupEnd))
+    ));
+}
+
+#[test]
+fn it_validates_fixtures() {
+    use adobe_swatch_exchange::{
+        read_ase_with_options, validate_ase, ConformanceLevel, ParseOptions,
+    };
+
+    let dirs = ["valid", "lenient", "invalid"].map(|kind| format!("{FIXTURES}/{kind}"));
+    let examples = concat!(env!("CARGO_MANIFEST_DIR"), "/examples").to_owned();
+    for dir in dirs.into_iter().chain([examples]) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "ase")
+            {
+                continue;
+            }
+            let input = std::fs::read(&path).unwrap();
+
+            let summary = validate_ase(&*input);
+            let (groups, colors) = match read_ase_with_options(&*input, &ParseOptions::DEFAULT) {
+                Ok(document) => document,
+                Err(err) => {
+                    let summary = summary.map(|_| ()).unwrap_err();
+                    assert_eq!(summary.to_string(), err.to_string(), "{}", path.display());
+                    continue;
+                }
+            };
+            let summary = summary.unwrap();
+            let grouped_colors: usize = groups.iter().map(|group| group.blocks.len()).sum();
+            assert_eq!(
+                (summary.groups, summary.colors),
+                (groups.len(), grouped_colors + colors.len()),
+                "{}",
+                path.display()
+            );
+            assert_eq!(summary.info.bytes_read, input.len() as u64);
+            let strict = read_ase_with_options(&*input, &ParseOptions::STRICT).is_ok();
+            assert_eq!(
+                summary.conformance == ConformanceLevel::Strict,
+                strict,
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
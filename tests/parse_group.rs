@@ -0,0 +1,75 @@
+//! Regression inputs for `Group::parse`, covering the boundaries of its block loop.
+//!
+//! The inputs are the data of a group start block, the same way the `parse_group` fuzz
+//! target passes them.
+
+use adobe_swatch_exchange::{ASEError, ColorBlock, ColorType, ColorValue, Group};
+
+#[test]
+fn it_returns_error_on_empty_group() {
+    let res = Group::parse(&[]);
+    assert!(
+        matches!(
+            res.as_ref().err().map(ASEError::root_cause),
+            Some(ASEError::InputDataParseError)
+        ),
+        "Only ASEError::InputDataParseError should be returned"
+    );
+}
+
+#[test]
+fn it_returns_error_on_zero_name_length() {
+    let res = Group::parse(&[0, 0]);
+    assert!(
+        matches!(
+            res.as_ref().err().map(ASEError::root_cause),
+            Some(ASEError::InputDataParseError)
+        ),
+        "Only ASEError::InputDataParseError should be returned"
+    );
+}
+
+#[test]
+fn it_reads_name_only() {
+    let res = Group::parse(&[0, 1, 0, 0]);
+    assert_eq!(res.unwrap(), Group::new("".to_owned(), vec![]));
+}
+
+#[test]
+fn it_ignores_single_trailing_byte() {
+    // a single byte is not enough for another block type
+    let res = Group::parse(&[0, 1, 0, 0, 0]);
+    assert_eq!(res.unwrap(), Group::new("".to_owned(), vec![]));
+}
+
+#[test]
+fn it_returns_error_on_truncated_block_length() {
+    let res = Group::parse(&[0, 1, 0, 0, 0, 1, 0, 0]);
+    assert!(
+        matches!(
+            res.as_ref().err().map(ASEError::root_cause),
+            Some(ASEError::InputDataParseError)
+        ),
+        "Only ASEError::InputDataParseError should be returned"
+    );
+}
+
+#[test]
+fn it_stops_on_huge_block_length() {
+    // the color block claims to be `u32::MAX` bytes long, so that the next block would be
+    // far outside of the group
+    let res = Group::parse(&[
+        0, 1, 0, 0, 0, 1, 255, 255, 255, 255, 0, 1, 0, 0, 71, 114, 97, 121, 63, 0, 0, 0, 0, 2, 0, 1,
+    ]);
+    assert_eq!(
+        res.unwrap(),
+        Group::new(
+            "".to_owned(),
+            vec![ColorBlock::new(
+                "".to_owned(),
+                ColorValue::Gray(0.5),
+                ColorType::Normal
+            )]
+        )
+    );
+}
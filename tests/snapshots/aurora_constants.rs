@@ -0,0 +1,11 @@
+// @generated by adobe-swatch-exchange
+
+pub mod aurora {
+    pub const RED: [f32; 3] = [0.7490196, 0.38039216, 0.41568628];
+    pub const ORANGE: [f32; 3] = [0.8156863, 0.5294118, 0.4392157];
+    pub const YELLOW: [f32; 3] = [0.92156863, 0.79607844, 0.54509807];
+    pub const GREEN: [f32; 3] = [0.6392157, 0.74509805, 0.54901963];
+    pub const PURPLE_MAGENTA: [f32; 3] = [0.7058824, 0.5568628, 0.6784314];
+}
+
+pub const _2ND_POLAR_NIGHT: [f32; 3] = [0.18039216, 0.20392157, 0.2509804];
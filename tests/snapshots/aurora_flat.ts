@@ -0,0 +1,13 @@
+// @generated by adobe-swatch-exchange
+
+export const auroraRed = [0.7490196, 0.38039216, 0.41568628] as const;
+
+export const auroraOrange = [0.8156863, 0.5294118, 0.4392157] as const;
+
+export const auroraYellow = [0.92156863, 0.79607844, 0.54509807] as const;
+
+export const auroraGreen = [0.6392157, 0.74509805, 0.54901963] as const;
+
+export const auroraPurpleMagenta = [0.7058824, 0.5568628, 0.6784314] as const;
+
+export const _2ndPolarNight = [0.18039216, 0.20392157, 0.2509804] as const;
@@ -0,0 +1,11 @@
+// @generated by adobe-swatch-exchange
+
+export const aurora = {
+  red: '#bf616a',
+  orange: '#d08770',
+  yellow: '#ebcb8b',
+  green: '#a3be8c',
+  purpleMagenta: '#b48ead',
+} as const;
+
+export const _2ndPolarNight = '#2e3440' as const;
@@ -0,0 +1,22 @@
+// @generated by adobe-swatch-exchange
+
+pub struct Swatch {
+    pub name: &'static str,
+    pub color: &'static str,
+}
+
+pub mod aurora {
+    use super::Swatch;
+
+    pub const SWATCHES: [Swatch; 5] = [
+        Swatch { name: "Red", color: "#bf616a" },
+        Swatch { name: "Orange", color: "#d08770" },
+        Swatch { name: "Yellow", color: "#ebcb8b" },
+        Swatch { name: "Green", color: "#a3be8c" },
+        Swatch { name: "Purple / Magenta", color: "#b48ead" },
+    ];
+}
+
+pub const SWATCHES: [Swatch; 1] = [
+    Swatch { name: "2nd Polar Night", color: "#2e3440" },
+];
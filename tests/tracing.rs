@@ -0,0 +1,100 @@
+//! Checks the events emitted by the parser with the `tracing` feature.
+
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use adobe_swatch_exchange::read_ase;
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Collects the names of the spans and the messages of the events.
+#[derive(Clone, Default)]
+struct Capture {
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<String>>>,
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.spans
+            .lock()
+            .unwrap()
+            .push(span.metadata().name().to_owned());
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        self.messages.lock().unwrap().push(message.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn capture(path: &str) -> Capture {
+    let data = std::fs::read(format!("{FIXTURES}/{path}")).unwrap();
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || read_ase(&*data).unwrap());
+    capture
+}
+
+#[test]
+fn it_traces_tolerated_deviations() {
+    let capture = capture("lenient/group_name_without_terminator.ase");
+    let spans = capture.spans.lock().unwrap();
+    assert!(spans.iter().any(|span| span == "block"));
+    assert!(spans.iter().any(|span| span == "color entry"));
+    let messages = capture.messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|message| message == "tolerated deviation from the format"));
+}
+
+#[test]
+fn it_traces_recovered_names() {
+    let capture = capture("lenient/name_length_uncounted.ase");
+    let messages = capture.messages.lock().unwrap();
+    assert!(messages
+        .iter()
+        .any(|message| message.starts_with("read a name, whose terminator")));
+}
+
+#[test]
+fn it_does_not_trace_deviations_of_valid_files() {
+    let capture = capture("valid/color_group_color.ase");
+    assert!(capture.messages.lock().unwrap().is_empty());
+}
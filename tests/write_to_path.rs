@@ -0,0 +1,130 @@
+//! Tests reading palettes from and atomically writing them to paths.
+
+use std::{fs, path::PathBuf};
+
+use adobe_swatch_exchange::{
+    read_ase, read_ase_file, write_ase_file, write_ase_to_path, ASEError, ColorBlock, ColorType,
+    ColorValue, ConformationError, Group,
+};
+
+/// Returns an empty directory, unique to the test.
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "adobe-swatch-exchange-{test}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn palette() -> (Vec<Group>, Vec<ColorBlock>) {
+    let group = Group::new(
+        "group".to_owned(),
+        vec![ColorBlock::new(
+            "blue".to_owned(),
+            ColorValue::Rgb(0.0, 0.0, 1.0),
+            ColorType::Global,
+        )],
+    );
+    let color = ColorBlock::new("gray".to_owned(), ColorValue::Gray(0.5), ColorType::Normal);
+    (vec![group], vec![color])
+}
+
+#[test]
+fn it_writes_to_path() {
+    let dir = temp_dir("writes");
+    let path = dir.join("palette.ase");
+    let (groups, colors) = palette();
+
+    write_ase_to_path(&path, groups.clone(), colors.clone()).unwrap();
+    assert_eq!(
+        read_ase(fs::File::open(&path).unwrap()).unwrap(),
+        (groups, colors)
+    );
+    // only the palette is left, the temporary file has been renamed
+    let files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, vec!["palette.ase"]);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn it_replaces_existing_files() {
+    let dir = temp_dir("replaces");
+    let path = dir.join("palette.ase");
+    fs::write(&path, b"old").unwrap();
+    let (groups, colors) = palette();
+
+    write_ase_to_path(&path, groups.clone(), colors.clone()).unwrap();
+    assert_eq!(
+        read_ase(fs::File::open(&path).unwrap()).unwrap(),
+        (groups, colors)
+    );
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn it_keeps_existing_files_on_error() {
+    let dir = temp_dir("keeps");
+    let path = dir.join("palette.ase");
+    fs::write(&path, b"old").unwrap();
+    let color = ColorBlock::new(
+        "a".repeat(usize::from(u16::MAX)),
+        ColorValue::Gray(0.5),
+        ColorType::Normal,
+    );
+
+    assert!(write_ase_to_path(&path, vec![], vec![color]).is_err());
+    assert_eq!(fs::read(&path).unwrap(), b"old");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn it_reads_and_writes_files() {
+    let dir = temp_dir("files");
+    let path = dir.join("palette.ase");
+    let (groups, colors) = palette();
+
+    write_ase_file(&path, groups.clone(), colors.clone()).unwrap();
+    assert_eq!(read_ase_file(&path).unwrap(), (groups, colors));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn it_includes_the_path_in_errors() {
+    let dir = temp_dir("file-errors");
+    let missing = dir.join("missing.ase");
+    let err = read_ase_file(&missing).unwrap_err();
+    assert!(matches!(
+        &err,
+        ASEError::File { path, .. } if path == &missing
+    ));
+    assert!(matches!(
+        err.root_cause(),
+        ASEError::Io(err) if err.kind() == std::io::ErrorKind::NotFound
+    ));
+    assert!(err.to_string().starts_with(&missing.display().to_string()));
+
+    let invalid = dir.join("invalid.ase");
+    fs::write(&invalid, b"ASEF\0\x02\0\0\0\0\0\0").unwrap();
+    assert!(matches!(
+        read_ase_file(&invalid)
+            .as_ref()
+            .map_err(ASEError::root_cause),
+        Err(ASEError::Invalid(ConformationError::FileVersion))
+    ));
+
+    let err = write_ase_file(dir.join("missing").join("palette.ase"), vec![], vec![]).unwrap_err();
+    assert!(matches!(err, ASEError::File { .. }));
+
+    fs::remove_dir_all(dir).unwrap();
+}